@@ -0,0 +1,88 @@
+//! Compares the bitmask match counting of day 04 with the `HashSet`s it replaced, on a large synthetic input.
+//!
+//! Run with `cargo bench --bench day04`. Both sides parse every card, so the difference is the cost of counting the
+//! matches, and of allocating two sets per card.
+
+use aoc2023::day04;
+use std::{
+    collections::HashSet,
+    fmt::Write,
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+/// Number of cards of the input.
+const N_CARDS: usize = 20_000;
+
+/// Runs of each side, the fastest one is reported.
+const RUNS: usize = 20;
+
+fn main() {
+    let input = cards(N_CARDS);
+
+    let (bitmask, points) = fastest(|| day04::solve_part1(black_box(&input)).unwrap());
+    let (hash_sets, expected) = fastest(|| total_points_hash_sets(black_box(&input)));
+    assert_eq!(points, expected);
+
+    println!("{N_CARDS} cards, fastest of {RUNS} runs:");
+    println!("  bitmasks:  {bitmask:?}");
+    println!("  hash sets: {hash_sets:?}");
+    println!("  speedup:   {:.1}x", hash_sets.as_secs_f64() / bitmask.as_secs_f64());
+}
+
+/// Returns `n` cards shaped like the real input, 10 winning and 25 lottery numbers below 100 each, few of them
+/// matching.
+fn cards(n: usize) -> String {
+    // xorshift, the same input on every run
+    let mut state = 2023u64;
+    let mut number = move || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state % 99 + 1
+    };
+
+    let mut input = String::new();
+    for id in 1..=n {
+        let winning: Vec<_> = (0..10).map(|_| number()).collect();
+        let lottery: Vec<_> = (0..25).map(|_| number()).collect();
+        let join = |ns: &[u64]| ns.iter().map(|n| format!("{n:2}")).collect::<Vec<_>>().join(" ");
+        writeln!(input, "Card {id:5}: {} | {}", join(&winning), join(&lottery)).unwrap();
+    }
+    input
+}
+
+/// Returns the fastest running time of `f` over [`RUNS`] runs, and its result.
+fn fastest<T>(mut f: impl FnMut() -> T) -> (Duration, T) {
+    let mut best = Duration::MAX;
+    let mut result = None;
+    for _ in 0..RUNS {
+        let start = Instant::now();
+        let value = f();
+        best = best.min(start.elapsed());
+        result = Some(value);
+    }
+    (best, result.unwrap())
+}
+
+/// Returns the total points of the cards, collecting the numbers of each card in two `HashSet`s.
+fn total_points_hash_sets(input: &str) -> u32 {
+    input
+        .lines()
+        .map(|line| {
+            let (_, numbers) = line.split_once(':').unwrap();
+            let (winning, yours) = numbers.split_once('|').unwrap();
+            let set = |s: &str| {
+                s.split_whitespace()
+                    .map(|n| n.parse().unwrap())
+                    .collect::<HashSet<u32>>()
+            };
+            let matches = set(winning).intersection(&set(yours)).count() as u32;
+            if matches == 0 {
+                0
+            } else {
+                1 << (matches - 1)
+            }
+        })
+        .sum()
+}
@@ -0,0 +1,17 @@
+//! Feeds arbitrary inputs to the parse and solve path of every day, which must return an error instead of panicking.
+//!
+//! The first byte selects the day, and the rest is the input. Run with `cargo fuzz run solve`.
+
+#![no_main]
+
+use aoc2023::runner;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let Some((&day, input)) = data.split_first() else {
+        return;
+    };
+    if let (Some(run), Ok(input)) = (runner(day % 25 + 1), std::str::from_utf8(input)) {
+        let _ = run(input);
+    }
+});
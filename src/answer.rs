@@ -0,0 +1,113 @@
+//! Answers of the puzzles.
+//!
+//! Every day returns the answers of its two parts as [`Answer`]s, so they can be printed, compared with known answers
+//! or serialized as JSON without parsing the printed text back.
+
+use std::fmt::{self, Display, Write};
+
+//----------
+// Enums
+//----------
+
+/// Answer of a part of a puzzle.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Answer {
+    U64(u64),
+    I64(i64),
+    Text(String),
+}
+
+//----------
+// Implementations
+//----------
+
+impl Answer {
+    /// Serializes the answer as a JSON value: a number, or a string for text answers.
+    pub fn to_json(&self) -> String {
+        match self {
+            Answer::U64(n) => n.to_string(),
+            Answer::I64(n) => n.to_string(),
+            Answer::Text(text) => {
+                let mut json = String::with_capacity(text.len() + 2);
+                json.push('"');
+                for c in text.chars() {
+                    match c {
+                        '"' => json.push_str("\\\""),
+                        '\\' => json.push_str("\\\\"),
+                        '\n' => json.push_str("\\n"),
+                        '\r' => json.push_str("\\r"),
+                        '\t' => json.push_str("\\t"),
+                        c if c.is_control() => write!(json, "\\u{:04x}", c as u32).unwrap(),
+                        c => json.push(c),
+                    }
+                }
+                json.push('"');
+                json
+            }
+        }
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::U64(n) => write!(f, "{n}"),
+            Answer::I64(n) => write!(f, "{n}"),
+            Answer::Text(text) => write!(f, "{text}"),
+        }
+    }
+}
+
+/// Implements `From` for the unsigned integer types, widened to `u64`.
+macro_rules! from_unsigned {
+    ($($t:ty),*) => {
+        $(impl From<$t> for Answer {
+            fn from(n: $t) -> Answer {
+                Answer::U64(n as u64)
+            }
+        })*
+    };
+}
+
+from_unsigned!(u8, u16, u32, u64, usize);
+
+impl From<i64> for Answer {
+    fn from(n: i64) -> Answer {
+        Answer::I64(n)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(text: String) -> Answer {
+        Answer::Text(text)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(text: &str) -> Answer {
+        Answer::Text(text.to_owned())
+    }
+}
+
+//----------
+// Tests
+//----------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_answer() {
+        assert_eq!(Answer::from(42usize), Answer::U64(42));
+        assert_eq!(Answer::from(-7i64).to_string(), "-7");
+        assert_eq!(Answer::from("button").to_string(), "button");
+
+        assert_eq!(Answer::U64(114).to_json(), "114");
+        assert_eq!(Answer::I64(-2).to_json(), "-2");
+        assert_eq!(
+            Answer::from("say \"hi\"\\\n\u{1}").to_json(),
+            r#""say \"hi\"\\\n\u0001""#
+        );
+    }
+}
@@ -0,0 +1,185 @@
+//! Bump allocation of strings, for inputs with thousands of short labels.
+//!
+//! `StrArena` appends every string to a single growing buffer and hands out ids instead of references, so storing a
+//! label costs no allocation of its own. `Interner` builds on it to map labels to dense `u32` ids without owning a
+//! `String` per key: its hash table only stores the hashes of the labels.
+
+use std::{
+    collections::{hash_map::RandomState, HashMap},
+    hash::BuildHasher,
+    ops::Range,
+};
+
+//----------
+// Structs
+//----------
+
+/// Append-only storage of strings, kept back to back in one buffer.
+#[derive(Clone, Debug, Default)]
+pub struct StrArena {
+    buf: String,
+    spans: Vec<Range<u32>>,
+}
+
+/// Assigns dense ids to distinct strings, in order of first appearance, storing each string once in a `StrArena`.
+#[derive(Clone, Debug, Default)]
+pub struct Interner {
+    strings: StrArena,
+    /// First id of every hash value.
+    heads: HashMap<u64, u32>,
+    /// Next id with the same hash, or `NONE`, for each id.
+    collisions: Vec<u32>,
+    hasher: RandomState,
+}
+
+//----------
+// Implementations
+//----------
+
+impl StrArena {
+    /// Creates an empty arena.
+    pub fn new() -> StrArena {
+        StrArena::default()
+    }
+
+    /// Creates an empty arena able to hold `n_strings` strings of `n_bytes` bytes in total without reallocating.
+    pub fn with_capacity(n_strings: usize, n_bytes: usize) -> StrArena {
+        StrArena {
+            buf: String::with_capacity(n_bytes),
+            spans: Vec::with_capacity(n_strings),
+        }
+    }
+
+    /// Appends `s` to the arena, returning its id. Ids are consecutive, starting at 0.
+    pub fn alloc(&mut self, s: &str) -> u32 {
+        let start = self.buf.len() as u32;
+        self.buf.push_str(s);
+        self.spans.push(start..self.buf.len() as u32);
+        self.spans.len() as u32 - 1
+    }
+
+    /// Returns the string `id`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no string `id`.
+    pub fn get(&self, id: u32) -> &str {
+        let span = &self.spans[id as usize];
+        &self.buf[span.start as usize..span.end as usize]
+    }
+
+    /// Returns the number of strings in the arena.
+    pub fn len(&self) -> usize {
+        self.spans.len()
+    }
+
+    /// Checks whether the arena holds no strings.
+    pub fn is_empty(&self) -> bool {
+        self.spans.is_empty()
+    }
+
+    /// Returns an iterator over the strings, in order of their ids.
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        (0..self.len() as u32).map(|id| self.get(id))
+    }
+}
+
+impl Interner {
+    const NONE: u32 = u32::MAX;
+
+    /// Creates an empty interner.
+    pub fn new() -> Interner {
+        Interner::default()
+    }
+
+    /// Returns the id of `s`, assigning the next id if it was not interned yet.
+    pub fn intern(&mut self, s: &str) -> u32 {
+        let hash = self.hasher.hash_one(s);
+        if let Some(id) = self.find(hash, s) {
+            return id;
+        }
+
+        let id = self.strings.alloc(s);
+        let previous = self.heads.insert(hash, id);
+        self.collisions.push(previous.unwrap_or(Self::NONE));
+        id
+    }
+
+    /// Returns the id of `s`, if it was interned.
+    pub fn id(&self, s: &str) -> Option<u32> {
+        self.find(self.hasher.hash_one(s), s)
+    }
+
+    /// Returns the string with id `id`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no string `id`.
+    pub fn get(&self, id: u32) -> &str {
+        self.strings.get(id)
+    }
+
+    /// Returns the number of distinct strings.
+    pub fn len(&self) -> usize {
+        self.strings.len()
+    }
+
+    /// Checks whether no string was interned.
+    pub fn is_empty(&self) -> bool {
+        self.strings.is_empty()
+    }
+
+    /// Follows the chain of ids with hash `hash` looking for `s`.
+    fn find(&self, hash: u64, s: &str) -> Option<u32> {
+        let mut id = *self.heads.get(&hash)?;
+        while id != Self::NONE {
+            if self.strings.get(id) == s {
+                return Some(id);
+            }
+            id = self.collisions[id as usize];
+        }
+        None
+    }
+}
+
+//----------
+// Tests
+//----------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_str_arena() {
+        let mut arena = StrArena::with_capacity(3, 8);
+        assert_eq!(arena.alloc("jqt"), 0);
+        assert_eq!(arena.alloc(""), 1);
+        assert_eq!(arena.alloc("rhn"), 2);
+        assert_eq!(arena.get(2), "rhn");
+        assert_eq!(arena.iter().collect::<Vec<_>>(), ["jqt", "", "rhn"]);
+        assert!(!arena.is_empty());
+    }
+
+    #[test]
+    fn test_interner() {
+        let mut interner = Interner::new();
+        assert_eq!(interner.intern("jqt"), 0);
+        assert_eq!(interner.intern("rhn"), 1);
+        assert_eq!(interner.intern("jqt"), 0);
+        assert_eq!(interner.len(), 2);
+        assert_eq!(interner.id("rhn"), Some(1));
+        assert_eq!(interner.id("xhk"), None);
+        assert_eq!(interner.get(1), "rhn");
+
+        // many labels, same ids as a plain map
+        let mut expected = HashMap::new();
+        for i in 0..5000 {
+            let label = format!("n{}", i % 3000);
+            let next = expected.len() as u32;
+            let id = *expected.entry(label.clone()).or_insert(next);
+            assert_eq!(interner.intern(&label), id + 2);
+        }
+        assert_eq!(interner.len(), 3002);
+    }
+}
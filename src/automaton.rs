@@ -0,0 +1,220 @@
+//! Multi-pattern string search with Aho-Corasick automata.
+//!
+//! The patterns are stored in a trie whose nodes also get a failure link: the node of the longest proper suffix of
+//! their string that is in the trie. Following the links ahead of time turns the trie into a complete transition table,
+//! so a text is searched for all the patterns at once in a single pass, one table lookup per byte.
+//!
+//! A `Matcher` keeps two automata, one of the patterns and one of the patterns reversed, to find both the first match
+//! of a text, scanning it forwards, and the last match, scanning it backwards.
+
+use std::{collections::VecDeque, ops::Range};
+
+//----------
+// Structs
+//----------
+
+/// Finds the first and the last occurrence of any of a set of patterns, each associated with a value.
+#[derive(Clone, Debug)]
+pub struct Matcher<V> {
+    forward: Automaton<V>,
+    backward: Automaton<V>,
+}
+
+/// Occurrence of a pattern in a text: its byte range and the value of the pattern.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Match<V> {
+    pub range: Range<usize>,
+    pub value: V,
+}
+
+/// Aho-Corasick automaton as a complete transition table. State 0 is the root, the empty string.
+#[derive(Clone, Debug)]
+struct Automaton<V> {
+    transitions: Vec<[u32; 256]>,
+    /// Longest pattern that is a suffix of the string of each state, as its length and value.
+    longest: Vec<Option<(usize, V)>>,
+    /// Length of the longest pattern.
+    max_len: usize,
+}
+
+//----------
+// Implementations
+//----------
+
+impl<V: Copy> Matcher<V> {
+    /// Creates a matcher for `patterns`, given with their values. If a pattern is repeated, its last value is kept.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a pattern is empty.
+    pub fn new<'p>(patterns: impl IntoIterator<Item = (&'p str, V)>) -> Matcher<V> {
+        let patterns: Vec<_> = patterns.into_iter().collect();
+        assert!(patterns.iter().all(|(p, _)| !p.is_empty()), "empty pattern");
+
+        Matcher {
+            forward: Automaton::new(patterns.iter().map(|&(p, v)| (p.bytes().collect(), v))),
+            backward: Automaton::new(patterns.iter().map(|&(p, v)| (p.bytes().rev().collect(), v))),
+        }
+    }
+
+    /// Returns the occurrence of a pattern starting first in `text`. Of the patterns starting at the same byte, the
+    /// longest one is returned.
+    pub fn find_first(&self, text: &[u8]) -> Option<Match<V>> {
+        let (start, len, value) = self.forward.leftmost(text.iter().copied())?;
+        Some(Match {
+            range: start..start + len,
+            value,
+        })
+    }
+
+    /// Returns the occurrence of a pattern ending last in `text`. Of the patterns ending at the same byte, the longest
+    /// one is returned.
+    pub fn find_last(&self, text: &[u8]) -> Option<Match<V>> {
+        let (start, len, value) = self.backward.leftmost(text.iter().rev().copied())?;
+        let end = text.len() - start;
+        Some(Match {
+            range: end - len..end,
+            value,
+        })
+    }
+}
+
+impl<V: Copy> Automaton<V> {
+    /// Builds the automaton of `patterns`, given as bytes with their values.
+    fn new(patterns: impl IntoIterator<Item = (Vec<u8>, V)>) -> Automaton<V> {
+        let mut automaton = Automaton {
+            transitions: vec![[0; 256]],
+            longest: vec![None],
+            max_len: 0,
+        };
+
+        // trie, where 0 marks the missing children as no node points back to the root
+        for (pattern, value) in patterns {
+            let mut state = 0;
+            for &b in &pattern {
+                let next = automaton.transitions[state][b as usize] as usize;
+                state = if next != 0 { next } else { automaton.add_child(state, b) };
+            }
+            automaton.longest[state] = Some((pattern.len(), value));
+            automaton.max_len = automaton.max_len.max(pattern.len());
+        }
+
+        // failure links in breadth-first order, so the links of shallower states are complete when they are followed
+        let mut queue: VecDeque<_> = automaton.transitions[0]
+            .iter()
+            .filter(|&&child| child != 0)
+            .map(|&child| (child as usize, 0))
+            .collect();
+        while let Some((state, fail)) = queue.pop_front() {
+            if automaton.longest[state].is_none() {
+                automaton.longest[state] = automaton.longest[fail];
+            }
+            for b in 0..256 {
+                let child = automaton.transitions[state][b] as usize;
+                let fail_child = automaton.transitions[fail][b];
+                if child != 0 {
+                    queue.push_back((child, fail_child as usize));
+                } else {
+                    automaton.transitions[state][b] = fail_child;
+                }
+            }
+        }
+
+        automaton
+    }
+
+    fn add_child(&mut self, state: usize, b: u8) -> usize {
+        let child = self.transitions.len();
+        self.transitions.push([0; 256]);
+        self.longest.push(None);
+        self.transitions[state][b as usize] = child as u32;
+        child
+    }
+
+    /// Returns the match starting first in `bytes`, as its start, length and value.
+    fn leftmost(&self, bytes: impl Iterator<Item = u8>) -> Option<(usize, usize, V)> {
+        let mut best: Option<(usize, usize, V)> = None;
+        let mut state = 0;
+
+        for (i, b) in bytes.enumerate() {
+            // matches ending later than `i` start after `i + 1 - max_len`
+            if best.is_some_and(|(start, _, _)| start + self.max_len <= i) {
+                break;
+            }
+
+            state = self.transitions[state][b as usize] as usize;
+            if let Some((len, value)) = self.longest[state] {
+                let start = i + 1 - len;
+                if best.is_none_or(|(best_start, best_len, _)| (start, best_len) < (best_start, len)) {
+                    best = Some((start, len, value));
+                }
+            }
+        }
+
+        best
+    }
+}
+
+//----------
+// Tests
+//----------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WORDS: [(&str, u32); 3] = [("one", 1), ("eight", 8), ("two", 2)];
+
+    #[test]
+    fn test_find() {
+        let matcher = Matcher::new(WORDS);
+        let text = b"xtwoneightx";
+        assert_eq!(matcher.find_first(text), Some(Match { range: 1..4, value: 2 }));
+        assert_eq!(matcher.find_last(text), Some(Match { range: 5..10, value: 8 }));
+
+        assert_eq!(matcher.find_first(b"on"), None);
+        assert_eq!(matcher.find_last(b""), None);
+    }
+
+    #[test]
+    fn test_nested_patterns() {
+        // a pattern ending first does not hide a longer one starting earlier
+        let matcher = Matcher::new([("abcd", 1), ("bc", 2), ("c", 3)]);
+        assert_eq!(matcher.find_first(b"xabcd"), Some(Match { range: 1..5, value: 1 }));
+        assert_eq!(matcher.find_first(b"xabcx"), Some(Match { range: 2..4, value: 2 }));
+        assert_eq!(matcher.find_last(b"abcdx"), Some(Match { range: 0..4, value: 1 }));
+        assert_eq!(matcher.find_last(b"xbcab"), Some(Match { range: 1..3, value: 2 }));
+
+        // failure links reach patterns that are suffixes of the current state
+        let matcher = Matcher::new([("she", 1), ("he", 2), ("hers", 3)]);
+        assert_eq!(matcher.find_first(b"ushers"), Some(Match { range: 1..4, value: 1 }));
+        assert_eq!(matcher.find_last(b"ushers"), Some(Match { range: 2..6, value: 3 }));
+    }
+
+    #[test]
+    fn test_against_naive() {
+        let matcher = Matcher::new(WORDS);
+        let naive_first = |text: &str| {
+            (0..text.len()).find_map(|i| WORDS.iter().find(|(w, _)| text[i..].starts_with(w)).map(|&(_, v)| v))
+        };
+        let naive_last = |text: &str| {
+            (0..=text.len())
+                .rev()
+                .find_map(|i| WORDS.iter().find(|(w, _)| text[..i].ends_with(w)).map(|&(_, v)| v))
+        };
+
+        for text in [
+            "",
+            "oneight",
+            "twone",
+            "eightwothree",
+            "xxeighxtwo",
+            "onetwoeight",
+            "ttwwoo",
+        ] {
+            let bytes = text.as_bytes();
+            assert_eq!(matcher.find_first(bytes).map(|m| m.value), naive_first(text), "{text}");
+            assert_eq!(matcher.find_last(bytes).map(|m| m.value), naive_last(text), "{text}");
+        }
+    }
+}
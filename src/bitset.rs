@@ -0,0 +1,185 @@
+//! Fixed capacity set of small unsigned integers, stored as a bitmask.
+//!
+//! `BitSet<W>` holds values in `0..64 * W` inside `W` words, without heap allocations. The default of two words (128
+//! values) fits puzzle numbers below 100; larger sets, like the visited states of a grid, just use more words.
+
+use std::fmt;
+
+//----------
+// Structs
+//----------
+
+/// Set of integers in `0..64 * W`.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BitSet<const W: usize = 2> {
+    words: [u64; W],
+}
+
+//----------
+// Implementations
+//----------
+
+impl<const W: usize> BitSet<W> {
+    /// Number of values that fit in the set.
+    pub const CAPACITY: usize = 64 * W;
+
+    /// Creates an empty set.
+    pub fn new() -> BitSet<W> {
+        BitSet { words: [0; W] }
+    }
+
+    /// Adds `val` to the set. Returns `false` if it was already present.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `val` is not lower than `CAPACITY`.
+    pub fn insert(&mut self, val: usize) -> bool {
+        let (word, mask) = Self::locate(val);
+        let is_new = self.words[word] & mask == 0;
+        self.words[word] |= mask;
+        is_new
+    }
+
+    /// Removes `val` from the set. Returns `false` if it was not present.
+    pub fn remove(&mut self, val: usize) -> bool {
+        if val >= Self::CAPACITY {
+            return false;
+        }
+        let (word, mask) = Self::locate(val);
+        let was_present = self.words[word] & mask != 0;
+        self.words[word] &= !mask;
+        was_present
+    }
+
+    /// Checks whether `val` is in the set.
+    pub fn contains(&self, val: usize) -> bool {
+        if val >= Self::CAPACITY {
+            return false;
+        }
+        let (word, mask) = Self::locate(val);
+        self.words[word] & mask != 0
+    }
+
+    /// Returns the number of values in the set.
+    pub fn len(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    /// Checks whether the set is empty.
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|&w| w == 0)
+    }
+
+    /// Removes all values.
+    pub fn clear(&mut self) {
+        self.words = [0; W];
+    }
+
+    /// Returns the values present in both sets.
+    pub fn intersection(&self, other: &BitSet<W>) -> BitSet<W> {
+        BitSet {
+            words: std::array::from_fn(|i| self.words[i] & other.words[i]),
+        }
+    }
+
+    /// Returns the values present in any of the sets.
+    pub fn union(&self, other: &BitSet<W>) -> BitSet<W> {
+        BitSet {
+            words: std::array::from_fn(|i| self.words[i] | other.words[i]),
+        }
+    }
+
+    /// Returns an iterator over the values of the set, in increasing order.
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        self.words.iter().enumerate().flat_map(|(i, &word)| {
+            let mut word = word;
+            std::iter::from_fn(move || {
+                if word == 0 {
+                    return None;
+                }
+                let bit = word.trailing_zeros() as usize;
+                word &= word - 1; // clears the lowest set bit
+                Some(i * 64 + bit)
+            })
+        })
+    }
+
+    /// Returns the word index and bit mask of `val`.
+    fn locate(val: usize) -> (usize, u64) {
+        assert!(val < Self::CAPACITY, "value {val} does not fit in the BitSet");
+        (val / 64, 1 << (val % 64))
+    }
+}
+
+//----------
+// Traits
+//----------
+
+impl<const W: usize> Default for BitSet<W> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const W: usize> FromIterator<usize> for BitSet<W> {
+    fn from_iter<T: IntoIterator<Item = usize>>(iter: T) -> Self {
+        let mut set = BitSet::new();
+        iter.into_iter().for_each(|val| {
+            set.insert(val);
+        });
+        set
+    }
+}
+
+impl<const W: usize> fmt::Debug for BitSet<W> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+//----------
+// Tests
+//----------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tests() {
+        let mut set = BitSet::<2>::new();
+        assert!(set.is_empty());
+        assert!(set.insert(3));
+        assert!(set.insert(64));
+        assert!(set.insert(127));
+        assert!(!set.insert(3));
+
+        assert!(set.contains(64));
+        assert!(!set.contains(65));
+        assert!(!set.contains(1000));
+        assert_eq!(set.len(), 3);
+        assert_eq!(set.iter().collect::<Vec<_>>(), [3, 64, 127]);
+
+        assert!(set.remove(64));
+        assert!(!set.remove(64));
+        assert_eq!(set.len(), 2);
+
+        set.clear();
+        assert!(set.is_empty());
+    }
+
+    #[test]
+    fn test_set_operations() {
+        let a: BitSet = [41, 48, 83, 86, 17].into_iter().collect();
+        let b: BitSet = [83, 86, 6, 31, 17, 9, 48, 53].into_iter().collect();
+
+        assert_eq!(a.intersection(&b).iter().collect::<Vec<_>>(), [17, 48, 83, 86]);
+        assert_eq!(a.union(&b).len(), 9);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_out_of_range() {
+        BitSet::<1>::new().insert(64);
+    }
+}
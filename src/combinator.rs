@@ -0,0 +1,307 @@
+//! Small parser combinator library, for inputs with more structure than whitespace separated tokens.
+//!
+//! A parser is a function taking the remaining input and returning the rest of the input together with the parsed
+//! value, or a `Failure` pointing at where it failed. Parsers are combined by the functions of this module, for
+//! example a line like `AAA = (BBB, CCC)` is parsed by:
+//!
+//! ```
+//! use aoc2023::combinator::*;
+//!
+//! let node = pair(
+//!     terminated(word, tag(" = ")),
+//!     delimited(tag("("), separated_list(word, tag(", ")), tag(")")),
+//! );
+//! let (name, next) = complete("AAA = (BBB, CCC)", node).unwrap();
+//! assert_eq!((name, next), ("AAA", vec!["BBB", "CCC"]));
+//! ```
+//!
+//! Whitespace is not skipped implicitly, it must be part of the tags or consumed with `space`.
+
+use crate::error::AocError;
+use std::{fmt, str::FromStr};
+
+/// Result of a parser: the remaining input and the parsed value.
+pub type PResult<'a, T> = Result<(&'a str, T), Failure<'a>>;
+
+//----------
+// Structs
+//----------
+
+/// Reason why a parser failed, and the input it failed at.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Failure<'a> {
+    pub rest: &'a str,
+    pub expected: Expected,
+}
+
+/// What a failed parser expected to find.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Expected {
+    /// A literal string.
+    Tag(&'static str),
+    /// A kind of token, like "a number".
+    Kind(&'static str),
+    /// The end of the input.
+    End,
+}
+
+//----------
+// Traits
+//----------
+
+/// Parses a value of type `T` from the start of the input.
+///
+/// Implemented for every function with the signature of a parser, so closures and plain functions (like `word`) can
+/// be used directly.
+pub trait Parser<'a, T> {
+    fn parse(&self, input: &'a str) -> PResult<'a, T>;
+}
+
+impl<'a, T, F> Parser<'a, T> for F
+where
+    F: Fn(&'a str) -> PResult<'a, T>,
+{
+    fn parse(&self, input: &'a str) -> PResult<'a, T> {
+        self(input)
+    }
+}
+
+impl fmt::Display for Expected {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Expected::Tag(tag) => write!(f, "`{tag}`"),
+            Expected::Kind(kind) => write!(f, "{kind}"),
+            Expected::End => write!(f, "end of input"),
+        }
+    }
+}
+
+//----------
+// Helper methods
+//----------
+
+/// Applies `parser` to the whole `input`, failing if anything is left over.
+///
+/// Errors are reported with the column where parsing failed, relative to `input`.
+pub fn complete<'a, T>(input: &'a str, parser: impl Parser<'a, T>) -> Result<T, AocError> {
+    let to_error = |f: Failure| {
+        let found = match f.rest.chars().next() {
+            Some(c) => format!("`{c}`"),
+            None => "end of input".to_owned(),
+        };
+        AocError::parse_at(input, f.rest, format!("Unexpected {found}, expected {}.", f.expected))
+    };
+
+    match parser.parse(input) {
+        Ok(("", value)) => Ok(value),
+        Ok((rest, _)) => Err(to_error(fail(rest, Expected::End))),
+        Err(f) => Err(to_error(f)),
+    }
+}
+
+fn fail<'a>(rest: &'a str, expected: Expected) -> Failure<'a> {
+    Failure { rest, expected }
+}
+
+/// Splits the longest prefix of `input` whose characters satisfy `pred`.
+fn take_while(input: &str, pred: impl Fn(char) -> bool) -> (&str, &str) {
+    let end = input.find(|c| !pred(c)).unwrap_or(input.len());
+    input.split_at(end)
+}
+
+//----------
+// Basic parsers
+//----------
+
+/// Matches the literal `tag`.
+pub fn tag<'a>(tag: &'static str) -> impl Fn(&'a str) -> PResult<'a, &'a str> {
+    move |input: &'a str| match input.strip_prefix(tag) {
+        Some(rest) => Ok((rest, &input[..tag.len()])),
+        None => Err(fail(input, Expected::Tag(tag))),
+    }
+}
+
+/// Matches a non-empty run of letters, digits and underscores.
+pub fn word(input: &str) -> PResult<'_, &str> {
+    match take_while(input, |c| c.is_ascii_alphanumeric() || c == '_') {
+        ("", _) => Err(fail(input, Expected::Kind("a word"))),
+        (word, rest) => Ok((rest, word)),
+    }
+}
+
+/// Matches any number of spaces and tabs, including none. Newlines are not consumed.
+pub fn space(input: &str) -> PResult<'_, &str> {
+    let (space, rest) = take_while(input, |c| c == ' ' || c == '\t');
+    Ok((rest, space))
+}
+
+/// Matches a decimal integer with an optional leading minus sign, and parses it as `T`.
+pub fn integer<'a, T: FromStr>(input: &'a str) -> PResult<'a, T> {
+    let sign = usize::from(input.starts_with('-'));
+    let (digits, _) = take_while(&input[sign..], |c| c.is_ascii_digit());
+    let (number, rest) = input.split_at(sign + digits.len());
+
+    match number.parse() {
+        Ok(value) if !digits.is_empty() => Ok((rest, value)),
+        _ => Err(fail(input, Expected::Kind("a number"))),
+    }
+}
+
+//----------
+// Combinators
+//----------
+
+/// Applies `f` to the value parsed by `parser`.
+pub fn map<'a, A, B>(parser: impl Parser<'a, A>, f: impl Fn(A) -> B) -> impl Fn(&'a str) -> PResult<'a, B> {
+    move |input: &'a str| parser.parse(input).map(|(rest, value)| (rest, f(value)))
+}
+
+/// Applies `first` and then `second`, returning both values.
+pub fn pair<'a, A, B>(
+    first: impl Parser<'a, A>,
+    second: impl Parser<'a, B>,
+) -> impl Fn(&'a str) -> PResult<'a, (A, B)> {
+    move |input: &'a str| {
+        let (rest, a) = first.parse(input)?;
+        let (rest, b) = second.parse(rest)?;
+        Ok((rest, (a, b)))
+    }
+}
+
+/// Applies `prefix` and then `parser`, keeping only the value of `parser`.
+pub fn preceded<'a, P, T>(
+    prefix: impl Parser<'a, P>,
+    parser: impl Parser<'a, T>,
+) -> impl Fn(&'a str) -> PResult<'a, T> {
+    map(pair(prefix, parser), |(_, value)| value)
+}
+
+/// Applies `parser` and then `suffix`, keeping only the value of `parser`.
+pub fn terminated<'a, T, S>(
+    parser: impl Parser<'a, T>,
+    suffix: impl Parser<'a, S>,
+) -> impl Fn(&'a str) -> PResult<'a, T> {
+    map(pair(parser, suffix), |(value, _)| value)
+}
+
+/// Applies `open`, `parser` and `close`, keeping only the value of `parser`.
+pub fn delimited<'a, O, T, C>(
+    open: impl Parser<'a, O>,
+    parser: impl Parser<'a, T>,
+    close: impl Parser<'a, C>,
+) -> impl Fn(&'a str) -> PResult<'a, T> {
+    preceded(open, terminated(parser, close))
+}
+
+/// Applies `parser` if it matches, returning `None` without consuming input otherwise.
+pub fn opt<'a, T>(parser: impl Parser<'a, T>) -> impl Fn(&'a str) -> PResult<'a, Option<T>> {
+    move |input: &'a str| match parser.parse(input) {
+        Ok((rest, value)) => Ok((rest, Some(value))),
+        Err(_) => Ok((input, None)),
+    }
+}
+
+/// Applies `first`, or `second` if `first` fails. The failure of `second` is reported if both fail.
+pub fn either<'a, T>(first: impl Parser<'a, T>, second: impl Parser<'a, T>) -> impl Fn(&'a str) -> PResult<'a, T> {
+    move |input: &'a str| first.parse(input).or_else(|_| second.parse(input))
+}
+
+/// Parses one or more `item`s separated by `separator`.
+///
+/// A separator that is not followed by an item is not consumed.
+pub fn separated_list<'a, T, S>(
+    item: impl Parser<'a, T>,
+    separator: impl Parser<'a, S>,
+) -> impl Fn(&'a str) -> PResult<'a, Vec<T>> {
+    move |input: &'a str| {
+        let (mut rest, first) = item.parse(input)?;
+        let mut items = vec![first];
+
+        while let Ok((after_sep, _)) = separator.parse(rest) {
+            match item.parse(after_sep) {
+                Ok((after_item, value)) => {
+                    items.push(value);
+                    rest = after_item;
+                }
+                Err(_) => break,
+            }
+        }
+
+        Ok((rest, items))
+    }
+}
+
+//----------
+// Tests
+//----------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_basic_parsers() {
+        assert_eq!(tag("px{")("px{a<2006"), Ok(("a<2006", "px{")));
+        assert_eq!(word("qkq,m"), Ok((",m", "qkq")));
+        assert_eq!(integer::<i32>("-12 x"), Ok((" x", -12)));
+        assert_eq!(space("  \tx"), Ok(("x", "  \t")));
+
+        assert_eq!(tag("{")("x"), Err(fail("x", Expected::Tag("{"))));
+        assert!(word(",").is_err());
+        assert!(integer::<u8>("-").is_err());
+        assert!(integer::<u8>("300").is_err());
+    }
+
+    #[test]
+    fn test_combinators() {
+        // day 19 workflow
+        let rule = pair(
+            pair(word, either(tag("<"), tag(">"))),
+            pair(integer::<u32>, preceded(tag(":"), word)),
+        );
+        let workflow = pair(
+            word,
+            delimited(
+                tag("{"),
+                pair(separated_list(rule, tag(",")), preceded(tag(","), word)),
+                tag("}"),
+            ),
+        );
+
+        let (name, (rules, fallback)) = complete("px{a<2006:qkq,m>2090:A,rfg}", workflow).unwrap();
+        assert_eq!(name, "px");
+        assert_eq!(rules, [(("a", "<"), (2006, "qkq")), (("m", ">"), (2090, "A"))]);
+        assert_eq!(fallback, "rfg");
+
+        // day 20 module
+        let module = pair(
+            pair(opt(either(tag("%"), tag("&"))), terminated(word, tag(" -> "))),
+            separated_list(word, pair(tag(","), space)),
+        );
+        let ((kind, name), outputs) = complete("%a -> inv, con", &module).unwrap();
+        assert_eq!((kind, name, outputs), (Some("%"), "a", vec!["inv", "con"]));
+        let ((kind, _), _) = complete("broadcaster -> a", &module).unwrap();
+        assert_eq!(kind, None);
+    }
+
+    #[test]
+    fn test_complete_errors() {
+        let list = delimited(tag("("), separated_list(integer::<u32>, tag(", ")), tag(")"));
+        assert_eq!(complete("(1, 2, 3)", &list).unwrap(), [1, 2, 3]);
+
+        let err = complete("(1, 2, x)", &list).unwrap_err();
+        assert_eq!(err.to_string(), "Line 1, column 6: Unexpected `,`, expected `)`.");
+
+        let err = complete("(1) extra", &list).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Line 1, column 4: Unexpected ` `, expected end of input."
+        );
+
+        let err = complete("(1", &list).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Line 1, column 3: Unexpected end of input, expected `)`."
+        );
+    }
+}
@@ -0,0 +1,149 @@
+//! Multiset counting how many times each item occurs.
+//!
+//! The sorted count profile of a collection (for example `[3, 2]` for a full house in a poker hand) describes its shape
+//! regardless of which items are repeated.
+
+use std::{
+    cmp::Reverse,
+    collections::{hash_map, HashMap},
+    hash::Hash,
+};
+
+//----------
+// Structs
+//----------
+
+/// Counts the occurrences of each item.
+#[derive(Clone, Debug)]
+pub struct Counter<T> {
+    counts: HashMap<T, usize>,
+}
+
+//----------
+// Implementations
+//----------
+
+impl<T: Eq + Hash> Counter<T> {
+    /// Creates an empty counter.
+    pub fn new() -> Counter<T> {
+        Counter { counts: HashMap::new() }
+    }
+
+    /// Counts one more occurrence of `item`.
+    pub fn add(&mut self, item: T) {
+        self.add_n(item, 1);
+    }
+
+    /// Counts `n` more occurrences of `item`.
+    pub fn add_n(&mut self, item: T, n: usize) {
+        if n > 0 {
+            *self.counts.entry(item).or_insert(0) += n;
+        }
+    }
+
+    /// Returns the number of occurrences of `item`.
+    pub fn get(&self, item: &T) -> usize {
+        self.counts.get(item).copied().unwrap_or(0)
+    }
+
+    /// Removes all occurrences of `item`, returning how many there were.
+    pub fn remove(&mut self, item: &T) -> usize {
+        self.counts.remove(item).unwrap_or(0)
+    }
+
+    /// Returns the number of distinct items.
+    pub fn len(&self) -> usize {
+        self.counts.len()
+    }
+
+    /// Checks whether no item has been counted.
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+
+    /// Returns the total number of occurrences of all items.
+    pub fn total(&self) -> usize {
+        self.counts.values().sum()
+    }
+
+    /// Returns the counts of the distinct items, from the largest to the smallest.
+    pub fn profile(&self) -> Vec<usize> {
+        let mut profile: Vec<usize> = self.counts.values().copied().collect();
+        profile.sort_unstable_by_key(|&count| Reverse(count));
+        profile
+    }
+
+    /// Returns the items with their counts, from the most to the least common. Ties are in arbitrary order.
+    pub fn most_common(&self) -> Vec<(&T, usize)> {
+        let mut items: Vec<(&T, usize)> = self.iter().collect();
+        items.sort_unstable_by_key(|&(_, count)| Reverse(count));
+        items
+    }
+
+    /// Returns an iterator over the distinct items and their counts, in arbitrary order.
+    pub fn iter(&self) -> impl Iterator<Item = (&T, usize)> {
+        self.counts.iter().map(|(item, &count)| (item, count))
+    }
+}
+
+impl<T: Eq + Hash> Default for Counter<T> {
+    fn default() -> Self {
+        Counter::new()
+    }
+}
+
+//----------
+// Traits
+//----------
+
+impl<T: Eq + Hash> FromIterator<T> for Counter<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut counter = Counter::new();
+        counter.extend(iter);
+        counter
+    }
+}
+
+impl<T: Eq + Hash> Extend<T> for Counter<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        iter.into_iter().for_each(|item| self.add(item));
+    }
+}
+
+impl<T> IntoIterator for Counter<T> {
+    type Item = (T, usize);
+    type IntoIter = hash_map::IntoIter<T, usize>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.counts.into_iter()
+    }
+}
+
+//----------
+// Tests
+//----------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tests() {
+        let mut counter: Counter<char> = "QQJQA".chars().collect();
+        assert_eq!(counter.get(&'Q'), 3);
+        assert_eq!(counter.get(&'K'), 0);
+        assert_eq!((counter.len(), counter.total()), (3, 5));
+        assert_eq!(counter.profile(), [3, 1, 1]);
+        assert_eq!(counter.most_common()[0], (&'Q', 3));
+
+        assert_eq!(counter.remove(&'J'), 1);
+        assert_eq!(counter.remove(&'J'), 0);
+        counter.add_n('A', 2);
+        counter.add_n('K', 0);
+        assert_eq!(counter.profile(), [3, 3]);
+        assert_eq!(counter.len(), 2);
+
+        assert!(Counter::<u8>::default().is_empty());
+        assert!(Counter::<u8>::new().profile().is_empty());
+    }
+}
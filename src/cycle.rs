@@ -0,0 +1,188 @@
+//! Cycle detection for deterministic simulations.
+//!
+//! A sequence `x0, f(x0), f(f(x0)), ...` generated by a function over a finite set of states eventually repeats. Its
+//! shape is a tail of `offset` states followed by a loop of `period` states, so any far away state can be found by
+//! reducing its index into the loop instead of simulating every step.
+//!
+//! `find_cycle` and `nth_state` only compare states, while `fast_forward` hashes them to detect the repetition in a
+//! single pass, trading memory for fewer calls to an expensive step function.
+
+use std::{collections::HashMap, hash::Hash};
+
+//----------
+// Structs
+//----------
+
+/// Shape of an eventually periodic sequence: the states from index `offset` onwards repeat every `period` steps.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Cycle {
+    pub offset: usize,
+    pub period: usize,
+}
+
+impl Cycle {
+    /// Returns the smallest index holding the same state as index `n`.
+    pub fn extrapolate(&self, n: usize) -> usize {
+        if n < self.offset {
+            n
+        } else {
+            self.offset + (n - self.offset) % self.period
+        }
+    }
+}
+
+//----------
+// Helper methods
+//----------
+
+/// Finds the cycle of the sequence starting at `initial` and generated by `step`, using Brent's algorithm.
+///
+/// Only compares states, so it needs no hashing and keeps at most three states alive. `step` is called roughly
+/// `offset + 2 * period` times. The sequence must be eventually periodic, otherwise this function never returns.
+pub fn find_cycle<S, F>(initial: S, mut step: F) -> Cycle
+where
+    S: Clone + Eq,
+    F: FnMut(&S) -> S,
+{
+    // find the period: the tortoise teleports to the hare at every power of two
+    let mut power = 1;
+    let mut period = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = step(&initial);
+    while tortoise != hare {
+        if power == period {
+            tortoise = hare.clone();
+            power *= 2;
+            period = 0;
+        }
+        hare = step(&hare);
+        period += 1;
+    }
+
+    // find the offset: walk two pointers `period` steps apart until they meet
+    let mut tortoise = initial.clone();
+    let mut hare = initial;
+    for _ in 0..period {
+        hare = step(&hare);
+    }
+    let mut offset = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        offset += 1;
+    }
+
+    Cycle { offset, period }
+}
+
+/// Returns the state at index `n` of the sequence starting at `initial` and generated by `step`.
+///
+/// Detects the cycle first, so only about `offset + 2 * period` steps are needed to find the cycle, plus at most
+/// `offset + period` steps to reach the state.
+pub fn nth_state<S, F>(initial: S, mut step: F, n: usize) -> S
+where
+    S: Clone + Eq,
+    F: FnMut(&S) -> S,
+{
+    let cycle = find_cycle(initial.clone(), &mut step);
+    (0..cycle.extrapolate(n)).fold(initial, |state, _| step(&state))
+}
+
+/// Returns the state after `n_steps` steps of `step` starting at `initial`, simulating only until a state repeats.
+///
+/// Every state seen is hashed and stored, so `step` is called at most `offset + period` times, and never more than
+/// `n_steps` times. Useful for puzzles asking for the state after a huge number of steps (like 10^9 spin cycles).
+pub fn fast_forward<S, F>(initial: S, mut step: F, n_steps: usize) -> S
+where
+    S: Clone + Eq + Hash,
+    F: FnMut(&S) -> S,
+{
+    let mut seen: HashMap<S, usize> = HashMap::new();
+    let mut history = vec![initial];
+
+    for i in 0..n_steps {
+        let state = &history[i];
+        if let Some(&offset) = seen.get(state) {
+            let cycle = Cycle {
+                offset,
+                period: i - offset,
+            };
+            return history.swap_remove(cycle.extrapolate(n_steps));
+        }
+        seen.insert(state.clone(), i);
+
+        let next = step(state);
+        history.push(next);
+    }
+
+    history.pop().unwrap()
+}
+
+//----------
+// Tests
+//----------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_cycle() {
+        // 0, 1, 2, 3, 4, 5, 3, 4, 5, ...
+        let step = |&x: &u32| if x == 5 { 3 } else { x + 1 };
+        assert_eq!(find_cycle(0, step), Cycle { offset: 3, period: 3 });
+
+        // purely periodic: 0, 1, 2, 3, 0, ...
+        assert_eq!(find_cycle(0, |&x: &u32| (x + 1) % 4), Cycle { offset: 0, period: 4 });
+
+        // fixed point
+        assert_eq!(find_cycle(7, |&x: &u32| x), Cycle { offset: 0, period: 1 });
+
+        // long tail and long loop
+        let step = |&x: &u64| if x < 1000 { x + 1 } else { 500 + (x - 500 + 1) % 600 };
+        assert_eq!(
+            find_cycle(0, step),
+            Cycle {
+                offset: 500,
+                period: 600
+            }
+        );
+    }
+
+    #[test]
+    fn test_extrapolate() {
+        let cycle = Cycle { offset: 3, period: 3 };
+        assert_eq!(cycle.extrapolate(2), 2);
+        assert_eq!(cycle.extrapolate(3), 3);
+        assert_eq!(cycle.extrapolate(7), 4);
+        assert_eq!(cycle.extrapolate(1_000_000_000), 4);
+
+        let step = |&x: &u32| if x == 5 { 3 } else { x + 1 };
+        assert_eq!(nth_state(0, step, 1_000_000_000), 4);
+    }
+
+    #[test]
+    fn test_fast_forward() {
+        let mut calls = 0;
+        let step = |&x: &u32| {
+            calls += 1;
+            if x == 5 {
+                3
+            } else {
+                x + 1
+            }
+        };
+        assert_eq!(fast_forward(0, step, 1_000_000_000), 4);
+        assert_eq!(calls, 6);
+
+        // fewer steps than needed to find the cycle
+        assert_eq!(fast_forward(0, |&x: &u32| x + 1, 10), 10);
+        assert_eq!(fast_forward(0, |&x: &u32| x + 1, 0), 0);
+
+        // same result as the comparison based approach
+        let step = |&x: &u64| if x < 1000 { x + 1 } else { 500 + (x - 500 + 1) % 600 };
+        for n in [0, 499, 500, 1099, 1100, 123_456_789] {
+            assert_eq!(fast_forward(0, step, n), nth_state(0, step, n), "n = {n}");
+        }
+    }
+}
@@ -14,88 +14,192 @@
 //! # Solution
 //!
 //! - Search from both left and right.
+//! - Digits are words of a dictionary, numeric digits for part 1 and numeric or spelled digits for part 2, found with
+//!   an Aho-Corasick automaton in a single pass from each end. Other dictionaries, with other spellings or extended
+//!   words, can be given to `total_calibration_value`.
+//! - `run_lines` solves both parts in a single pass over lines streamed by `input::lines`, so the input file is never
+//!   loaded whole.
 
-use std::fs;
-use std::str;
+use crate::{answer::Answer, automaton::Matcher, error::AocError};
 
-pub fn run() {
-    let input = fs::read_to_string("inputs/day01").unwrap();
+/// Numeric digits, with their values. The dictionary of part 1.
+pub const NUMERIC_DIGITS: [(&str, u32); 9] = [
+    ("1", 1),
+    ("2", 2),
+    ("3", 3),
+    ("4", 4),
+    ("5", 5),
+    ("6", 6),
+    ("7", 7),
+    ("8", 8),
+    ("9", 9),
+];
 
-    let a = total_calibration_value(&input, calibration_digits_pt01);
-    println!("Part 01: Total Calibration value: {}", a);
+/// Spelled digits, with their values. Added to the numeric digits in part 2.
+pub const SPELLED_DIGITS: [(&str, u32); 9] = [
+    ("one", 1),
+    ("two", 2),
+    ("three", 3),
+    ("four", 4),
+    ("five", 5),
+    ("six", 6),
+    ("seven", 7),
+    ("eight", 8),
+    ("nine", 9),
+];
 
-    let b = total_calibration_value(&input, calibration_digits_pt02);
-    println!("Part 02: Total Calibration value: {}", b);
+pub fn run(input: &str) -> Result<[Answer; 2], AocError> {
+    run_lines(input.lines().map(Ok))
+}
+
+/// Solves both parts in a single pass over the lines of the input, read one at a time.
+/// @param lines: Lines of the calibration data, like the ones streamed by `input::lines`.
+///
+/// # Errors
+///
+/// Fails if a line can not be read or has no digit, pointing to that line, or the sums overflow.
+pub fn run_lines(lines: impl IntoIterator<Item = Result<impl AsRef<str>, AocError>>) -> Result<[Answer; 2], AocError> {
+    let matchers = [Matcher::new(NUMERIC_DIGITS), Matcher::new(part2_dictionary())];
+    let mut sums = [0, 0];
+    for (i, line) in lines.into_iter().enumerate() {
+        let line = line?;
+        for (sum, matcher) in sums.iter_mut().zip(&matchers) {
+            let (first, last) = calibration_digits(line.as_ref(), matcher).ok_or_else(|| no_digits(i + 1))?;
+            *sum = add_calibration_value(*sum, first, last)?;
+        }
+    }
+    Ok(sums.map(Answer::from))
+}
+
+/// Returns the sum of the calibration values, using only numeric digits.
+///
+/// # Errors
+///
+/// Fails if a line has no digit.
+pub fn solve_part1(input: &str) -> Result<u32, AocError> {
+    total_calibration_value(input.lines(), &NUMERIC_DIGITS)
+}
+
+/// Returns the sum of the calibration values, with digits that can also be spelled out.
+///
+/// # Errors
+///
+/// Fails if a line has no digit.
+pub fn solve_part2(input: &str) -> Result<u32, AocError> {
+    total_calibration_value(input.lines(), &part2_dictionary())
+}
+
+/// Returns the dictionary of part 2, numeric and spelled digits.
+fn part2_dictionary() -> Vec<(&'static str, u32)> {
+    [NUMERIC_DIGITS, SPELLED_DIGITS].concat()
 }
 
 /// Returns the total sum of calibration values.
-/// @param s: String containing the calibration data.
-/// @param calibration: Function that reads the input data and returns the calibration digits.
-fn total_calibration_value(s: &str, calibration: fn(&str) -> (u32, u32)) -> u32 {
-    s.lines().map(calibration).map(|(first, last)| first * 10 + last).sum()
+/// @param lines: Lines of the calibration data, like the ones of a `str`.
+/// @param dictionary: Words that count as digits, with their values.
+///
+/// # Errors
+///
+/// Fails if a line has no word of the dictionary, pointing to that line, or the sum overflows.
+pub fn total_calibration_value(
+    lines: impl IntoIterator<Item = impl AsRef<str>>,
+    dictionary: &[(&str, u32)],
+) -> Result<u32, AocError> {
+    let matcher = Matcher::new(dictionary.iter().copied());
+    lines.into_iter().enumerate().try_fold(0, |sum, (i, line)| {
+        let (first, last) = calibration_digits(line.as_ref(), &matcher).ok_or_else(|| no_digits(i + 1))?;
+        add_calibration_value(sum, first, last)
+    })
 }
 
-/// Returns the calibration digits from an input line according to part 1 rules.
-/// @param line: A string containing a single line of text (without '\n').
-fn calibration_digits_pt01(line: &str) -> (u32, u32) {
-    let mut digits = line.chars().filter_map(|c| c.to_digit(10));
-    // lines have at least one digit,
-    let first = digits.next().unwrap();
-    // if only one, then repeat
-    let last: u32 = digits.next_back().unwrap_or(first);
-    (first, last)
+/// Adds the calibration value of the digits `first` and `last` to `sum`, failing if it overflows.
+fn add_calibration_value(sum: u32, first: u32, last: u32) -> Result<u32, AocError> {
+    first
+        .checked_mul(10)
+        .and_then(|tens| tens.checked_add(last))
+        .and_then(|value| sum.checked_add(value))
+        .ok_or_else(AocError::overflow)
 }
 
-/// Returns the calibration digits from an input line according to part 2 rules.
+/// Returns the calibration digits from an input line: the values of the first and the last words of the dictionary
+/// found, which may overlap. Returns `None` if the line has no such word.
 /// @param line: A string containing a single line of text (without '\n');
-fn calibration_digits_pt02(line: &str) -> (u32, u32) {
-    // closure to filter digits (single numeric values and spelled values)
-    let digit_filter = |s: &str| -> Option<u32> {
-        match s {
-            _ if s.as_bytes()[0].is_ascii_digit() => Some((s.as_bytes()[0] - b'0') as u32),
-            _ if s.starts_with("one") => Some(1),
-            _ if s.starts_with("two") => Some(2),
-            _ if s.starts_with("three") => Some(3),
-            _ if s.starts_with("four") => Some(4),
-            _ if s.starts_with("five") => Some(5),
-            _ if s.starts_with("six") => Some(6),
-            _ if s.starts_with("seven") => Some(7),
-            _ if s.starts_with("eight") => Some(8),
-            _ if s.starts_with("nine") => Some(9),
-            _ => None,
-        }
-    };
-
-    let len = line.len();
+/// @param dictionary: Matcher of the words that count as digits, built from a table like [`NUMERIC_DIGITS`].
+pub fn calibration_digits(line: &str, dictionary: &Matcher<u32>) -> Option<(u32, u32)> {
+    let line = line.as_bytes();
     // search from left
-    let first = (0..len).find_map(|i| digit_filter(&line[i..])).unwrap();
+    let first = dictionary.find_first(line)?.value;
     // search from right
-    let last = (1..len + 1).find_map(|i| digit_filter(&line[len - i..])).unwrap();
+    let last = dictionary.find_last(line)?.value;
+
+    Some((first, last))
+}
 
-    (first, last)
+/// Error of the line number `line` without any digit.
+fn no_digits(line: usize) -> AocError {
+    AocError::parse("Line without digits.").at_line(line).in_day(1)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{aoc_test, input::Lines};
+
+    const SAMPLE_PT1: &str = "1abc2
+pqr3stu8vwx
+a1b2c3d4e5f
+treb7uchet";
+
+    const SAMPLE_PT2: &str = "two1nine
+eightwothree
+abcone2threexyz
+xtwone3four
+4nineeightseven2
+zoneight234
+7pqrstsixteen";
+
+    aoc_test! { sample: SAMPLE_PT1, pt01_tests: solve_part1 => 142 }
+    aoc_test! { sample: SAMPLE_PT2, pt02_tests: solve_part2 => 281 }
+
+    // spelled digits may overlap
+    aoc_test! { sample: "oneight", pt02_overlapping: solve_part2 => 18 }
 
     #[test]
-    fn pt01_tests() {
-        assert_eq!(total_calibration_value("1abc2", calibration_digits_pt01), 12);
-        assert_eq!(total_calibration_value("pqr3stu8vwx", calibration_digits_pt01), 38);
-        assert_eq!(total_calibration_value("a1b2c3d4e5f", calibration_digits_pt01), 15);
-        assert_eq!(total_calibration_value("treb7uchet", calibration_digits_pt01), 77);
+    fn test_dictionaries() {
+        let matcher = Matcher::new(NUMERIC_DIGITS);
+        assert_eq!(calibration_digits("two1nine", &matcher), Some((1, 1)));
+        assert_eq!(calibration_digits("nine", &matcher), None);
+
+        // localized spellings and extended words
+        let dictionary = [("zero", 0), ("ten", 10), ("un", 1), ("deux", 2)];
+        let matcher = Matcher::new(dictionary);
+        assert_eq!(calibration_digits("zerotendeux", &matcher), Some((0, 2)));
+        assert_eq!(calibration_digits("xtenx", &matcher), Some((10, 10)));
+        assert_eq!(
+            total_calibration_value(["zerodeux", "unten"], &dictionary).unwrap(),
+            2 + 20
+        );
+
+        let err = total_calibration_value(["1abc2", "two"], &NUMERIC_DIGITS).unwrap_err();
+        assert_eq!(err.to_string(), "Day 01, line 2: Line without digits.");
+
+        // large values overflow the calibration value of a line, or the sum
+        let overflow = "Invalid input: the numbers are too large, the answer overflows";
+        let dictionary = [("big", u32::MAX / 10 + 1), ("large", 390_000_000)];
+        let err = total_calibration_value(["big"], &dictionary).unwrap_err();
+        assert_eq!(err.to_string(), overflow);
+        assert_eq!(total_calibration_value(["large"], &dictionary).unwrap(), 4_290_000_000);
+        let err = total_calibration_value(["large", "large"], &dictionary).unwrap_err();
+        assert_eq!(err.to_string(), overflow);
     }
 
     #[test]
-    fn pt02_tests() {
-        assert_eq!(total_calibration_value("two1nine", calibration_digits_pt02), 29);
-        assert_eq!(total_calibration_value("eightwothree", calibration_digits_pt02), 83);
-        assert_eq!(total_calibration_value("abcone2threexyz", calibration_digits_pt02), 13);
-        assert_eq!(total_calibration_value("xtwone3four", calibration_digits_pt02), 24);
-        assert_eq!(total_calibration_value("4nineeightseven2", calibration_digits_pt02), 42);
-        assert_eq!(total_calibration_value("zoneight234", calibration_digits_pt02), 14);
-        assert_eq!(total_calibration_value("7pqrstsixteen", calibration_digits_pt02), 76);
-        assert_eq!(total_calibration_value("oneight", calibration_digits_pt02), 18);
+    fn test_run_lines() {
+        let lines = Lines::new("4nineeightseven2\r\nzoneight234\n7pqrstsixteen\n\n".as_bytes(), "test");
+        assert_eq!(run_lines(lines).unwrap(), [143u32.into(), 132u32.into()]);
+
+        // the lines without numeric digits fail part 1
+        let err = run_lines(Lines::new("1\nnine".as_bytes(), "test")).unwrap_err();
+        assert_eq!(err.to_string(), "Day 01, line 2: Line without digits.");
     }
 }
@@ -19,82 +19,193 @@
 //! # Solution
 //!
 //! - Keeping track of maximum values.
+//! - Colors are kept by name, so games with any colors can be analyzed. The limits of part 1 can be changed with
+//!   `run_with`, or from the command line like `aoc2023 2 --limits red=12,green=13,blue=14,yellow=3`.
+//! - Beyond the answers, `game_stats` returns the maxima, validity and power of every game, which `stats_csv` exports.
 
-use std::{error, fs, str::FromStr};
-use Color::*;
+use crate::{answer::Answer, error::AocError, iter_ext::IterExt, token::Tokenizer};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::{self, Write},
+};
 
-pub fn run() {
-    let input = fs::read_to_string("inputs/day02").unwrap();
-    let games = parse_input(&input).unwrap();
+/// Limits of the bag of part 1.
+const DEFAULT_LIMITS: [(&str, u32); 3] = [("red", 12), ("green", 13), ("blue", 14)];
 
-    println!("Part 01: Sum of Valid games IDs: {}", sum_valid(&games));
-    println!("Part 02: Sum of Powers: {}", sum_powers(&games));
+/// Number of cubes of each color, by color name.
+pub type Cubes = BTreeMap<String, u32>;
+
+pub fn run(input: &str) -> Result<[Answer; 2], AocError> {
+    run_with(input, &default_limits())
+}
+
+/// Solves both parts with the given limits of the bag instead of the default ones.
+///
+/// # Errors
+///
+/// Fails if the input is malformed, or a sum overflows.
+pub fn run_with(input: &str, limits: &Cubes) -> Result<[Answer; 2], AocError> {
+    let games = parse_input(input)?;
+
+    let part1 = sum_valid(&games, limits).ok_or_else(AocError::overflow)?;
+    let part2 = sum_powers(&games, limits).ok_or_else(AocError::overflow)?;
+    Ok([part1.into(), part2.into()])
+}
+
+/// Returns the sum of the IDs of the games possible with the bag's cubes.
+///
+/// # Errors
+///
+/// Fails if the input is malformed, or the sum overflows.
+pub fn solve_part1(input: &str) -> Result<u32, AocError> {
+    sum_valid(&parse_input(input)?, &default_limits()).ok_or_else(AocError::overflow)
+}
+
+/// Returns the sum of the powers of the minimum sets of cubes of each game.
+///
+/// # Errors
+///
+/// Fails if the input is malformed, or the sum overflows.
+pub fn solve_part2(input: &str) -> Result<u32, AocError> {
+    sum_powers(&parse_input(input)?, &default_limits()).ok_or_else(AocError::overflow)
+}
+
+/// Returns the limits of part 1: 12 red, 13 green and 14 blue cubes.
+pub fn default_limits() -> Cubes {
+    DEFAULT_LIMITS.iter().map(|&(color, n)| (color.to_owned(), n)).collect()
+}
+
+/// Returns the statistics of every game of the input, in order.
+/// @param input: reference to a string containing records of games.
+/// @param limits: the cubes in the bag.
+///
+/// # Errors
+///
+/// Fails if the input is malformed, or the power of a game overflows.
+pub fn game_stats(input: &str, limits: &Cubes) -> Result<Vec<GameStats>, AocError> {
+    let games = parse_input(input)?;
+    games
+        .into_iter()
+        .map(|game| {
+            Ok(GameStats {
+                id: game.id,
+                valid: game.is_valid(limits),
+                power: game.power(limits.keys()).ok_or_else(AocError::overflow)?,
+                max: game.max,
+            })
+        })
+        .collect()
+}
+
+/// Writes game statistics as CSV, one game per line, with a column for the maximum of each color seen in the games or
+/// in the bag, sorted by name.
+/// @param stats: statistics returned by `game_stats`.
+/// @param limits: the cubes in the bag.
+pub fn stats_csv(stats: &[GameStats], limits: &Cubes) -> String {
+    let colors: BTreeSet<_> = limits.keys().chain(stats.iter().flat_map(|s| s.max.keys())).collect();
+
+    let mut csv = String::from("id,valid,power");
+    colors.iter().for_each(|color| write!(csv, ",{color}").unwrap());
+    for game in stats {
+        write!(csv, "\n{},{},{}", game.id, game.valid, game.power).unwrap();
+        for &color in &colors {
+            write!(csv, ",{}", game.max.get(color).copied().unwrap_or(0)).unwrap();
+        }
+    }
+    csv.push('\n');
+    csv
+}
+
+/// Parses limits of cubes.
+/// @param s: string in the format "{color}={number}", separated by ','. For example, "red=12,green=13,blue=14".
+pub fn parse_limits(s: &str) -> Result<Cubes, AocError> {
+    let mut tokens = Tokenizer::new(s);
+    let mut limits = Cubes::new();
+
+    loop {
+        let color = tokens.expect_word()?;
+        tokens.expect_symbol('=')?;
+        limits.insert(color.to_owned(), tokens.number()?);
+
+        if !tokens.eat_symbol(',') {
+            tokens.expect_end()?;
+            return Ok(limits);
+        }
+    }
 }
 
 /// Parses the input string into a collection of `Game`s
 /// @param input: reference to a string containing records of games.
-fn parse_input(input: &str) -> Option<Vec<Game>> {
+fn parse_input(input: &str) -> Result<Vec<Game>, AocError> {
     let mut games: Vec<Game> = Vec::new();
 
     // Example line: "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green"
-    for line in input.lines() {
-        // Get Game id
-        let mut iter = line.strip_prefix("Game ")?.split(": ");
-        let id: u32 = iter.next()?.parse().unwrap();
-
-        // create game struct
-        let mut game = Game::new(id);
-
-        // break game record and parse into `Cube`s, then update `Game` struct
-        let iter_cubes_str = iter.next()?.split([',', ';']);
-        for cube in iter_cubes_str {
-            let cube = cube.trim().parse().unwrap();
-            game.update(cube);
-        }
-
+    for (i, line) in input.lines().enumerate() {
+        let game = parse_game(line).map_err(|e| e.at_line(i + 1).in_day(2))?;
         games.push(game);
     }
 
-    Some(games)
+    Ok(games)
 }
 
-/// Returns the sum of `id`s of valid games.
+/// Parses a single record of a game.
+/// @param line: string in the format "Game {id}: {cubes}", where the cube records are separated by ',' or ';'.
+fn parse_game(line: &str) -> Result<Game, AocError> {
+    let mut tokens = Tokenizer::new(line);
+
+    // Get Game id
+    tokens.expect_keyword("Game")?;
+    let mut game = Game::new(tokens.number()?);
+    tokens.expect_symbol(':')?;
+
+    // parse each cube record, "{number} {color}", then update `Game` struct
+    loop {
+        let quantity = tokens.number()?;
+        game.update(tokens.expect_word()?, quantity);
+
+        if !tokens.eat_symbol(',') && !tokens.eat_symbol(';') {
+            tokens.expect_end()?;
+            return Ok(game);
+        }
+    }
+}
+
+/// Returns the sum of `id`s of valid games, or `None` if it overflows.
 /// @param `games`: a reference to an array of `Game`s.
-fn sum_valid(games: &[Game]) -> u32 {
-    games.iter().filter(|g| g.is_valid()).map(|g| g.id).sum()
+/// @param `limits`: the cubes in the bag.
+fn sum_valid(games: &[Game], limits: &Cubes) -> Option<u32> {
+    games.iter().filter(|g| g.is_valid(limits)).map(|g| g.id).checked_sum()
 }
 
-/// Returns the sum of "powers" of games.
+/// Returns the sum of "powers" of games, or `None` if it overflows.
 /// @param `games`: a reference to an array of `Game`s.
-fn sum_powers(games: &[Game]) -> u32 {
-    games.iter().map(|g| g.power()).sum()
+/// @param `limits`: the cubes in the bag, whose colors are multiplied.
+fn sum_powers(games: &[Game], limits: &Cubes) -> Option<u32> {
+    let powers: Option<Vec<u32>> = games.iter().map(|g| g.power(limits.keys())).collect();
+    powers?.into_iter().checked_sum()
 }
 
 //----------
-// Structs and enums
+// Structs
 //----------
 
-/// Represents the possible colors of the cubes.
-#[derive(Copy, Clone)]
-enum Color {
-    Red,
-    Green,
-    Blue,
-}
-
-/// Intermediary struct to parse a game record, storing the color and number of cubes.
-#[derive(Copy, Clone)]
-struct Cube {
-    color: Color,
-    quantity: u32,
+/// Statistics of a game, returned by `game_stats`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GameStats {
+    pub id: u32,
+    /// Maximum number of cubes of each color recorded.
+    pub max: Cubes,
+    /// Whether the maxima are within the limits of the bag.
+    pub valid: bool,
+    /// Product of the maxima of the colors of the bag.
+    pub power: u32,
 }
 
-/// Stores a game `id` and the maximum amount of each kind of cube recorded.
+/// Stores a game `id` and the maximum amount of each color of cube recorded.
+#[derive(Debug, PartialEq)]
 struct Game {
     id: u32,
-    max_red: u32,
-    max_green: u32,
-    max_blue: u32,
+    max: Cubes,
 }
 
 //----------
@@ -102,81 +213,54 @@ struct Game {
 //----------
 
 impl Game {
-    //----------
-    // Color limits
-    //
-    // Limits defined for each color.
-    //----------
-    const LIMIT_RED: u32 = 12;
-    const LIMIT_GREEN: u32 = 13;
-    const LIMIT_BLUE: u32 = 14;
-
     /// Initializes a new struct with the given id.
     fn new(id: u32) -> Game {
-        Game {
-            id,
-            max_red: 0,
-            max_green: 0,
-            max_blue: 0,
-        }
+        Game { id, max: Cubes::new() }
     }
 
-    /// Compares a `Cube` quantity to the stored number, updating `self` if greater.
-    /// @param `cube`: a `Cube`.
-    fn update(&mut self, cube: Cube) {
-        match cube.color {
-            Red => self.max_red = self.max_red.max(cube.quantity),
-            Green => self.max_green = self.max_green.max(cube.quantity),
-            Blue => self.max_blue = self.max_blue.max(cube.quantity),
+    /// Compares a quantity of cubes to the stored number for their color, updating `self` if greater.
+    /// @param `color`: name of the color of the cubes.
+    /// @param `quantity`: number of cubes.
+    fn update(&mut self, color: &str, quantity: u32) {
+        match self.max.get_mut(color) {
+            Some(max) => *max = (*max).max(quantity),
+            None => {
+                self.max.insert(color.to_owned(), quantity);
+            }
         }
     }
 
-    /// Returns a boolean indicating whether the game is valid.
-    /// A game is considered valid if the maximum number of cubes for each color is within the color's limit.
-    fn is_valid(&self) -> bool {
-        self.max_red <= Game::LIMIT_RED && self.max_green <= Game::LIMIT_GREEN && self.max_blue <= Game::LIMIT_BLUE
+    /// Returns the maximum number of cubes of `color` recorded, 0 if there were none.
+    fn max_of(&self, color: &str) -> u32 {
+        self.max.get(color).copied().unwrap_or(0)
     }
 
-    /// Returns the "power" of the game.
-    fn power(&self) -> u32 {
-        self.max_red * self.max_green * self.max_blue
+    /// Returns a boolean indicating whether the game is valid.
+    /// A game is considered valid if the maximum number of cubes for each color is within the color's limit. Colors
+    /// without a limit are not in the bag.
+    fn is_valid(&self, limits: &Cubes) -> bool {
+        self.max
+            .iter()
+            .all(|(color, &max)| max <= limits.get(color).copied().unwrap_or(0))
     }
-}
-
-//----------
-// FromStr
-//
-// Parsing methods for enums and structs
-//----------
 
-impl FromStr for Color {
-    type Err = String;
-
-    /// Parses a cube color.
-    /// @param `s`: Only acceptable values are "red", "green" and "blue".
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s {
-            "red" => Ok(Red),
-            "green" => Ok(Green),
-            "blue" => Ok(Blue),
-            _ => Err("Error parsing color.".to_owned()),
-        }
+    /// Returns the "power" of the game: the product of the maximum numbers of cubes of `colors`, or `None` if it
+    /// overflows.
+    fn power<'c>(&self, colors: impl IntoIterator<Item = &'c String>) -> Option<u32> {
+        colors.into_iter().map(|color| self.max_of(color)).checked_product()
     }
 }
 
-impl FromStr for Cube {
-    type Err = Box<dyn error::Error>;
-
-    /// Parses a cube record.
-    /// @param `s`: String format expected: "{number} {color}", for example: "3 blue". Number must be a positive
-    /// integer, and color must be one of the acceptable variants of the enum `Color`.
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut iter = s.split_ascii_whitespace();
-
-        Ok(Cube {
-            quantity: iter.next().ok_or("Error getting quantity.")?.parse()?,
-            color: iter.next().ok_or("Error getting color")?.parse()?,
-        })
+/// Formats the game as a record with a single round of the maximum of each color, which parses back into the same
+/// game, like "Game 1: 3 blue, 4 red".
+impl fmt::Display for Game {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Game {}:", self.id)?;
+        for (i, (color, quantity)) in self.max.iter().enumerate() {
+            let sep = if i == 0 { "" } else { "," };
+            write!(f, "{sep} {quantity} {color}")?;
+        }
+        Ok(())
     }
 }
 
@@ -187,21 +271,135 @@ impl FromStr for Cube {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{aoc_test, testing::corrupt};
+    use proptest::{prelude::*, sample::Index};
 
-    #[test]
-    fn tests() {
-        let input = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+    const SAMPLE: &str = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
 Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
 Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
 Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
 Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
 
-        let games = parse_input(input).unwrap();
+    aoc_test! {
+        sample: SAMPLE,
+        pt01_sample: solve_part1 => 8,
+        pt02_sample: solve_part2 => 2286,
+    }
 
-        // Part 01
-        assert_eq!(sum_valid(&games), 8);
+    #[test]
+    fn test_limits() {
+        let limits = parse_limits("red=12, green=13,blue=14").unwrap();
+        assert_eq!(limits, default_limits());
+
+        // any color can be in the bag
+        let games = parse_input("Game 1: 3 blue\nGame 2: 4 red, 1 purple; 2 purple").unwrap();
+        assert_eq!(sum_valid(&games, &default_limits()), Some(1));
+        assert_eq!(
+            sum_valid(&games, &parse_limits("red=4,blue=3,purple=2").unwrap()),
+            Some(3)
+        );
+        // the powers multiply the colors of the bag, there are no blue cubes in the second game
+        assert_eq!(games[1].max_of("purple"), 2);
+        assert_eq!(sum_powers(&games, &parse_limits("red=4,purple=2").unwrap()), Some(8));
+        assert_eq!(sum_powers(&games, &parse_limits("red=4,blue=3").unwrap()), Some(0));
+
+        let games = parse_input(SAMPLE).unwrap();
+        assert_eq!(
+            sum_valid(&games, &parse_limits("red=20,green=13,blue=15").unwrap()),
+            Some(15)
+        );
+        let answers = run_with(SAMPLE, &parse_limits("red=20,green=13,blue=15").unwrap()).unwrap();
+        assert_eq!(answers, [15u32.into(), 2286u32.into()]);
+
+        let err = parse_limits("red=12,green").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Line 1, column 13: Unexpected end of input, expected `=`."
+        );
+    }
+
+    #[test]
+    fn test_stats() {
+        let limits = default_limits();
+        let stats = game_stats(SAMPLE, &limits).unwrap();
+        assert_eq!(stats.len(), 5);
+        assert_eq!(
+            stats[2],
+            GameStats {
+                id: 3,
+                max: parse_limits("red=20,green=13,blue=6").unwrap(),
+                valid: false,
+                power: 1560,
+            }
+        );
+
+        // the stats add up to the answers
+        let valid: u32 = stats.iter().filter(|s| s.valid).map(|s| s.id).sum();
+        let power: u32 = stats.iter().map(|s| s.power).sum();
+        assert_eq!((valid, power), (8, 2286));
+
+        let stats = game_stats("Game 1: 3 blue, 4 red\nGame 7: 1 purple", &limits).unwrap();
+        assert_eq!(
+            stats_csv(&stats, &limits),
+            "id,valid,power,blue,green,purple,red\n1,true,0,3,0,0,4\n7,false,0,0,0,1,0\n"
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let err = parse_input("Game x: 3 blue").err().unwrap();
+        assert_eq!(
+            err.to_string(),
+            "Day 02, line 1, column 6: Unexpected token `x`, expected a number."
+        );
+
+        let err = parse_input("Game 1: 3 blue; 2 red 5 green").err().unwrap();
+        assert_eq!(
+            err.to_string(),
+            "Day 02, line 1, column 23: Unexpected token `5`, expected the end of the input."
+        );
+    }
+
+    /// Games with up to 6 counts of cubes, of the colors of the puzzle and an unknown one.
+    fn game() -> impl Strategy<Value = Game> {
+        let cubes = (prop::sample::select(&["red", "green", "blue", "purple"][..]), 0..100u32);
+        (0..1000u32, prop::collection::vec(cubes, 1..=6)).prop_map(|(id, cubes)| {
+            let mut game = Game::new(id);
+            cubes
+                .into_iter()
+                .for_each(|(color, quantity)| game.update(color, quantity));
+            game
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn test_game_round_trip(
+            game in game(),
+            index in any::<Index>(),
+            c in prop::sample::select(&['#', '!', '?'][..]),
+        ) {
+            let record = game.to_string();
+            prop_assert_eq!(parse_game(&record).unwrap(), game, "{}", record);
+
+            let corrupted = corrupt(&record, index, c);
+            prop_assert!(parse_game(&corrupted).is_err(), "{}", corrupted);
+        }
 
-        // Part 02
-        assert_eq!(sum_powers(&games), 2286);
+        // arbitrary records fail without panicking
+        #[test]
+        fn test_game_garbage(record in "[Game19:,;r ]{0,20}") {
+            let _ = parse_game(&record);
+        }
+    }
+
+    #[test]
+    fn test_overflow() {
+        let err = solve_part2("Game 1: 70000 red, 70000 green, 1 blue").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid input: the numbers are too large, the answer overflows"
+        );
+        assert!(solve_part1("Game 4000000000: 1 red\nGame 4000000000: 1 red").is_err());
     }
 }
@@ -19,143 +19,192 @@
 //!
 //! # Solution
 //!
-//! - Expanded borders of the input in order to avoid dealing with overflow in boundary cases.
+//! - Works on the input grid itself, clamping the neighbourhoods of the numbers to its borders instead of padding a
+//!   copy of it.
 //!
-//! - For part 1: Parse all numbers and stores their start and end positions, then check for any symbol around the
-//!   extended rectangle defined by those positions.
+//! - For part 1: Parse all numbers and stores their start and end positions, then check for any symbol among the
+//!   neighbours of their digits.
 //!
-//! - For part 2: Checks the input data for '*' and them compare their positions to the part numbers, if there are
-//!   exactly two adjacent part numbers, store the gear ratio.
+//! - For part 2: Indexes the '*' around each part number, storing the numbers adjacent to every '*', and keeps the ones
+//!   with exactly two adjacent part numbers as gears. Each number only visits its neighbouring cells, so the search is
+//!   linear in the size of the grid.
+//!
+//! - `Schematic` exposes the numbers and symbols of a schematic, with their positions, to answer other questions about
+//!   it.
 //!
 //! # Commentaries
 //!
-//! - Part 2 solution has an O^2 complexity, which could be improved.
-use std::fs;
+//! - The first solution of part 2 compared every '*' to every part number, with an O^2 complexity. It is kept for
+//!   comparison, and selected with `run_with(input, GearSearch::Naive)`, or `aoc2023 3 --strategy naive` from the
+//!   command line.
+use crate::{
+    answer::Answer,
+    error::AocError,
+    grid::{Grid, Position},
+    iter_ext::IterExt,
+    scan,
+};
+use std::{
+    collections::{HashMap, HashSet},
+    str::FromStr,
+};
+
+/// Gears of the puzzle: '*' adjacent to exactly two part numbers.
+pub const GEAR: GearSpec = GearSpec { symbol: '*', arity: 2 };
+
+pub fn run(input: &str) -> Result<[Answer; 2], AocError> {
+    run_with(input, GearSearch::Indexed)
+}
 
-pub fn run() {
-    let input = fs::read_to_string("inputs/day03").unwrap();
-    let (extended_grid, _, n_cols) = expand_borders(&input, '.');
-    let part_numbers = find_part_numbers(&extended_grid, n_cols);
+/// Solves both parts, finding the gears of part 2 with the given search.
+///
+/// # Errors
+///
+/// Fails if the lines of the input have different lengths, or a number or a sum is too large.
+pub fn run_with(input: &str, search: GearSearch) -> Result<[Answer; 2], AocError> {
+    let grid = parse_input(input)?;
+    let part_numbers = find_part_numbers(&grid);
+    let gears = match search {
+        GearSearch::Indexed => find_gears(&grid, &part_numbers, GEAR),
+        GearSearch::Naive => find_gears_naive(&grid, &part_numbers, GEAR),
+    };
+
+    let part1 = sum_numbers(&part_numbers).ok_or_else(AocError::overflow)?;
+    let part2 = sum_gear_ratios(&gears).ok_or_else(AocError::overflow)?;
+    Ok([part1.into(), part2.into()])
+}
 
-    // Part 1
-    println!("Part 01: Sum of part numbers: {}", sum_numbers(&part_numbers));
+/// Returns the sum of the part numbers of the schematic.
+///
+/// # Errors
+///
+/// Fails if the lines of the input have different lengths, or a number or the sum is too large.
+pub fn solve_part1(input: &str) -> Result<u64, AocError> {
+    sum_numbers(&find_part_numbers(&parse_input(input)?)).ok_or_else(AocError::overflow)
+}
 
-    // Part 2
-    let gears = find_gears(&extended_grid, &part_numbers);
-    println!("Part 02: Gear ratio sum: {}", sum_gear_ratios(&gears));
+/// Returns the sum of the gear ratios of the schematic.
+///
+/// # Errors
+///
+/// Fails if the lines of the input have different lengths, or a number or the sum is too large.
+pub fn solve_part2(input: &str) -> Result<u64, AocError> {
+    let grid = parse_input(input)?;
+    let part_numbers = find_part_numbers(&grid);
+    let gears = find_gears(&grid, &part_numbers, GEAR);
+    sum_gear_ratios(&gears).ok_or_else(AocError::overflow)
 }
 
-/// Takes an `input` string representing a grid of two-dimensional data and expands its borders with the `neutral`
-/// character.
-/// Returns a tuple containing the expanded grid, number of rows and number of columns.
-fn expand_borders(input: &str, neutral: char) -> (String, usize, usize) {
-    let n_cols = input.lines().next().unwrap().len(); // number of columns
-    let n_rows = input.len() / (n_cols + 1); // number of rows; +1 accounts for '\n'
-
-    // extends borders of the grid
-    let mut grid = String::with_capacity((n_cols + 3) * (n_rows + 2));
-    let border = std::iter::repeat(neutral).take(n_cols + 2).collect::<String>();
-    grid.push_str(&border);
-    grid.push('\n');
-    grid.extend(input.lines().map(|l| format!(".{}.\n", l)));
-    grid.push_str(&border);
-
-    (grid, n_rows + 2, n_cols + 2)
+/// Renders the schematic with the digits of the part numbers highlighted, to be printed to a terminal.
+///
+/// # Errors
+///
+/// Fails if the lines of the input have different lengths, or a number is too large.
+pub fn render(input: &str) -> Result<String, AocError> {
+    let grid = parse_input(input)?;
+    Ok(render_part_numbers(&grid, &find_part_numbers(&grid)))
 }
 
-/// Takes an expanded grid string and the number of columns to return a Vec containing the part numbers.
-fn find_part_numbers(expanded_grid: &str, n_cols: usize) -> Vec<Number> {
-    // Numbers array and auxiliary variables
-    let mut numbers = Vec::<Number>::new();
-    let mut number_acc = Vec::<u8>::new();
-    let (mut start, mut end) = (0, 0);
-
-    // runs over the data storing all possible part numbers
-    for (row, line) in expanded_grid.lines().enumerate() {
-        for (col, char) in line.as_bytes().iter().enumerate() {
-            // push digits into accumulation buffer
-            if char.is_ascii_digit() && col < n_cols {
-                if number_acc.is_empty() {
-                    start = col;
-                }
-                number_acc.push(*char);
-                end = col;
-            }
+/// Parses the schematic into a grid.
+fn parse_input(input: &str) -> Result<Grid<u8>, AocError> {
+    parse_schematic(input).map_err(|e| e.in_day(3))
+}
 
-            // if numeric sequence ends, resolve number and save position
-            if !number_acc.is_empty() && (!char.is_ascii_digit() || col == n_cols - 1) {
-                // converts a sequence of characters into a number
-                let n: u32 = (0..number_acc.len()).fold(0, |acc, i| acc * 10 + (number_acc[i] - b'0') as u32);
-
-                // could have converted number_acc to string and done the parse
-                // let n: u32 = std::str::from_utf8(&number_acc).unwrap().parse().unwrap();
-
-                // saves number and its position
-                numbers.push(Number {
-                    val: n,
-                    row,
-                    start,
-                    end,
-                    is_part: false,
-                });
-
-                // clears auxiliary variables
-                number_acc.clear();
-                start = 0;
-                end = 0;
+/// Parses the schematic into a grid, failing if a number does not fit in a `u32`.
+fn parse_schematic(s: &str) -> Result<Grid<u8>, AocError> {
+    for (i, line) in s.lines().enumerate() {
+        for digits in scan::find_digits(line.as_bytes()) {
+            let number = &line[digits];
+            if number.parse::<u32>().is_err() {
+                let err = AocError::parse_at(line, number, format!("Number `{number}` is too large."));
+                return Err(err.at_line(i + 1));
             }
         }
     }
+    s.parse()
+}
 
-    // closure to determine if a character is considered a symbol
-    let contains_symbol = |s: &str| -> bool { s.contains(|c: char| c != '.' && !c.is_ascii_digit()) };
+/// Returns an iterator over the numbers of a grid, row by row.
+fn numbers(grid: &Grid<u8>) -> impl Iterator<Item = Number> + '_ {
+    grid.rows().enumerate().flat_map(|(row, line)| {
+        scan::find_digits(line).map(move |digits| Number {
+            // converts a sequence of characters into a number
+            val: line[digits.clone()]
+                .iter()
+                .fold(0, |acc, &d| acc * 10 + (d - b'0') as u32),
+            row,
+            start: digits.start,
+            end: digits.end - 1,
+        })
+    })
+}
 
-    // checks if numbers are part numbers
-    for n in &mut numbers {
-        // Because the grid has been extended, there is no need to deal with boundary conditions.
-        let start = n.start - 1;
-        let end = n.end + 1;
+/// Returns an iterator over the symbols of a grid, row by row.
+fn symbols(grid: &Grid<u8>) -> impl Iterator<Item = Symbol> + '_ {
+    grid.iter()
+        .filter(|(_, &c)| is_symbol(c))
+        .map(|(pos, &c)| Symbol { glyph: c as char, pos })
+}
 
-        let idx_curr = n.row * (n_cols + 1);
-        let idx_above = (n.row - 1) * (n_cols + 1);
-        let idx_below = (n.row + 1) * (n_cols + 1);
+/// Determines if a character is considered a symbol.
+fn is_symbol(c: u8) -> bool {
+    c != b'.' && !c.is_ascii_digit()
+}
 
-        // a number is a part number if there is a symbol adjacent to it
-        let is_part = contains_symbol(&expanded_grid[idx_above + start..idx_above + end + 1])     // line above
-        || contains_symbol(&expanded_grid[idx_below + start..idx_below + end + 1])                  // line below
-        || contains_symbol(&expanded_grid[idx_curr + start..idx_curr + start + 1])                  // character to the left
-        || contains_symbol(&expanded_grid[idx_curr + end..idx_curr + end + 1]); // character to the right
+/// Takes a grid and returns a Vec containing the part numbers.
+fn find_part_numbers(grid: &Grid<u8>) -> Vec<Number> {
+    // a number is a part number if there is a symbol adjacent to any of its digits
+    numbers(grid)
+        .filter(|n| {
+            (n.start..=n.end)
+                .flat_map(|col| grid.neighbors8(Position::new(n.row, col)))
+                .any(|pos| is_symbol(grid[pos]))
+        })
+        .collect()
+}
 
-        if is_part {
-            n.is_part = true;
+/// Takes a grid and an array of part numbers to return a Vec of the gears described by `spec`, indexing the part
+/// numbers by the gear symbols adjacent to them.
+fn find_gears(grid: &Grid<u8>, part_numbers: &[Number], spec: GearSpec) -> Vec<Gear> {
+    // values of the part numbers adjacent to each gear symbol, including the symbols without any
+    let mut adjacency: HashMap<Position, Vec<u32>> = grid
+        .iter()
+        .filter(|(_, &c)| c as char == spec.symbol)
+        .map(|(pos, _)| (pos, Vec::new()))
+        .collect();
+
+    for n in part_numbers {
+        // cells around the number, clamped to the grid
+        let rows = n.row.saturating_sub(1)..=(n.row + 1).min(grid.n_rows() - 1);
+        for row in rows {
+            for col in n.start.saturating_sub(1)..=(n.end + 1).min(grid.n_cols() - 1) {
+                let pos = Position::new(row, col);
+                if let Some(numbers) = adjacency.get_mut(&pos) {
+                    numbers.push(n.val);
+                }
+            }
         }
     }
 
-    // keep only part numbers
-    numbers.retain(|n| n.is_part);
-
-    numbers
+    adjacency
+        .values()
+        .filter_map(|numbers| Gear::new(numbers, spec))
+        .collect()
 }
 
-/// Takes a grid and an array of part numbers to return a Vec of gears.
-fn find_gears(grid: &str, part_numbers: &[Number]) -> Vec<Gear> {
+/// Takes a grid and an array of part numbers to return a Vec of the gears described by `spec`, comparing every gear
+/// symbol to every part number.
+fn find_gears_naive(grid: &Grid<u8>, part_numbers: &[Number], spec: GearSpec) -> Vec<Gear> {
     let mut gears = Vec::<Gear>::new();
 
-    for (row, line) in grid.lines().enumerate() {
-        for (col, &char) in line.as_bytes().iter().enumerate() {
-            if char == b'*' {
-                // checks adjacency
-                let mut adjacency = Vec::<u32>::new();
-                adjacency.extend(
-                    part_numbers
-                        .iter()
-                        .filter(|n| n.is_adjacent(Position::new(row, col)))
-                        .map(|n| n.val),
-                );
-
-                if let Some(gear) = Gear::new(&adjacency) {
-                    gears.push(gear);
-                }
+    for (pos, &char) in grid.iter() {
+        if char as char == spec.symbol {
+            // checks adjacency
+            let mut adjacency = Vec::<u32>::new();
+            adjacency.extend(part_numbers.iter().filter(|n| n.is_adjacent(pos)).map(|n| n.val));
+
+            if let Some(gear) = Gear::new(&adjacency, spec) {
+                gears.push(gear);
             }
         }
     }
@@ -163,50 +212,78 @@ fn find_gears(grid: &str, part_numbers: &[Number]) -> Vec<Gear> {
     gears
 }
 
-/// Returns the sum of all values stored in the `Number` struct
-fn sum_numbers(part_numbers: &[Number]) -> u32 {
-    part_numbers.iter().map(|n| n.val).sum()
+/// Renders the schematic with the digits of the part numbers highlighted.
+fn render_part_numbers(grid: &Grid<u8>, part_numbers: &[Number]) -> String {
+    let digits: HashSet<Position> = part_numbers
+        .iter()
+        .flat_map(|n| (n.start..=n.end).map(move |col| Position::new(n.row, col)))
+        .collect();
+    grid.render_highlighted(|&b| b as char, &digits)
 }
 
-/// Returns the sum of all gears ratios
-fn sum_gear_ratios(gears: &[Gear]) -> u32 {
-    gears.iter().map(|g| g.ratio).sum()
+/// Returns the sum of all values stored in the `Number` struct, or `None` if it overflows.
+fn sum_numbers(part_numbers: &[Number]) -> Option<u64> {
+    part_numbers.iter().map(|n| u64::from(n.val)).checked_sum()
 }
 
-/// Struct to store two-dimensional grid positions.
-struct Position {
-    row: usize,
-    col: usize,
+/// Returns the sum of all gears ratios, or `None` if it or a ratio overflows.
+fn sum_gear_ratios(gears: &[Gear]) -> Option<u64> {
+    let ratios: Option<Vec<u64>> = gears.iter().map(|g| g.ratio).collect();
+    ratios?.into_iter().checked_sum()
+}
+
+/// Engine schematic, giving access to its numbers and symbols.
+#[derive(Clone, Debug)]
+pub struct Schematic {
+    grid: Grid<u8>,
 }
 
 /// Struct representing numbers and part numbers on the grid. It stores the value of the number, the row where it is
-/// located, start and end positions within the row and indicates whether the number is a part number.
-struct Number {
-    val: u32,
-    row: usize,
-    start: usize,
-    end: usize,
-    is_part: bool,
+/// located, and its start and end positions (both inclusive) within the row.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Number {
+    pub val: u32,
+    pub row: usize,
+    pub start: usize,
+    pub end: usize,
 }
 
-/// Struct to represent gears and store the gear ratio.
-struct Gear {
-    ratio: u32,
+/// Struct representing a symbol on the grid: its character and position.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Symbol {
+    pub glyph: char,
+    pub pos: Position,
 }
 
-impl Position {
-    /// Creates a new `Position` from row and column indexes within grid.
-    fn new(row: usize, col: usize) -> Position {
-        Position { row, col }
-    }
+/// Description of gears: a `symbol` adjacent to exactly `arity` part numbers.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GearSpec {
+    pub symbol: char,
+    pub arity: usize,
+}
+
+/// Search of the gears of part 2.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GearSearch {
+    /// Indexes the symbols around each part number, linear in the size of the grid.
+    Indexed,
+    /// Compares every symbol to every part number, quadratic.
+    Naive,
+}
+
+/// Struct to represent gears and store the gear ratio, `None` if the product overflows.
+struct Gear {
+    ratio: Option<u64>,
 }
 
 impl Gear {
     /// Creates a new `Gear` if it meets the adjacency parameters.
-    fn new(adjacency: &[u32]) -> Option<Gear> {
-        if adjacency.len() == 2 {
+    /// @param `adjacency`: values of the part numbers adjacent to the symbol.
+    /// @param `spec`: description of the gears, only its arity is checked.
+    fn new(adjacency: &[u32], spec: GearSpec) -> Option<Gear> {
+        if adjacency.len() == spec.arity {
             return Some(Gear {
-                ratio: adjacency.iter().product(),
+                ratio: adjacency.iter().map(|&n| u64::from(n)).checked_product(),
             });
         }
 
@@ -214,14 +291,71 @@ impl Gear {
     }
 }
 
+impl Schematic {
+    /// Returns an iterator over the numbers of the schematic, row by row.
+    pub fn numbers(&self) -> impl Iterator<Item = Number> + '_ {
+        numbers(&self.grid)
+    }
+
+    /// Returns an iterator over the symbols of the schematic, any character that is neither a digit nor a '.', row by
+    /// row.
+    pub fn symbols(&self) -> impl Iterator<Item = Symbol> + '_ {
+        symbols(&self.grid)
+    }
+
+    /// Returns an iterator over the part numbers of the schematic, the numbers adjacent to a symbol.
+    pub fn part_numbers(&self) -> impl Iterator<Item = Number> {
+        find_part_numbers(&self.grid).into_iter()
+    }
+
+    /// Returns the ratios of the gears described by `spec`, the products of their adjacent part numbers, in no
+    /// particular order.
+    ///
+    /// # Errors
+    ///
+    /// Fails if a ratio overflows.
+    pub fn gear_ratios(&self, spec: GearSpec) -> Result<Vec<u64>, AocError> {
+        let gears = find_gears(&self.grid, &find_part_numbers(&self.grid), spec);
+        gears.iter().map(|g| g.ratio.ok_or_else(AocError::overflow)).collect()
+    }
+}
+
+impl FromStr for Schematic {
+    type Err = AocError;
+
+    /// Parses a schematic.
+    /// @param `s`: lines of the same length, with digits, '.' and symbols. The numbers must fit in a `u32`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Schematic {
+            grid: parse_schematic(s)?,
+        })
+    }
+}
+
+impl FromStr for GearSearch {
+    type Err = AocError;
+
+    /// Parses the name of a search, `indexed` or `naive`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "indexed" => Ok(GearSearch::Indexed),
+            "naive" => Ok(GearSearch::Naive),
+            _ => Err(AocError::invalid(format!(
+                "unknown gear search `{s}`, expected `indexed` or `naive`"
+            ))),
+        }
+    }
+}
+
 impl Number {
     /// Checks if a given position is adjacent to a number on the grid.
-    fn is_adjacent(&self, symbol: Position) -> bool {
+    pub fn is_adjacent(&self, symbol: Position) -> bool {
         // avoids the use of subtraction, cuz it may cause overflow on edge cases
         self.row.abs_diff(symbol.row) <= 1  // same row, one above or one below
             && symbol.col <= (self.end + 1) // must be at most the position immediately after end
-            && (symbol.col >= self.start || self.start.abs_diff(symbol.col) == 1) // must be at least the position
-                                                                                  // immediately before start
+            && (symbol.col >= self.start || self.start.abs_diff(symbol.col) == 1)
+        // must be at least the position
+        // immediately before start
     }
 }
 
@@ -231,10 +365,9 @@ impl Number {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::aoc_test;
 
-    #[test]
-    fn tests() {
-        let input = "467..114..
+    const SAMPLE: &str = "467..114..
 ...*......
 ..35..633.
 ......#...
@@ -245,14 +378,110 @@ mod tests {
 ...$.*....
 .664.598..";
 
-        let (grid, _, n_cols) = expand_borders(input, '.');
-        let part_numbers = find_part_numbers(&grid, n_cols);
+    aoc_test! {
+        sample: SAMPLE,
+        pt01_sample: solve_part1 => 4361,
+        pt02_sample: solve_part2 => 467835,
+    }
+
+    #[test]
+    fn test_line_endings() {
+        let crlf = SAMPLE.replace('\n', "\r\n") + "\r\n";
+        assert_eq!(solve_part1(&crlf).unwrap(), 4361);
+        assert_eq!(solve_part2(&crlf).unwrap(), 467835);
+    }
+
+    #[test]
+    fn test_gears() {
+        // numbers touching a '*' from every side, and a '*' with three part numbers
+        let input = "12*3..
+...*..
+.4.5.6
+..*...
+7..8*9";
+        for input in [SAMPLE, input] {
+            let grid = parse_input(input).unwrap();
+            let part_numbers = find_part_numbers(&grid);
+            for arity in 0..4 {
+                let spec = GearSpec { symbol: '*', arity };
+                let mut indexed: Vec<_> = find_gears(&grid, &part_numbers, spec).iter().map(|g| g.ratio).collect();
+                let mut naive: Vec<_> = find_gears_naive(&grid, &part_numbers, spec)
+                    .iter()
+                    .map(|g| g.ratio)
+                    .collect();
+                indexed.sort_unstable();
+                naive.sort_unstable();
+                assert_eq!(indexed, naive);
+            }
+        }
+        assert_eq!(solve_part2(input).unwrap(), 36 + 15 + 72);
+        assert_eq!(
+            run_with(SAMPLE, "naive".parse().unwrap()).unwrap(),
+            run(SAMPLE).unwrap()
+        );
+
+        // what-if: gears of three part numbers, and other symbols
+        let schematic: Schematic = input.parse().unwrap();
+        assert_eq!(
+            schematic.gear_ratios(GearSpec { symbol: '*', arity: 3 }).unwrap(),
+            [4 * 5 * 8]
+        );
+        let schematic: Schematic = SAMPLE.parse().unwrap();
+        assert_eq!(
+            schematic.gear_ratios(GearSpec { symbol: '+', arity: 1 }).unwrap(),
+            [592]
+        );
+        assert_eq!(schematic.gear_ratios(GearSpec { symbol: '$', arity: 2 }).unwrap(), []);
+        let schematic: Schematic = "1.*\n*..".parse().unwrap();
+        assert_eq!(schematic.gear_ratios(GearSpec { symbol: '*', arity: 0 }).unwrap(), [1]);
+    }
 
-        // Part 01
-        assert_eq!(sum_numbers(&part_numbers), 4361);
+    #[test]
+    fn test_schematic() {
+        let schematic: Schematic = SAMPLE.parse().unwrap();
+        assert_eq!(schematic.numbers().count(), 10);
+        assert_eq!(
+            schematic.numbers().nth(2),
+            Some(Number {
+                val: 35,
+                row: 2,
+                start: 2,
+                end: 3
+            })
+        );
+        let glyphs: String = schematic.symbols().map(|s| s.glyph).collect();
+        assert_eq!(glyphs, "*#*+$*");
+        assert_eq!(schematic.part_numbers().map(|n| n.val).sum::<u32>(), 4361);
+
+        // custom questions: the numbers adjacent to '#' only, and the symbols adjacent to two numbers
+        let hash = schematic.symbols().find(|s| s.glyph == '#').unwrap();
+        let near_hash: Vec<_> = schematic
+            .numbers()
+            .filter(|n| n.is_adjacent(hash.pos))
+            .map(|n| n.val)
+            .collect();
+        assert_eq!(near_hash, [633]);
+        let pairs = schematic
+            .symbols()
+            .filter(|s| schematic.numbers().filter(|n| n.is_adjacent(s.pos)).count() == 2)
+            .count();
+        assert_eq!(pairs, 2);
+    }
 
-        // Part 02
-        let gears = find_gears(&grid, &part_numbers);
-        assert_eq!(sum_gear_ratios(&gears), 467835);
+    #[test]
+    fn test_large_numbers() {
+        let err = solve_part1("1.\n*.\n.4294967296").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Day 03, line 3, column 2: Number `4294967296` is too large."
+        );
+
+        assert_eq!(solve_part1("4000000000*4000000000").unwrap(), 8000000000);
+        assert_eq!(solve_part2("4000000000*4000000000").unwrap(), 16000000000000000000);
+        let err = solve_part2("4000000000*4000000000.4000000000*4000000000").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid input: the numbers are too large, the answer overflows"
+        );
     }
 }
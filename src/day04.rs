@@ -19,40 +19,130 @@
 //!
 //! # Solution
 //!
-//! - Using `Hashset` to find the matches between lottery and winning numbers;
+//! - Using a `BitSet` to find the matches between lottery and winning numbers, without allocating. The `day04`
+//!   benchmark compares it with the two `HashSet`s per card of the first solution;
 //!
-//! - Employing an array to track the quantities of each card by id.
+//! - Employing a queue to track the copies won of the next cards, only as long as the most matches of a card;
+//!
+//! - Processing the cards one line at a time, from any iterator of lines, so the memory does not grow with the number
+//!   of cards. `run_lines` solves both parts in a single pass over lines streamed by `input::lines`.
+//!
+//! The solver only keeps the number of matches of each card, while `card_details` returns the numbers of the cards, to
+//! render or audit them.
+
+use crate::{answer::Answer, bitset::BitSet, error::AocError};
+use std::{collections::VecDeque, fmt, str::FromStr};
+
+pub fn run(input: &str) -> Result<[Answer; 2], AocError> {
+    run_lines(input.lines().map(Ok))
+}
 
-use std::{collections::HashSet, error, fs, str::FromStr};
-pub fn run() {
-    let input = fs::read_to_string("inputs/day04").unwrap();
-    let cards: Vec<Scratchcard> = input.lines().flat_map(str::parse).collect();
+/// Solves both parts in a single pass over the lines of the input, read one at a time.
+/// @param `lines`: lines of the input, like the ones streamed by `input::lines`.
+///
+/// # Errors
+///
+/// Fails if a line can not be read, the input is malformed, or an answer overflows.
+pub fn run_lines(lines: impl IntoIterator<Item = Result<impl AsRef<str>, AocError>>) -> Result<[Answer; 2], AocError> {
+    // `None` once the points overflow
+    let mut points = Some(0u32);
+    let cards = parse_cards(lines).inspect(|card| {
+        if let Ok(card) = card {
+            points = points.zip(card.points()).and_then(|(total, p)| total.checked_add(p));
+        }
+    });
+    let n_cards = process_card_pile(cards)?;
+    let points = points.ok_or_else(AocError::overflow)?;
+    Ok([points.into(), n_cards.into()])
+}
 
-    // part 1
-    let total_points: u32 = cards.iter().map(|c| c.points()).sum();
-    println!("Part 01: Total points: {}", total_points);
+/// Returns the total points of the scratchcards.
+///
+/// # Errors
+///
+/// Fails if the input is malformed, or the points overflow.
+pub fn solve_part1(input: &str) -> Result<u32, AocError> {
+    total_points(parse_cards(input.lines().map(Ok)))
+}
 
-    // part 2
-    let total_cards: u32 = process_card_pile(&cards);
-    println!("Part 02: Total cards: {}", total_cards);
+/// Returns the total number of scratchcards after winning copies of the following cards.
+///
+/// # Errors
+///
+/// Fails if the input is malformed, or the number of cards overflows.
+pub fn solve_part2(input: &str) -> Result<u32, AocError> {
+    process_card_pile(parse_cards(input.lines().map(Ok)))
+}
+
+/// Returns the winning and lottery numbers of every scratchcard, in order.
+///
+/// # Errors
+///
+/// Fails if the input is malformed.
+pub fn card_details(input: &str) -> Result<Vec<CardDetails>, AocError> {
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.is_empty())
+        .map(|(i, line)| line.parse().map_err(|e: AocError| e.at_line(i + 1).in_day(4)))
+        .collect()
 }
 
 //----------
 // Helper methods
 //----------
 
+/// Parses the scratchcards of the non-empty lines, one at a time.
+/// @param `lines`: lines of the input, like the ones of a `str` or streamed by `input::lines`, or the errors reading
+/// them, which are passed on.
+fn parse_cards(
+    lines: impl IntoIterator<Item = Result<impl AsRef<str>, AocError>>,
+) -> impl Iterator<Item = Result<Scratchcard, AocError>> {
+    lines
+        .into_iter()
+        .enumerate()
+        .filter(|(_, line)| !matches!(line, Ok(line) if line.as_ref().is_empty()))
+        .map(|(i, line)| line?.as_ref().parse().map_err(|e: AocError| e.at_line(i + 1).in_day(4)))
+}
+
+/// Returns the total points of the scratchcards, or the error of the first card that could not be parsed. Fails if the
+/// points overflow.
+fn total_points(cards: impl IntoIterator<Item = Result<Scratchcard, AocError>>) -> Result<u32, AocError> {
+    cards.into_iter().try_fold(0u32, |total, card| {
+        card?
+            .points()
+            .and_then(|points| total.checked_add(points))
+            .ok_or_else(AocError::overflow)
+    })
+}
+
 /// Returns the final number of cards based on the rules defined in part 2.
-/// @param `cards`: reference to an array of `Scratchcards` ordered by `id`. The `id`s must be sequential, starting at
-/// 1, to ensure accurate processing and output calculation.
-fn process_card_pile(cards: &[Scratchcard]) -> u32 {
-    // Occurrence array. Initially, we have one of each card.
-    let mut card_pile = vec![1; cards.len()];
+/// @param `cards`: `Scratchcards` ordered by `id`. The `id`s must be sequential, starting at 1, to ensure accurate
+/// processing and output calculation. Fails with the error of the first card that could not be parsed, or if the number
+/// of cards overflows.
+fn process_card_pile(cards: impl IntoIterator<Item = Result<Scratchcard, AocError>>) -> Result<u32, AocError> {
+    // Copies won of the next cards, starting with the current one. Copies past the last card are never counted.
+    let mut copies = VecDeque::new();
+    let mut total = 0u32;
     for card in cards {
-        // `card.id` starts at one instead of zero.
-        let n_cards = card_pile[card.id - 1];
-        (card.id..card.id + card.matches as usize).for_each(|i| card_pile[i] += n_cards);
+        let card = card?;
+        // the original card and its copies
+        let n_cards = copies
+            .pop_front()
+            .unwrap_or(0u32)
+            .checked_add(1)
+            .ok_or_else(AocError::overflow)?;
+        total = total.checked_add(n_cards).ok_or_else(AocError::overflow)?;
+
+        let matches = card.matches as usize;
+        if copies.len() < matches {
+            copies.resize(matches, 0);
+        }
+        for c in copies.iter_mut().take(matches) {
+            *c = c.checked_add(n_cards).ok_or_else(AocError::overflow)?;
+        }
     }
-    card_pile.iter().sum()
+    Ok(total)
 }
 
 //----------
@@ -66,47 +156,104 @@ struct Scratchcard {
     matches: u32,
 }
 
+/// Stores a scratchcard `id` with its `winning` and `lottery` numbers.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CardDetails {
+    pub id: usize,
+    pub winning: BitSet,
+    pub lottery: BitSet,
+}
+
 impl Scratchcard {
-    /// Calculates the point value of the `Scratchcard`,
-    fn points(&self) -> u32 {
+    /// Calculates the point value of the `Scratchcard`, or `None` if it overflows.
+    fn points(&self) -> Option<u32> {
         if self.matches > 0 {
-            2u32.pow(self.matches - 1)
+            2u32.checked_pow(self.matches - 1)
         } else {
-            0
+            Some(0)
+        }
+    }
+}
+
+impl CardDetails {
+    /// Returns the lottery numbers that are winning numbers.
+    pub fn matched(&self) -> BitSet {
+        self.winning.intersection(&self.lottery)
+    }
+
+    /// Calculates the point value of the card, or `None` if it overflows.
+    pub fn points(&self) -> Option<u32> {
+        Scratchcard::from(self).points()
+    }
+}
+
+impl From<&CardDetails> for Scratchcard {
+    fn from(card: &CardDetails) -> Self {
+        Scratchcard {
+            id: card.id,
+            matches: card.matched().len() as u32,
         }
     }
 }
 
 impl FromStr for Scratchcard {
-    type Err = Box<dyn error::Error>;
+    type Err = AocError;
+
+    /// Parses a Scratchcard, only keeping its number of matches. See `CardDetails::from_str`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Scratchcard::from(&s.parse::<CardDetails>()?))
+    }
+}
+
+impl FromStr for CardDetails {
+    type Err = AocError;
 
-    /// Parses a Scratchcard.
+    /// Parses the numbers of a Scratchcard.
     /// @param `s`: String expected in the format: "Card {id}: {winning numbers} | {lottery numbers}". Here, winning and
     /// lottery numbers are sequences of unsigned integers separated by whitespace.
     /// A valid example is: "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53".
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut iter = s
             .strip_prefix("Card")
-            .ok_or("`Card` prefix not found.")?
+            .ok_or_else(|| AocError::parse("`Card` prefix not found."))?
             .split([':', '|']);
-        let id: usize = iter.next().ok_or("Not able to get `ID`.")?.trim().parse()?;
+        let id = iter
+            .next()
+            .ok_or_else(|| AocError::parse("Not able to get `ID`."))?
+            .trim();
+        let id: usize = id.parse().map_err(|e| AocError::parse_at(s, id, e))?;
 
-        let mut parse_number_sequence = || -> Result<HashSet<u32>, Self::Err> {
-            Ok(iter
+        let mut parse_number_sequence = || -> Result<BitSet, Self::Err> {
+            let mut set = BitSet::new();
+            for token in iter
                 .next()
-                .ok_or("Not able to find a number sequence.")?
+                .ok_or_else(|| AocError::parse("Not able to find a number sequence."))?
                 .split_ascii_whitespace()
-                .map(str::parse)
-                .collect::<Result<HashSet<u32>, _>>()?)
+            {
+                let n: usize = token.parse().map_err(|e| AocError::parse_at(s, token, e))?;
+                if n >= BitSet::<2>::CAPACITY {
+                    let message = format!("Number {n} is too large for a scratchcard.");
+                    return Err(AocError::parse_at(s, token, message));
+                }
+                set.insert(n);
+            }
+            Ok(set)
         };
 
-        let win = parse_number_sequence()?;
+        let winning = parse_number_sequence()?;
         let lottery = parse_number_sequence()?;
 
-        Ok(Scratchcard {
-            id,
-            matches: win.intersection(&lottery).count() as u32,
-        })
+        Ok(CardDetails { id, winning, lottery })
+    }
+}
+
+/// Formats the card like the input, with the numbers in increasing order: "Card 1: 17 41 | 6 83".
+impl fmt::Display for CardDetails {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Card {}:", self.id)?;
+        self.winning.iter().try_for_each(|n| write!(f, " {n}"))?;
+        write!(f, " |")?;
+        self.lottery.iter().try_for_each(|n| write!(f, " {n}"))
     }
 }
 
@@ -116,31 +263,112 @@ impl FromStr for Scratchcard {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{aoc_test, input::Lines, testing::corrupt};
+    use proptest::{prelude::*, sample::Index};
 
     #[test]
     fn test_parse_scratchcard() {
         let input = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53";
         assert_eq!(input.parse::<Scratchcard>().unwrap(), Scratchcard { id: 1, matches: 4 });
+
+        let err = "Card 1: 41 48 | 83 4x".parse::<Scratchcard>().unwrap_err();
+        assert!(err.to_string().starts_with("Line 1, column 20:"));
+        let err = "Card 1: 41 48 | 83 400".parse::<Scratchcard>().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Line 1, column 20: Number 400 is too large for a scratchcard."
+        );
     }
 
-    #[test]
-    fn tests() {
-        let input = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+    const SAMPLE: &str = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
 Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
 Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
 Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
 Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
 Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
 
-        let cards: Vec<Scratchcard> = input.lines().flat_map(str::parse).collect();
+    aoc_test! {
+        sample: SAMPLE,
+        pt01_points: |s: &str| {
+            parse_cards(s.lines().map(Ok))
+                .map(|c| c.map(|c| c.points().unwrap()))
+                .collect::<Result<Vec<_>, _>>()
+        } => [8, 2, 2, 1, 0, 0],
+        pt01_sample: solve_part1 => 13,
+        pt02_sample: solve_part2 => 30,
+    }
+
+    #[test]
+    fn test_run_lines() {
+        let input = SAMPLE.replace('\n', "\r\n") + "\r\n";
+        let lines = Lines::new(input.as_bytes(), "test");
+        assert_eq!(run_lines(lines).unwrap(), [13u32.into(), 30u32.into()]);
 
-        // part 1
-        assert_eq!(cards[0].points(), 8);
-        assert_eq!(cards[1].points(), 2);
-        assert_eq!(cards[3].points(), 1);
-        assert_eq!(cards[5].points(), 0);
+        let err = run_lines(Lines::new("Card 1: 1 | 1\nCard 2: 1".as_bytes(), "test")).unwrap_err();
+        assert!(err.to_string().starts_with("Day 04, line 2"), "{err}");
+    }
+
+    #[test]
+    fn test_overflow() {
+        let overflow = "Invalid input: the numbers are too large, the answer overflows";
 
-        // part 2
-        assert_eq!(process_card_pile(&cards), 30);
+        // a card matching 40 numbers is worth 2^39 points
+        let numbers = (1..=40).map(|n| n.to_string()).collect::<Vec<_>>().join(" ");
+        let card = format!("Card 1: {numbers} | {numbers}");
+        assert_eq!(solve_part1(&card).unwrap_err().to_string(), overflow);
+        assert_eq!(solve_part2(&card).unwrap(), 1);
+
+        // the copies of cards matching 10 numbers each double with every card
+        let numbers = (1..=10).map(|n| n.to_string()).collect::<Vec<_>>().join(" ");
+        let cards: String = (1..=39)
+            .map(|id| format!("Card {id}: {numbers} | {numbers}\n"))
+            .collect();
+        assert_eq!(solve_part1(&cards).unwrap(), 39 * 512);
+        assert_eq!(solve_part2(&cards).unwrap_err().to_string(), overflow);
+        assert_eq!(run(&cards).unwrap_err().to_string(), overflow);
+    }
+
+    #[test]
+    fn test_card_details() {
+        let cards = card_details(SAMPLE).unwrap();
+        assert_eq!(cards.len(), 6);
+        assert_eq!(cards[0].winning.iter().collect::<Vec<_>>(), [17, 41, 48, 83, 86]);
+        assert_eq!(cards[0].matched().iter().collect::<Vec<_>>(), [17, 48, 83, 86]);
+        assert_eq!(
+            cards.iter().map(|c| c.points()).collect::<Vec<_>>(),
+            [8, 2, 2, 1, 0, 0].map(Some)
+        );
+
+        // the details agree with the count-only path
+        for (card, line) in cards.iter().zip(SAMPLE.lines()) {
+            assert_eq!(Scratchcard::from(card), line.parse::<Scratchcard>().unwrap());
+        }
+    }
+
+    /// Cards with up to 9 winning numbers and 9 numbers drawn, all below 100.
+    fn card() -> impl Strategy<Value = CardDetails> {
+        let numbers = || prop::collection::vec(0..100usize, 0..10).prop_map(|numbers| numbers.into_iter().collect());
+        (0..1000usize, numbers(), numbers()).prop_map(|(id, winning, lottery)| CardDetails { id, winning, lottery })
+    }
+
+    proptest! {
+        #[test]
+        fn test_card_round_trip(
+            card in card(),
+            index in any::<Index>(),
+            c in prop::sample::select(&['x', '-', '#'][..]),
+        ) {
+            let line = card.to_string();
+            prop_assert_eq!(line.parse::<CardDetails>().unwrap(), card, "{}", line);
+
+            let corrupted = corrupt(&line, index, c);
+            prop_assert!(corrupted.parse::<CardDetails>().is_err(), "{}", corrupted);
+        }
+
+        // arbitrary lines fail without panicking
+        #[test]
+        fn test_card_garbage(line in "[Card19:| ]{0,20}") {
+            let _ = line.parse::<CardDetails>();
+        }
     }
 }
@@ -15,185 +15,419 @@
 //!
 //! # Solution
 //!
-//! - Utilizes brute force by applying the mapping transformation to all seeds.
+//! - The seven maps are first composed into a single seed to location map, so each seed, or seed range, is resolved
+//!   with one binary search instead of seven.
 //!
-//! - For part 2, uses Rayon to paralelize the iterator.
+//! - Utilizes brute force by applying the composed map to all seeds.
 //!
-//! The single-threaded version for part 2 took around 75 seconds, whereas the parallel solution ran in 9.5 seconds,
-//! handling over 3,5 billion seeds. These timing are amazing and if compared to what people were reporting for brute
-//! force approaches on the Advent of Code subreddit.
+//! - For part 2, pushes the seed ranges through the composed map as a whole: it splits a range at the boundaries of its
+//!   entries and shifts the pieces, and the lowest location is the lowest start of the resulting ranges. It runs in
+//!   microseconds.
 //!
-//! # Possible Alternative Solutions
+//! The first solution of part 2 was brute force, using Rayon to paralelize the iterator. The single-threaded version
+//! took around 75 seconds, whereas the parallel solution ran in 9.5 seconds, handling over 3,5 billion seeds. These
+//! timing are amazing and if compared to what people were reporting for brute force approaches on the Advent of Code
+//! subreddit. It is still available, to cross-validate the range splitting, as `Strategy::BruteForce`, selected with
+//! `run_with` or with `aoc2023 5 --strategy brute-force` (`intervals` is the default).
+//!
+//! `Strategy::Reverse`, or `--strategy reverse`, selects a reverse search brute force: begins from the lowest possible
+//! position and walks up the maps, iterates until finding an initial seed. The number of positions to search should be
+//! much lower than the total amount of seeds for part 2, due to the location being in the order of millions instead of
+//! billions.
 //!
-//! - Reverse search brute force: Begins from the lowest possible position and walks up the maps, iterates until finding
-//!   an initial seed. The number of positions to search should be much lower than the total amount of seeds for part 2,
-//!   due to the location being in the order of millions instead of billions.
+//! The entries of a map are sorted by their source ranges when they are read, so their order in the input does not
+//! matter, but overlapping source ranges make the map ambiguous and are rejected. [`validate_almanac`] reports them
+//! along with the gaps between the ranges of each map, the values that are silently mapped to themselves, and is run
+//! with the `--validate` option. Negative numbers, and ranges ending beyond the largest `i64`, are rejected with the
+//! position of the number.
+//!
+//! # Possible Alternative Solutions
 //!
 //! - Bucket splitting: Operates with buckets of seeds instead of individual seeds. When a bucket is larger than a
 //!   range, split the bucket. Working with buckets should vastly reduce the input size.
-//!
-//! - Range splitting: Preprocesses the maps by segmenting the ranges into smaller ones until establishing a direct map
-//!   between seed and location.
+use crate::{
+    answer::Answer,
+    error::AocError,
+    interval::Interval,
+    range_map::RangeMap,
+    token::{TokenKind, Tokenizer},
+};
 use rayon::prelude::*;
-use std::{cmp::Ordering, error, fs, str::FromStr, time::Instant};
+use std::{fmt, str::FromStr};
 
-type Seeds = Vec<u64>;
-type AMap = Vec<Entry>;
-type Almanac = Vec<AMap>;
+type Seeds = Vec<i64>;
+type Almanac = Vec<RangeMap>;
 
-pub fn run() {
-    let input = fs::read_to_string("inputs/day05").unwrap();
-    let (seeds, almanac) = parse_input(&input);
+pub fn run(input: &str) -> Result<[Answer; 2], AocError> {
+    run_with(input, Strategy::Intervals)
+}
 
-    // part 1
-    let location = process_lowest_location(&seeds, &almanac);
-    println!("Part 1: Lowest Location number: {}", location);
+/// Solves both parts, finding the lowest location of part 2 with the given strategy.
+///
+/// # Errors
+///
+/// Fails if the input is malformed, has no seed, or the seeds do not describe ranges of seeds.
+pub fn run_with(input: &str, strategy: Strategy) -> Result<[Answer; 2], AocError> {
+    let (seeds, almanac) = parse_input(input)?;
+    let seed_to_location = compose_almanac(&almanac);
+    let lowest = process_lowest_location(&seeds, &seed_to_location).ok_or_else(no_seeds)?;
+
+    Ok([
+        (lowest as u64).into(),
+        strategy
+            .lowest_location_pt2(&seeds, &almanac, &seed_to_location)?
+            .into(),
+    ])
+}
 
-    // part 2
-    let now = Instant::now();
-    let location_pt2 = process_lowest_location_pt2_mt(&seeds, &almanac);
-    let elapsed = now.elapsed();
-    println!("Elapsed: {:.2?}", elapsed);
-    println!("Part 2: Lowest Location number: {}", location_pt2);
+/// Returns the lowest location number of the seeds.
+///
+/// # Errors
+///
+/// Fails if the input is malformed, or has no seed.
+pub fn solve_part1(input: &str) -> Result<u64, AocError> {
+    let (seeds, almanac) = parse_input(input)?;
+    let lowest = process_lowest_location(&seeds, &compose_almanac(&almanac)).ok_or_else(no_seeds)?;
+    Ok(lowest as u64)
+}
+
+/// Returns the lowest location number of the seeds, reading the seeds line as pairs of range start and length.
+///
+/// # Errors
+///
+/// Fails if the input is malformed, or the seeds line does not hold pairs of numbers describing some seed.
+pub fn solve_part2(input: &str) -> Result<u64, AocError> {
+    let (seeds, almanac) = parse_input(input)?;
+    let seed_to_location = compose_almanac(&almanac);
+    Ok(Strategy::Intervals.lowest_location_pt2(&seeds, &almanac, &seed_to_location)? as u64)
+}
+
+/// Returns the overlaps and the gaps between the source ranges of each map of the almanac, in the order of the maps
+/// and, within a map, of the source ranges.
+///
+/// # Errors
+///
+/// Fails if the input is malformed.
+pub fn validate_almanac(input: &str) -> Result<Vec<AlmanacIssue>, AocError> {
+    let (_, blocks) = parse_blocks(input).map_err(|e| e.in_day(5))?;
+    Ok(blocks.iter().flat_map(MapBlock::issues).collect())
 }
 
 /// Parse an input string into `Seeds` and `Almanac`
 ///
-/// Maps are stored in the order of occurrence, while the entries within the maps are sorted.
-fn parse_input(s: &str) -> (Seeds, Almanac) {
-    let mut iter = s.split("\n\n");
-
-    let seeds = iter
-        .next()
-        .unwrap()
-        .strip_prefix("seeds: ")
-        .unwrap()
-        .split_ascii_whitespace()
-        .flat_map(str::parse)
-        .collect::<Seeds>();
-
-    let almanac = iter
-        .map(|s| s.lines().skip(1).flat_map(str::parse).collect::<AMap>())
-        .map(|mut vec| {
-            vec.sort_unstable();
-            vec
-        })
-        .collect::<Almanac>();
+/// Maps are stored in the order of occurrence.
+fn parse_input(s: &str) -> Result<(Seeds, Almanac), AocError> {
+    parse_almanac(s).map_err(|e| e.in_day(5))
+}
+
+/// Parses the almanac, failing if the source ranges of a map overlap.
+fn parse_almanac(s: &str) -> Result<(Seeds, Almanac), AocError> {
+    let (seeds, blocks) = parse_blocks(s)?;
+
+    let mut almanac: Almanac = Vec::new();
+    for block in blocks {
+        if let Some(issue) = block.issues().into_iter().find(|issue| issue.kind.is_overlap()) {
+            return Err(AocError::invalid(issue));
+        }
+        let entries = block.entries.iter().map(|e| (e.source, e.destination_start));
+        almanac.push(entries.collect());
+    }
 
-    (seeds, almanac)
+    Ok((seeds, almanac))
 }
 
-/// Returns the lowest location from the `Seeds` and `Almanac` inputs.
-fn process_lowest_location(seeds: &Seeds, almanac: &Almanac) -> u64 {
-    // position is given by the last map
-    let mut location = u64::MAX;
-    let mut val;
-    for &seed in seeds {
-        val = seed;
-        for map in almanac {
-            let idx = map.binary_search_by(|e| e.cmp_to(val));
+/// Parses the seeds, "seeds: {numbers}", followed by the maps, each with a header like "seed-to-soil map:" and one
+/// entry per line, "{destination start} {source start} {length}".
+fn parse_blocks(s: &str) -> Result<(Seeds, Vec<MapBlock>), AocError> {
+    let mut tokens = Tokenizer::new(s);
+
+    tokens.expect_keyword("seeds")?;
+    tokens.expect_symbol(':')?;
+    let mut seeds: Seeds = Vec::new();
+    while tokens.peek_kind() == Some(TokenKind::Number) {
+        let (seed, offset) = almanac_number(&mut tokens, s)?;
+        // part 2 reads the seeds as pairs of range start and length
+        if seeds.len() % 2 == 1 && Interval::checked_with_len(seeds[seeds.len() - 1], seed).is_none() {
+            return Err(AocError::parse_at_offset(
+                s,
+                offset,
+                "The seed range ends beyond the largest number.",
+            ));
+        }
+        seeds.push(seed);
+    }
 
-            val = if let Ok(idx) = idx {
-                let diff = val - map[idx].start;
-                map[idx].destination_start + diff
-            } else {
-                val
-            };
+    let mut blocks = Vec::new();
+    while !tokens.is_empty() {
+        let from = tokens.expect_word()?;
+        tokens.expect_symbol('-')?;
+        tokens.expect_keyword("to")?;
+        tokens.expect_symbol('-')?;
+        let to = tokens.expect_word()?;
+        tokens.expect_keyword("map")?;
+        tokens.expect_symbol(':')?;
+
+        let mut entries = Vec::new();
+        while let Some(token) = tokens.peek().filter(|token| token.kind == TokenKind::Number) {
+            let line = s[..token.span.start].matches('\n').count() + 1;
+            let (destination_start, _) = almanac_number(&mut tokens, s)?;
+            let (start, _) = almanac_number(&mut tokens, s)?;
+            let (len, offset) = almanac_number(&mut tokens, s)?;
+            let source = Interval::checked_with_len(start, len)
+                .filter(|_| Interval::checked_with_len(destination_start, len).is_some())
+                .ok_or_else(|| AocError::parse_at_offset(s, offset, "The range ends beyond the largest number."))?;
+            entries.push(BlockEntry {
+                line,
+                source,
+                destination_start,
+            });
         }
-        location = location.min(val);
+        blocks.push(MapBlock {
+            name: format!("{from}-to-{to}"),
+            entries,
+        });
     }
 
-    location
+    Ok((seeds, blocks))
+}
+
+/// Reads the next number of the almanac, which can not be negative, along with its byte offset in `s`.
+fn almanac_number(tokens: &mut Tokenizer, s: &str) -> Result<(i64, usize), AocError> {
+    let offset = tokens.peek().map_or(s.len(), |token| token.span.start);
+    let value = tokens.number()?;
+    if value < 0 {
+        return Err(AocError::parse_at_offset(
+            s,
+            offset,
+            format!("Negative number `{value}`."),
+        ));
+    }
+    Ok((value, offset))
+}
+
+/// Returns the error of an almanac without seeds.
+fn no_seeds() -> AocError {
+    AocError::invalid("there are no seeds")
 }
 
-/// Returns the lowest location from the `Seeds` and `Almanac` inputs. Internally, seeds will be processed using part
-/// 2 rules and muti-threading.
+/// Composes the maps of the almanac, in order, into a single map from seeds to locations.
+fn compose_almanac(almanac: &Almanac) -> RangeMap {
+    almanac
+        .iter()
+        .fold(RangeMap::new(), |composed, map| composed.compose(map))
+}
+
+/// Returns the lowest location of the `Seeds`, given the composed map of the almanac, or `None` if there is no seed.
+fn process_lowest_location(seeds: &Seeds, seed_to_location: &RangeMap) -> Option<i64> {
+    seeds.iter().map(|&seed| seed_to_location.lookup(seed)).min()
+}
+
+/// Returns the lowest location of the seed ranges of part 2, given the composed map of the almanac, or `None` if the
+/// ranges hold no seed. Internally, seeds will be processed using multi-threading.
 ///
-/// It is the same algorithm as part 1, but rewritten to use only iterators, allowing parallel execution with Rayon.
-fn process_lowest_location_pt2_mt(seeds: &Seeds, almanac: &Almanac) -> u64 {
-    let seeds = seeds.par_chunks(2).flat_map(|a| (a[0]..a[0] + a[1])); // .take(a[1] as usize)
-
-    seeds
-        .map(|seed| {
-            almanac
-                .iter()
-                .scan(seed, |val, map| {
-                    let v = *val;
-                    let idx = map.binary_search_by(|e| {
-                        if e.start > v {
-                            Ordering::Greater
-                        } else if e.start <= v && v <= e.end {
-                            Ordering::Equal
-                        } else {
-                            Ordering::Less
-                        }
-                    });
+/// It is the same algorithm as part 1, but processing the seeds in parallel with Rayon.
+fn process_lowest_location_pt2_mt(ranges: &[Interval], seed_to_location: &RangeMap) -> Option<i64> {
+    let seeds = ranges.par_iter().flat_map(|range| range.start..range.end);
 
-                    *val = if let Ok(idx) = idx {
-                        let diff = v - map[idx].start;
-                        map[idx].destination_start + diff
-                    } else {
-                        v
-                    };
+    seeds.map(|seed| seed_to_location.lookup(seed)).min()
+}
 
-                    Some(*val)
-                })
-                .last()
-                .unwrap()
-        })
+/// Returns the lowest location of the seed ranges of part 2, given the composed map of the almanac, or `None` if the
+/// ranges hold no seed.
+///
+/// The map splits the seed ranges at the boundaries of its entries, so whole ranges are mapped at once.
+fn process_lowest_location_pt2_intervals(ranges: &[Interval], seed_to_location: &RangeMap) -> Option<i64> {
+    ranges
+        .iter()
+        .flat_map(|&range| seed_to_location.lookup_range(range))
+        .map(|range| range.start)
         .min()
-        .unwrap()
+}
+
+/// Returns the lowest location of the seed ranges of part 2, mapping back through the `Almanac`, or `None` if the
+/// ranges hold no seed.
+///
+/// Tries every location from 0 upwards, mapping it back through the maps, until one comes from a seed. The almanac
+/// holds no negative number, so every seed has a location on the way.
+fn process_lowest_location_pt2_reverse(ranges: &[Interval], almanac: &Almanac) -> Option<i64> {
+    let is_seed = |value: i64| ranges.iter().any(|range| range.contains(value));
+
+    (0..=i64::MAX).find(|&location| {
+        // a value can have several preimages, all of them are followed
+        let mut values = vec![location];
+        for map in almanac.iter().rev() {
+            values = values.into_iter().flat_map(|value| map.preimages(value)).collect();
+        }
+        values.into_iter().any(is_seed)
+    })
 }
 
 //----------
-// Structs
+// Validation
 //----------
 
-#[derive(Clone, Copy, PartialEq, Eq)]
-struct Entry {
-    start: u64,
-    end: u64,
-    destination_start: u64,
+/// Problem found in a map of the almanac by [`validate_almanac`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AlmanacIssue {
+    /// Name of the map, like "seed-to-soil".
+    pub map: String,
+    /// Line of the entry where the problem is found.
+    pub line: usize,
+    pub kind: IssueKind,
+}
+
+/// Kinds of problems of a map of the almanac.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IssueKind {
+    /// The source range overlaps the one of the entry at `line` on the `common` values.
+    Overlap { line: usize, common: Interval },
+    /// The values between the source range and the previous one are not covered by any entry.
+    Gap(Interval),
 }
 
-impl Entry {
-    fn cmp_to(&self, val: u64) -> Ordering {
-        if self.start > val {
-            Ordering::Greater
-        } else if self.start <= val && val <= self.end {
-            Ordering::Equal
-        } else {
-            Ordering::Less
+/// Map of the almanac as written in the input.
+#[derive(Clone, Debug)]
+struct MapBlock {
+    name: String,
+    entries: Vec<BlockEntry>,
+}
+
+/// Entry of a map of the almanac, and the line where it is written.
+#[derive(Clone, Copy, Debug)]
+struct BlockEntry {
+    line: usize,
+    source: Interval,
+    destination_start: i64,
+}
+
+impl MapBlock {
+    /// Returns the overlaps and gaps between the source ranges of the map, sorted by the start of the ranges.
+    fn issues(&self) -> Vec<AlmanacIssue> {
+        let mut entries: Vec<_> = self.entries.iter().filter(|e| !e.source.is_empty()).collect();
+        entries.sort_by_key(|e| (e.source.start, e.line));
+
+        let mut issues = Vec::new();
+        // entry reaching the furthest so far, as a range may overlap several of the following ones
+        let mut furthest: Option<&BlockEntry> = None;
+        for entry in entries {
+            if let Some(prev) = furthest {
+                let kind = match prev.source.intersection(&entry.source) {
+                    Some(common) => Some(IssueKind::Overlap {
+                        line: prev.line,
+                        common,
+                    }),
+                    None if prev.source.end < entry.source.start => {
+                        Some(IssueKind::Gap(Interval::new(prev.source.end, entry.source.start)))
+                    }
+                    None => None,
+                };
+                if let Some(kind) = kind {
+                    issues.push(AlmanacIssue {
+                        map: self.name.clone(),
+                        line: entry.line,
+                        kind,
+                    });
+                }
+            }
+            if furthest.is_none_or(|prev| prev.source.end < entry.source.end) {
+                furthest = Some(entry);
+            }
         }
+        issues
     }
 }
 
-impl Ord for Entry {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        self.start.cmp(&other.start)
+impl IssueKind {
+    fn is_overlap(&self) -> bool {
+        matches!(self, IssueKind::Overlap { .. })
     }
 }
 
-impl PartialOrd for Entry {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some(self.cmp(other))
+impl fmt::Display for AlmanacIssue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} map, line {}: ", self.map, self.line)?;
+        match self.kind {
+            IssueKind::Overlap { line, common } => {
+                write!(f, "source range overlaps the one of line {line} on {common}")
+            }
+            IssueKind::Gap(gap) => write!(f, "no entry maps {gap}, before this one"),
+        }
     }
 }
 
-impl FromStr for Entry {
-    type Err = Box<dyn error::Error>;
+/// Formats the map like the input, its header followed by one line per entry.
+impl fmt::Display for MapBlock {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} map:", self.name)?;
+        for entry in &self.entries {
+            let source = entry.source;
+            write!(f, "\n{} {} {}", entry.destination_start, source.start, source.len())?;
+        }
+        Ok(())
+    }
+}
 
+//----------
+// Strategies
+//----------
+
+/// Algorithms solving part 2.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Strategy {
+    /// Maps whole seed ranges, see `process_lowest_location_pt2_intervals`.
+    Intervals,
+    /// Maps every seed, in parallel, see `process_lowest_location_pt2_mt`.
+    BruteForce,
+    /// Maps locations back to seeds, see `process_lowest_location_pt2_reverse`.
+    Reverse,
+}
+
+impl Strategy {
+    /// Returns the lowest location of the seeds ranges, given the almanac and its composed map.
+    ///
+    /// Fails if the seeds are not pairs of range start and length, the end of a range overflows, or the ranges hold
+    /// no seed.
+    fn lowest_location_pt2(
+        self,
+        seeds: &Seeds,
+        almanac: &Almanac,
+        seed_to_location: &RangeMap,
+    ) -> Result<i64, AocError> {
+        if !seeds.len().is_multiple_of(2) {
+            return Err(AocError::invalid("the seeds are not pairs of range start and length"));
+        }
+        let ranges = seeds
+            .chunks(2)
+            .map(|a| Interval::checked_with_len(a[0], a[1]))
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(AocError::overflow)?;
+        // the reverse search would never end
+        if ranges.iter().all(Interval::is_empty) {
+            return Err(AocError::invalid("the seed ranges hold no seed"));
+        }
+
+        let location = match self {
+            Strategy::Intervals => process_lowest_location_pt2_intervals(&ranges, seed_to_location),
+            Strategy::BruteForce => process_lowest_location_pt2_mt(&ranges, seed_to_location),
+            Strategy::Reverse => process_lowest_location_pt2_reverse(&ranges, almanac),
+        };
+        location.ok_or_else(|| AocError::invalid("the seed ranges hold no seed"))
+    }
+}
+
+impl FromStr for Strategy {
+    type Err = AocError;
+
+    /// Parses the name of a strategy, `intervals`, `brute-force` or `reverse`.
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut iter = s.split_ascii_whitespace().flat_map(str::parse);
-        let destination_start = iter.next().ok_or("Not able to parse the destination start.")?;
-        let start = iter.next().ok_or("Not able to parse the start of the range.")?;
-        let end = start + iter.next().ok_or("Not able to parse the end of the range.")? - 1;
-
-        Ok(Entry {
-            start,
-            end,
-            destination_start,
-        })
+        match s {
+            "intervals" => Ok(Strategy::Intervals),
+            "brute-force" => Ok(Strategy::BruteForce),
+            "reverse" => Ok(Strategy::Reverse),
+            _ => Err(AocError::invalid(format!(
+                "unknown strategy `{s}`, expected `intervals`, `brute-force` or `reverse`"
+            ))),
+        }
     }
 }
 
@@ -203,10 +437,12 @@ impl FromStr for Entry {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{aoc_test, testing::corrupt};
+    // the `Strategy` of the day, not the one of `proptest`, whose trait is only needed for its methods
+    use super::Strategy;
+    use proptest::{prelude::*, sample::Index, strategy::Strategy as _};
 
-    #[test]
-    fn tests() {
-        let input = "seeds: 79 14 55 13
+    const SAMPLE: &str = "seeds: 79 14 55 13
 
 seed-to-soil map:
 50 98 2
@@ -240,14 +476,186 @@ humidity-to-location map:
 60 56 37
 56 93 4";
 
-        let (seeds, almanac) = parse_input(input);
+    aoc_test! {
+        sample: SAMPLE,
+        pt1_sample: solve_part1 => 35,
+        pt2_sample: solve_part2 => 46,
+    }
 
-        // part 1
-        let location = process_lowest_location(&seeds, &almanac);
-        assert_eq!(location, 35);
+    #[test]
+    fn test_strategies() {
+        // seed ranges crossing the boundaries of the entries, or covered by none
+        let inputs = [
+            SAMPLE,
+            "seeds: 0 120 200 3\n\na-to-b map:\n50 98 2\n52 50 48\n\nb-to-c map:\n0 15 37\n100 0 15",
+        ];
+        for input in inputs {
+            let (seeds, almanac) = parse_input(input).unwrap();
+            let seed_to_location = compose_almanac(&almanac);
+            let lowest = |strategy: Strategy| strategy.lowest_location_pt2(&seeds, &almanac, &seed_to_location);
+            let intervals = lowest(Strategy::Intervals).unwrap();
+            assert_eq!(lowest(Strategy::BruteForce).unwrap(), intervals);
+            assert_eq!(lowest(Strategy::Reverse).unwrap(), intervals);
+        }
+        assert_eq!(
+            run_with(SAMPLE, "reverse".parse().unwrap()).unwrap(),
+            run(SAMPLE).unwrap()
+        );
+        assert_eq!(
+            "linear".parse::<Strategy>().unwrap_err().to_string(),
+            "Invalid input: unknown strategy `linear`, expected `intervals`, `brute-force` or `reverse`"
+        );
+
+        // part 2 needs pairs of numbers, describing at least a seed
+        let errors = [
+            ("seeds: 1 2 3", "the seeds are not pairs of range start and length"),
+            ("seeds: 5 0", "the seed ranges hold no seed"),
+        ];
+        for (seeds, message) in errors {
+            let err = solve_part2(&format!("{seeds}\n\na-to-b map:\n0 5 1")).unwrap_err();
+            assert_eq!(err.to_string(), format!("Invalid input: {message}"));
+        }
 
-        // part 2
-        let location2mt = process_lowest_location_pt2_mt(&seeds, &almanac);
-        assert_eq!(location2mt, 46);
+        // part 1 needs a seed too
+        for input in ["seeds:\n\na-to-b map:\n0 5 1", "seeds:"] {
+            assert_eq!(
+                solve_part1(input).unwrap_err().to_string(),
+                "Invalid input: there are no seeds"
+            );
+            assert!(run(input).is_err());
+        }
+    }
+
+    #[test]
+    fn test_compose_almanac() {
+        let (_, almanac) = parse_input(SAMPLE).unwrap();
+        let seed_to_location = compose_almanac(&almanac);
+        for seed in -5..120 {
+            let location = almanac.iter().fold(seed, |val, map| map.lookup(val));
+            assert_eq!(seed_to_location.lookup(seed), location, "seed {seed}");
+        }
+        assert_eq!(seed_to_location.lookup(79), 82);
+    }
+
+    #[test]
+    fn test_validate_almanac() {
+        assert!(validate_almanac(SAMPLE).unwrap().is_empty());
+
+        // unsorted entries are fine, the second range overlaps the first and third ones, leaving a gap before the last
+        let input = "seeds: 1 2\n\na-to-b map:\n0 30 10\n0 0 10\n0 5 30\n0 50 5";
+        let issues = validate_almanac(input).unwrap();
+        let lines: Vec<_> = issues.iter().map(|issue| issue.to_string()).collect();
+        assert_eq!(
+            lines,
+            [
+                "a-to-b map, line 6: source range overlaps the one of line 5 on [5, 10)",
+                "a-to-b map, line 4: source range overlaps the one of line 6 on [30, 35)",
+                "a-to-b map, line 7: no entry maps [40, 50), before this one",
+            ]
+        );
+
+        let err = parse_input(input).err().unwrap();
+        assert_eq!(err.to_string(), format!("Invalid input: {}", lines[0]));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let err = parse_input("seeds: 79 14\n\nseed-to-soil map:\n50 98 two\n52 50 48")
+            .err()
+            .unwrap();
+        assert_eq!(
+            err.to_string(),
+            "Day 05, line 4, column 7: Unexpected token `two`, expected a number."
+        );
+
+        let err = parse_input("seeds: 79 14\n\nseed-to-soil:\n50 98 2").err().unwrap();
+        assert_eq!(
+            err.to_string(),
+            "Day 05, line 3, column 13: Unexpected token `:`, expected `map`."
+        );
+
+        // ranges ending beyond the largest number, and negative numbers
+        let map = "\n\nseed-to-soil map:\n";
+        let errors = [
+            (
+                format!("seeds: 9223372036854775807 5{map}0 0 1"),
+                "Day 05, line 1, column 28: The seed range ends beyond the largest number.",
+            ),
+            (
+                format!("seeds: 1 2{map}9223372036854775807 0 10"),
+                "Day 05, line 4, column 23: The range ends beyond the largest number.",
+            ),
+            (
+                format!("seeds: 1 2{map}1 9223372036854775800 100"),
+                "Day 05, line 4, column 23: The range ends beyond the largest number.",
+            ),
+            (
+                format!("seeds: 1 2{map}1 -5 100"),
+                "Day 05, line 4, column 3: Negative number `-5`.",
+            ),
+        ];
+        for (input, message) in errors {
+            assert_eq!(parse_input(&input).err().unwrap().to_string(), message, "{input}");
+        }
+
+        // the strategies check the ranges they are given too
+        let ranges = vec![i64::MAX, 5];
+        let err = Strategy::BruteForce
+            .lowest_location_pt2(&ranges, &Vec::new(), &RangeMap::new())
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid input: the numbers are too large, the answer overflows"
+        );
+    }
+
+    /// Maps with up to 3 entries, of numbers below 100, between the categories of the puzzle.
+    fn map_block() -> impl proptest::strategy::Strategy<Value = MapBlock> {
+        let category = || prop::sample::select(&["seed", "soil", "water", "light", "location"][..]);
+        let entry = (0..100i64, 0..100i64, 0..100i64);
+        (category(), category(), prop::collection::vec(entry, 0..4)).prop_map(|(from, to, entries)| MapBlock {
+            name: format!("{from}-to-{to}"),
+            entries: entries
+                .into_iter()
+                .enumerate()
+                .map(|(line, (destination_start, start, len))| BlockEntry {
+                    line,
+                    source: Interval::with_len(start, len),
+                    destination_start,
+                })
+                .collect(),
+        })
+    }
+
+    /// The maps without the lines of their entries, which depend on the layout.
+    fn contents(blocks: &[MapBlock]) -> Vec<(String, Vec<(Interval, i64)>)> {
+        let entries = |b: &MapBlock| b.entries.iter().map(|e| (e.source, e.destination_start)).collect();
+        blocks.iter().map(|b| (b.name.clone(), entries(b))).collect()
+    }
+
+    proptest! {
+        #[test]
+        fn test_almanac_round_trip(
+            seeds in prop::collection::vec(0..100i64, 0..5),
+            blocks in prop::collection::vec(map_block(), 0..4),
+            index in any::<Index>(),
+            c in prop::sample::select(&['#', '!', '?'][..]),
+        ) {
+            let mut text = String::from("seeds:");
+            seeds.iter().for_each(|seed| text.push_str(&format!(" {seed}")));
+            blocks.iter().for_each(|block| text.push_str(&format!("\n\n{block}")));
+            let (parsed_seeds, parsed_blocks) = parse_blocks(&text).unwrap();
+            prop_assert_eq!(parsed_seeds, seeds, "{}", text);
+            prop_assert_eq!(contents(&parsed_blocks), contents(&blocks), "{}", text);
+
+            let corrupted = corrupt(&text, index, c);
+            prop_assert!(parse_blocks(&corrupted).is_err(), "{}", corrupted);
+        }
+
+        // arbitrary almanacs fail without panicking
+        #[test]
+        fn test_almanac_garbage(almanac in "[sed:\\- \n19]{0,30}") {
+            let _ = parse_almanac(&almanac);
+        }
     }
 }
@@ -16,36 +16,85 @@
 //!
 //! # Solution
 //!
-//! Solves a closed form equation using the quadratic formula. The count of ways to beat the record corresponds to the
-//! number of integer values within the range given by the solutions of the equation.
+//! Binary search: the distance is symmetric around half the time limit, so the success cases are in the middle of the
+//! range, and the first of them is found with a binary search over the first half. Only integers are used, so the
+//! result is exact for any input.
 //!
-//! The time complexity of the closed form solution is theoretically O(n), but it uses a square root function that may
-//! or may not have have a grater complexity depending on how it is implemented in hardware.
+//! A closed form equation, using the quadratic formula, is also implemented. The count of ways to beat the record
+//! corresponds to the number of integer values within the range given by the solutions of the equation. It relies on a
+//! floating point square root, which loses precision for large inputs. It is kept for comparison, selected with
+//! `run_with(input, Method::ClosedForm)`, and benchmarked against the binary search with
+//! `aoc2023 6 --bench --strategy closed-form`.
 //!
 //! # Possible alternative solutions
 //!
 //! - Brute Force: the problem is symmetric, so the range can be found by counting from 0 to the first success case;
 //!
-//! - Binary search: the success cases are in the middle of the range.
-//!
 //! - Newton's method: can be employed to use only integer values.
-use std::fs;
+use crate::{answer::Answer, error::AocError, iter_ext::IterExt, parse, search};
+use std::str::FromStr;
+
+pub fn run(input: &str) -> Result<[Answer; 2], AocError> {
+    run_with(input, Method::BinarySearch)
+}
+
+/// Solves both parts, counting the ways to beat the records with the given method.
+///
+/// # Errors
+///
+/// Fails if the input is malformed or has no race, or the product of part 1 overflows.
+pub fn run_with(input: &str, method: Method) -> Result<[Answer; 2], AocError> {
+    Ok([
+        ways_product(input, method)?.into(),
+        ways_single_race(input, method)?.into(),
+    ])
+}
+
+/// Returns the product of the number of ways to beat the record of each race.
+///
+/// # Errors
+///
+/// Fails if the input is malformed, or the product overflows.
+pub fn solve_part1(input: &str) -> Result<u64, AocError> {
+    ways_product(input, Method::BinarySearch)
+}
 
-pub fn run() {
-    let input = fs::read_to_string("inputs/day06").unwrap();
-    let races = parse_input(&input);
+/// Returns the number of ways to beat the record of the single race given by the input without its spaces.
+///
+/// # Errors
+///
+/// Fails if the input is malformed or has no race.
+pub fn solve_part2(input: &str) -> Result<u64, AocError> {
+    ways_single_race(input, Method::BinarySearch)
+}
 
-    // part 1
-    let beat: u64 = races.iter().map(|&r| count_record_beating_ways(r)).product();
-    println!("Part 1: Product of the number of ways to beat the record: {}", beat);
+/// Returns the product of the number of ways to beat the record of each race, counted with `method`.
+fn ways_product(input: &str, method: Method) -> Result<u64, AocError> {
+    parse_input(input)?
+        .into_iter()
+        .map(|race| method.count_ways(race))
+        .checked_product()
+        .ok_or_else(AocError::overflow)
+}
 
-    // part 2, remove whitespace from input
-    let mut input_pt2 = input.clone();
+/// Returns the number of ways to beat the record of the race given by the input without its spaces, counted with
+/// `method`.
+fn ways_single_race(input: &str, method: Method) -> Result<u64, AocError> {
+    // remove whitespace from input
+    let mut input_pt2 = input.to_owned();
     input_pt2.retain(|c: char| c != ' ');
-    let race = parse_input(&input_pt2);
-    let beat_pt2 = count_record_beating_ways(race[0]);
+    let races = parse_input(&input_pt2)?;
+    let race = races.first().ok_or_else(|| AocError::invalid("there is no race"))?;
+    Ok(method.count_ways(*race))
+}
 
-    println!("Part 2: Number of ways to beat the record: {}", beat_pt2);
+/// Returns the statistics of every race of the input, in order, read as in part 1.
+///
+/// # Errors
+///
+/// Fails if the input is malformed, or the farthest distance of a race overflows.
+pub fn race_stats(input: &str) -> Result<Vec<RaceStats>, AocError> {
+    parse_input(input)?.into_iter().map(Race::stats).collect()
 }
 
 /// Parses an input string into a `Vec` of `Race`s.
@@ -56,25 +105,37 @@ pub fn run() {
 ///
 /// "Time:      7  15   30"
 /// "Distance:  9  40  200"
-fn parse_input(s: &str) -> Vec<Race> {
+///
+/// Fails if a line is missing or has the wrong prefix, a value is not a number that fits in a `u64`, or the lines have
+/// different numbers of values.
+fn parse_input(s: &str) -> Result<Vec<Race>, AocError> {
     let mut lines = s.lines();
 
-    let mut get_next_line = |prefix: &str| {
-        lines
-            .next()
-            .unwrap()
-            .strip_prefix(prefix)
-            .unwrap()
-            .split_ascii_whitespace()
-            .flat_map(str::parse)
+    let mut get_next_line = |n: usize, prefix: &str| -> Result<Vec<u64>, AocError> {
+        let line = lines.next().unwrap_or_default();
+        let values = line.strip_prefix(prefix).ok_or_else(|| {
+            AocError::parse(format!("Expected the prefix `{prefix}`."))
+                .at_line(n)
+                .in_day(6)
+        })?;
+        parse::numbers(values).map_err(|e| e.within(line, values).at_line(n).in_day(6))
     };
 
-    let time = get_next_line("Time:");
-    let distance = get_next_line("Distance:");
+    let time = get_next_line(1, "Time:")?;
+    let distance = get_next_line(2, "Distance:")?;
+    if time.len() != distance.len() {
+        return Err(AocError::invalid(format!(
+            "there are {} times but {} distances",
+            time.len(),
+            distance.len()
+        )));
+    }
 
-    time.zip(distance)
+    Ok(time
+        .into_iter()
+        .zip(distance)
         .map(|(time, distance)| Race { time, distance })
-        .collect()
+        .collect())
 }
 
 /// Returns the count of ways to beat the race record
@@ -120,26 +181,121 @@ fn parse_input(s: &str) -> Vec<Race> {
 /// the limits by simple rounding. To address this without resorting to conditionals, a workaround involves adding or
 /// subtracting 1 and then using a rounding method inverse to that originally needed (for example, ceil instead of
 /// floor).
+///
+/// The discriminant is computed with `u128`s, so it does not overflow, and a negative one means that the record cannot
+/// be beaten. When it is zero, the only time reaching the record does not beat it, and the range is empty.
 fn count_record_beating_ways(r: Race) -> u64 {
-    let delta_sqrt = ((r.time * r.time - 4 * r.distance) as f64).sqrt();
+    let Some(delta) = (r.time as u128 * r.time as u128).checked_sub(4 * r.distance as u128) else {
+        return 0;
+    };
+    let delta_sqrt = (delta as f64).sqrt();
 
     let t1 = ((r.time as f64 + delta_sqrt) / 2.0 - 1.0).ceil() as u64; // always "rounds" down, even if delta is exact
     let t2 = ((r.time as f64 - delta_sqrt) / 2.0 + 1.0).floor() as u64; // always "rounds" up, even if delta is exact
 
     //+1 because range inclusive
-    t1 - t2 + 1
+    (t1 + 1).saturating_sub(t2)
+}
+
+/// Returns the count of ways to beat the race record, using a binary search.
+///
+/// Holding the button for `t` reaches the distance `t * (T - t)`, which grows until `T / 2` and is symmetric around it.
+/// The first winning time `t1` is found by binary searching the first half, and the winning times are then the range
+/// from `t1` to `T - t1`.
+fn count_record_beating_alternative(r: Race) -> u64 {
+    let half = r.time / 2;
+    // a distance too large for a `u64` beats any record
+    let t1 = search::lower_bound(0..half + 1, |t| r.distance_held(t).is_none_or(|d| d > r.distance));
+
+    if t1 > half {
+        0
+    } else {
+        r.time - 2 * t1 + 1
+    }
 }
 
 //----------
 // Structs
 //----------
 
+/// Method counting the ways to beat a record.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Method {
+    /// Binary search of the first winning time, exact, see `count_record_beating_alternative`.
+    BinarySearch,
+    /// Quadratic formula with a floating point square root, see `count_record_beating_ways`.
+    ClosedForm,
+}
+
 #[derive(Clone, Copy)]
 struct Race {
     time: u64,
     distance: u64,
 }
 
+/// Statistics of a race, returned by [`race_stats`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RaceStats {
+    /// Time limit of the race.
+    pub time: u64,
+    /// Distance of the current record.
+    pub record: u64,
+    /// Time holding the button that goes the farthest. When two times do, the shorter one.
+    pub optimal_hold: u64,
+    /// Distance reached holding the button for `optimal_hold`.
+    pub max_distance: u64,
+    /// Distance by which `max_distance` beats the record, 0 if the record cannot be beaten.
+    pub margin: u64,
+    /// Number of ways to beat the record.
+    pub ways: u64,
+}
+
+impl Method {
+    /// Returns the count of ways to beat the record of `race`.
+    fn count_ways(self, race: Race) -> u64 {
+        match self {
+            Method::BinarySearch => count_record_beating_alternative(race),
+            Method::ClosedForm => count_record_beating_ways(race),
+        }
+    }
+}
+
+impl FromStr for Method {
+    type Err = AocError;
+
+    /// Parses the name of a method, `binary-search` or `closed-form`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "binary-search" => Ok(Method::BinarySearch),
+            "closed-form" => Ok(Method::ClosedForm),
+            _ => Err(AocError::invalid(format!(
+                "unknown method `{s}`, expected `binary-search` or `closed-form`"
+            ))),
+        }
+    }
+}
+
+impl Race {
+    /// Returns the distance reached holding the button for `hold`, or `None` if it overflows.
+    fn distance_held(self, hold: u64) -> Option<u64> {
+        hold.checked_mul(self.time - hold)
+    }
+
+    fn stats(self) -> Result<RaceStats, AocError> {
+        // the distance is a parabola peaking at half the time limit
+        let optimal_hold = self.time / 2;
+        let max_distance = self.distance_held(optimal_hold).ok_or_else(AocError::overflow)?;
+        Ok(RaceStats {
+            time: self.time,
+            record: self.distance,
+            optimal_hold,
+            max_distance,
+            margin: max_distance.saturating_sub(self.distance),
+            ways: count_record_beating_alternative(self),
+        })
+    }
+}
+
 //----------
 // Test
 //----------
@@ -147,13 +303,20 @@ struct Race {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::aoc_test;
 
-    #[test]
-    fn tests() {
-        let input = "Time:      7  15   30
+    const SAMPLE: &str = "Time:      7  15   30
 Distance:  9  40  200";
 
-        let races = parse_input(input);
+    aoc_test! {
+        sample: SAMPLE,
+        pt1_sample: solve_part1 => 288,
+        pt2_sample: solve_part2 => 71503,
+    }
+
+    #[test]
+    fn test_closed_form() {
+        let races = parse_input(SAMPLE).unwrap();
         assert_eq!(count_record_beating_ways(races[0]), 4);
         assert_eq!(count_record_beating_ways(races[1]), 8);
         assert_eq!(count_record_beating_ways(races[2]), 9);
@@ -161,5 +324,78 @@ Distance:  9  40  200";
         assert_eq!(count_record_beating_alternative(races[0]), 4);
         assert_eq!(count_record_beating_alternative(races[1]), 8);
         assert_eq!(count_record_beating_alternative(races[2]), 9);
+
+        // records that can not be beaten, or only reached
+        for (time, distance) in [(4, 4), (4, 5), (0, 0), (1, 0)] {
+            let race = Race { time, distance };
+            assert_eq!(count_record_beating_ways(race), count_record_beating_alternative(race));
+        }
+        assert_eq!(
+            run_with(SAMPLE, "closed-form".parse().unwrap()).unwrap(),
+            run(SAMPLE).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let err = solve_part1("Time: 7\nDist: 9").unwrap_err();
+        assert_eq!(err.to_string(), "Day 06, line 2: Expected the prefix `Distance:`.");
+
+        let err = solve_part1("Time: 7 15\nDistance: 9").unwrap_err();
+        assert_eq!(err.to_string(), "Invalid input: there are 2 times but 1 distances");
+
+        let err = solve_part2("Time:\nDistance:").unwrap_err();
+        assert_eq!(err.to_string(), "Invalid input: there is no race");
+
+        let err = solve_part1("Time: 7 1x5\nDistance: 9 40").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Day 06, line 1, column 9: invalid digit found in string"
+        );
+
+        // the number of part 2, joining the values, does not fit in a `u64`
+        let input = "Time: 1000000000 1000000000 10\nDistance: 9 40 200";
+        assert!(solve_part1(input).is_ok());
+        let err = solve_part2(input).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Day 06, line 1, column 6: number too large to fit in target type"
+        );
+    }
+
+    #[test]
+    fn test_race_stats() {
+        let stats = race_stats(SAMPLE).unwrap();
+        assert_eq!(
+            stats[0],
+            RaceStats {
+                time: 7,
+                record: 9,
+                optimal_hold: 3,
+                max_distance: 12,
+                margin: 3,
+                ways: 4,
+            }
+        );
+        assert_eq!(
+            stats
+                .iter()
+                .map(|s| (s.optimal_hold, s.max_distance, s.ways))
+                .collect::<Vec<_>>(),
+            [(3, 12, 4), (7, 56, 8), (15, 225, 9)]
+        );
+
+        // a record that cannot be beaten
+        let stats = race_stats("Time: 4\nDistance: 4").unwrap();
+        assert_eq!((stats[0].max_distance, stats[0].margin, stats[0].ways), (4, 0, 0));
+    }
+
+    #[test]
+    fn test_large_races() {
+        // the farthest distances do not fit in a `u64`, but still beat the records
+        let input = "Time: 10000000000 18446744073709551615\nDistance: 1 2";
+        assert_eq!(solve_part1("Time: 10000000000\nDistance: 1").unwrap(), 9999999999);
+        assert!(solve_part1(input).is_err());
+        assert!(race_stats(input).is_err());
     }
 }
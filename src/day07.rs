@@ -1,54 +1,180 @@
-use std::{cmp::Ordering, error, fs, str::FromStr};
+use crate::{answer::Answer, counter::Counter, error::AocError, iter_ext::IterExt, parse};
+use std::{cmp::Ordering, fmt, str::FromStr};
 
 use HandType::*;
 
-pub fn run() {
-    let input = fs::read_to_string("inputs/day07").unwrap();
-    let hands = parse_input(&input);
+pub fn run(input: &str) -> Result<[Answer; 2], AocError> {
+    Ok([solve_part1(input)?.into(), solve_part2(input)?.into()])
+}
+
+/// Returns the total winnings of the hands.
+///
+/// # Errors
+///
+/// Fails if the input is malformed, or the total overflows.
+pub fn solve_part1(input: &str) -> Result<u64, AocError> {
+    solve_with(input, &Standard)
+}
 
-    // Part 1
-    let total_pt1 = total_winnings(&hands);
-    println!("Part 1: Total winnings: {total_pt1}");
+/// Returns the total winnings of the hands, with `J` cards as jokers.
+///
+/// # Errors
+///
+/// Fails if the input is malformed, or the total overflows.
+pub fn solve_part2(input: &str) -> Result<u64, AocError> {
+    solve_with(input, &Wildcard(Card::J))
+}
 
-    // Part 2
-    let joker_hands = into_joker_hands(hands);
-    let total_pt2 = total_winnings(&joker_hands);
-    println!("Part 2: Total winnings: {total_pt2}");
+/// Returns the total winnings of the hands, ranked under `rules`.
+///
+/// # Errors
+///
+/// Fails if the input is malformed, or the total overflows.
+pub fn solve_with(input: &str, rules: &impl Ruleset) -> Result<u64, AocError> {
+    total_winnings(&rank_hands(&parse_input(input)?, rules)).ok_or_else(AocError::overflow)
+}
+
+/// Returns every hand, from the weakest to the strongest under `rules`, with its type, rank and winnings.
+///
+/// # Errors
+///
+/// Fails if the input is malformed, or the winnings of a hand overflow.
+pub fn rankings(input: &str, rules: &impl Ruleset) -> Result<Vec<RankedHand>, AocError> {
+    let hands = parse_input(input)?;
+    rank_hands(&hands, rules)
+        .into_iter()
+        .enumerate()
+        .map(|(i, hand)| {
+            Ok(RankedHand {
+                cards: hand.cards.iter().map(|card| card.to_string()).collect(),
+                hand_type: rules.hand_type(&hand.cards),
+                rank: i + 1,
+                bid: hand.bid,
+                winnings: winnings(i + 1, hand.bid).ok_or_else(AocError::overflow)?,
+            })
+        })
+        .collect()
 }
 
 //----------
 // Helper Methods
 //----------
 
-fn parse_input(input: &str) -> Vec<Hand> {
-    let mut hands = input.lines().flat_map(str::parse).collect::<Vec<Hand>>();
-    hands.sort_unstable();
-    hands
+fn parse_input(input: &str) -> Result<Vec<Hand>, AocError> {
+    parse::lines_as(input).map_err(|e| e.in_day(7))
+}
+
+/// Returns the hands sorted from the weakest to the strongest under `rules`.
+fn rank_hands<'h>(hands: &'h [Hand], rules: &impl Ruleset) -> Vec<&'h Hand> {
+    let mut ranked: Vec<_> = hands.iter().map(|hand| (rules.hand_type(&hand.cards), hand)).collect();
+    ranked.sort_by(|(type_a, a), (type_b, b)| {
+        type_a.cmp(type_b).then_with(|| {
+            let pairs = a.cards.iter().zip(&b.cards);
+            pairs
+                .map(|(&x, &y)| rules.compare_cards(x, y))
+                .find(|o| o.is_ne())
+                .unwrap_or(Ordering::Equal)
+        })
+    });
+    ranked.into_iter().map(|(_, hand)| hand).collect()
+}
+
+/// Returns the winnings of the hands, sorted from the weakest to the strongest, or `None` if they overflow.
+fn total_winnings(ranked: &[&Hand]) -> Option<u64> {
+    let winnings: Option<Vec<u64>> = ranked.iter().enumerate().map(|(i, h)| winnings(i + 1, h.bid)).collect();
+    winnings?.into_iter().checked_sum()
+}
+
+/// Returns the winnings of a hand, its rank times its bid, or `None` if they overflow.
+fn winnings(rank: usize, bid: u64) -> Option<u64> {
+    bid.checked_mul(rank as u64)
+}
+
+//----------
+// Rulesets
+//----------
+
+/// Rules of a variant of Camel Cards: how cards compare and which of them are wild.
+pub trait Ruleset {
+    /// Compares two cards by their strength, used to break ties between hands of the same type.
+    fn compare_cards(&self, a: Card, b: Card) -> Ordering;
+
+    /// Checks whether `card` is wild, counted as whichever card makes the strongest hand type.
+    fn is_wild(&self, _card: Card) -> bool {
+        false
+    }
+
+    /// Returns the type of a hand. Wildcards are counted as the most repeated card, which always gives the strongest
+    /// type.
+    fn hand_type(&self, cards: &[Card]) -> HandType {
+        let counter: Counter<Card> = cards.iter().copied().filter(|&card| !self.is_wild(card)).collect();
+        let wild = cards.len() - counter.total();
+
+        let mut profile = counter.profile();
+        match profile.first_mut() {
+            Some(largest) => *largest += wild,
+            None => profile.push(wild),
+        }
+
+        HandType::from_profile(&profile)
+    }
 }
 
-fn total_winnings(hands: &[Hand]) -> u64 {
-    hands.iter().enumerate().map(|(i, h)| (i as u64 + 1) * h.bid).sum()
+/// Rules of part 1: cards are ranked from `A` down to `2`, and none is wild.
+#[derive(Clone, Copy, Debug)]
+pub struct Standard;
+
+/// Rules of part 2, with `J` as the wildcard: the wildcard is the weakest card, and the other cards are ranked as in
+/// `Standard`.
+#[derive(Clone, Copy, Debug)]
+pub struct Wildcard(pub Card);
+
+impl Ruleset for Standard {
+    fn compare_cards(&self, a: Card, b: Card) -> Ordering {
+        a.cmp(&b)
+    }
 }
 
-fn into_joker_hands(mut hands: Vec<Hand>) -> Vec<Hand> {
-    hands.iter_mut().for_each(|card| card.change_to_joker_hand());
-    hands.sort_unstable();
-    hands
+impl Ruleset for Wildcard {
+    fn compare_cards(&self, a: Card, b: Card) -> Ordering {
+        match (self.is_wild(a), self.is_wild(b)) {
+            (true, true) => Ordering::Equal,
+            (true, false) => Ordering::Less,
+            (false, true) => Ordering::Greater,
+            (false, false) => a.cmp(&b),
+        }
+    }
+
+    fn is_wild(&self, card: Card) -> bool {
+        card == self.0
+    }
 }
 
 //----------
 // Structs and Enums
 //----------
 
-#[derive(PartialEq, Eq)]
+#[derive(Debug, PartialEq, Eq)]
 struct Hand {
     cards: [Card; 5],
-    h_type: HandType,
     bid: u64,
 }
 
-#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
-enum HandType {
+/// Hand of a ranking, returned by [`rankings`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RankedHand {
+    pub cards: String,
+    pub hand_type: HandType,
+    /// Position of the hand from the weakest, starting at 1.
+    pub rank: usize,
+    pub bid: u64,
+    /// Contribution of the hand to the total winnings, its rank times its bid.
+    pub winnings: u64,
+}
+
+/// Types of hands, from the weakest to the strongest.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum HandType {
     HighCard,
     OnePair,
     TwoPair,
@@ -58,104 +184,48 @@ enum HandType {
     FiveOfKind,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
-#[repr(u8)]
-enum Card {
-    A,
-    K,
-    Q,
-    J,
-    T,
-    N(u8),
-    Joker,
-}
+/// Card, stored as its strength, so cards compare by it: its number for the numbered cards from 2 to 9, and 10 to 14
+/// for `T` to `A`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Card(u8);
 
 //----------
 // Implementations
 //----------
 
 impl Card {
-    fn discriminant(&self) -> u8 {
-        // SAFETY: https://doc.rust-lang.org/std/mem/fn.discriminant.html
-        unsafe { *<*const _>::from(self).cast::<u8>() }
+    pub const A: Card = Card(14);
+    pub const K: Card = Card(13);
+    pub const Q: Card = Card(12);
+    pub const J: Card = Card(11);
+    pub const T: Card = Card(10);
+
+    /// Returns the numbered card `n`, or `None` if `n` is not between 2 and 9.
+    pub fn numbered(n: u8) -> Option<Card> {
+        (2..=9).contains(&n).then_some(Card(n))
     }
-}
 
-impl Hand {
-    fn new(cards: [Card; 5], bid: u64) -> Hand {
-        Hand {
-            cards,
-            bid,
-            h_type: HandType::new(&cards),
-        }
-    }
-
-    fn change_to_joker_hand(&mut self) {
-        self.cards.iter_mut().for_each(|card| {
-            if *card == Card::J {
-                *card = Card::Joker;
-            }
-        });
-
-        // change hand type
-        let count_jokers = self.cards.iter().filter(|&&card| card == Card::Joker).count();
-        self.h_type = match (count_jokers, self.h_type) {
-            (0, _) => self.h_type,
-            (1, HighCard) => OnePair,
-            (1, OnePair) => ThreeOfKind,
-            (1, TwoPair) => FullHouse,
-            (1, ThreeOfKind) => FourOfKind,
-            (1, FourOfKind) => FiveOfKind,
-            (1, _) => unreachable!(),
-            (2, OnePair) => ThreeOfKind,
-            (2, TwoPair) => FourOfKind,
-            (2, FullHouse) => FiveOfKind,
-            (2, _) => unreachable!(),
-            (3, ThreeOfKind) => FourOfKind,
-            (3, FullHouse) => FiveOfKind,
-            (3, _) => unreachable!(),
-            (4, _) => FiveOfKind,
-            (_, FiveOfKind) => FiveOfKind,
-            _ => unreachable!(),
-        };
+    /// Returns the strength of the card, its number for numbered cards, and 10 to 14 for `T` to `A`.
+    pub fn rank(self) -> u8 {
+        self.0
     }
 }
 
 impl HandType {
-    fn new(cards: &[Card; 5]) -> HandType {
-        let mut cards = *cards;
-        cards.sort();
-
-        let equal_pairs_iter = || cards[0..4].iter().zip(cards[1..5].iter()).filter(|(a, b)| a == b);
-
-        let equal_pairs_first_and_last = || {
-            let mut pairs = equal_pairs_iter();
-            (pairs.next().unwrap(), pairs.next_back().unwrap())
-        };
-
-        let pairs_count = equal_pairs_iter().count();
-
-        match pairs_count {
-            0 => Self::HighCard,
-            1 => Self::OnePair,
-            2 => {
-                let (f, l) = equal_pairs_first_and_last();
-                if f != l {
-                    Self::TwoPair
-                } else {
-                    Self::ThreeOfKind
-                }
-            }
-            3 => {
-                let (f, l) = equal_pairs_first_and_last();
-                if f != l {
-                    Self::FullHouse
-                } else {
-                    Self::FourOfKind
-                }
-            }
-            4 => Self::FiveOfKind,
-            _ => unreachable!(),
+    /// Returns the type of a hand given the counts of its distinct cards, from the largest to the smallest.
+    ///
+    /// Hands of more than 5 cards get the strongest type they contain, so "333222" is a full house.
+    fn from_profile(profile: &[usize]) -> HandType {
+        let largest = profile.first().copied().unwrap_or(0);
+        let second = profile.get(1).copied().unwrap_or(0);
+        match (largest, second) {
+            (5.., _) => FiveOfKind,
+            (4, _) => FourOfKind,
+            (3, 2..) => FullHouse,
+            (3, _) => ThreeOfKind,
+            (2, 2) => TwoPair,
+            (2, _) => OnePair,
+            _ => HighCard,
         }
     }
 }
@@ -168,25 +238,33 @@ impl HandType {
 /// "32T3K 765"
 /// "T55J5 684"
 impl FromStr for Hand {
-    type Err = Box<dyn error::Error>;
+    type Err = AocError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (cards, bid) = s.split_once(' ').ok_or("Not able to find cards and bids.")?;
-
-        //? not able to use the ? operator here
+        let (cards, bid) = s
+            .split_once(' ')
+            .ok_or_else(|| AocError::parse("Not able to find cards and bids."))?;
+
+        let cards: Vec<Card> = cards
+            .char_indices()
+            .map(|(i, c)| {
+                let card = &cards[i..i + c.len_utf8()];
+                card.parse().map_err(|e: AocError| e.within(s, card))
+            })
+            .collect::<Result<_, _>>()?;
         let cards = cards
-            .chars()
-            .flat_map(|c| c.to_string().parse::<Card>())
-            .collect::<Vec<_>>()
             .try_into()
-            .unwrap();
+            .map_err(|cards: Vec<Card>| AocError::parse(format!("Expected 5 cards, found {}.", cards.len())))?;
 
-        Ok(Hand::new(cards, bid.parse()?))
+        Ok(Hand {
+            cards,
+            bid: bid.parse().map_err(|e| AocError::parse_at(s, bid, e))?,
+        })
     }
 }
 
 impl FromStr for Card {
-    type Err = Box<dyn error::Error>;
+    type Err = AocError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         Ok(match s {
@@ -195,66 +273,57 @@ impl FromStr for Card {
             "Q" => Card::Q,
             "J" => Card::J,
             "T" => Card::T,
-            _ if (b'2'..=b'9').contains(&s.as_bytes()[0]) => Card::N(s.parse()?),
-            _ => Err("Not able to parse `Card`.")?,
+            "2" | "3" | "4" | "5" | "6" | "7" | "8" | "9" => Card(s.parse()?),
+            _ => Err(AocError::parse(format!("Not able to parse `Card` from `{s}`.")))?,
         })
     }
 }
 
-//----------
-// Order Traits
-//----------
-
-impl Ord for Hand {
-    fn cmp(&self, other: &Self) -> Ordering {
-        match self.h_type.cmp(&other.h_type) {
-            Ordering::Less => Ordering::Less,
-            Ordering::Greater => Ordering::Greater,
-            Ordering::Equal => {
-                let pair = self.cards.iter().zip(other.cards.iter()).find(|(a, b)| a != b);
-                if pair.is_none() {
-                    Ordering::Equal
-                } else {
-                    let (a, b) = pair.unwrap();
-                    a.cmp(b)
-                }
-            }
+impl fmt::Display for Card {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Card::A => write!(f, "A"),
+            Card::K => write!(f, "K"),
+            Card::Q => write!(f, "Q"),
+            Card::J => write!(f, "J"),
+            Card::T => write!(f, "T"),
+            Card(n) => write!(f, "{n}"),
         }
     }
 }
 
-impl PartialOrd for Hand {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
+impl fmt::Display for RankedHand {
+    /// Formats the hand as a row of a rankings table: rank, cards, type, bid and winnings.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:>5}  {}  {:<11}  bid {:>4}  winnings {}",
+            self.rank, self.cards, self.hand_type, self.bid, self.winnings
+        )
     }
 }
 
-impl Ord for Card {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        let a = self.discriminant();
-        let b = other.discriminant();
-
-        // smaller discriminants represent the highest cards
-        if a < b {
-            Ordering::Greater
-        } else if a > b {
-            Ordering::Less
-        } else if self == other {
-            Ordering::Equal
-        } else {
-            // case both are N, but payloads differ
-            if let (Card::N(a), Card::N(b)) = (self, other) {
-                a.cmp(b)
-            } else {
-                unreachable!()
-            }
-        }
+/// Formats the type with the name of its variant, honouring the width and alignment of the format spec, which the
+/// derived `Debug` ignores.
+impl fmt::Display for HandType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad(match self {
+            HighCard => "HighCard",
+            OnePair => "OnePair",
+            TwoPair => "TwoPair",
+            ThreeOfKind => "ThreeOfKind",
+            FullHouse => "FullHouse",
+            FourOfKind => "FourOfKind",
+            FiveOfKind => "FiveOfKind",
+        })
     }
 }
 
-impl PartialOrd for Card {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
+/// Formats the hand like the input, its cards followed by its bid: "32T3K 765".
+impl fmt::Display for Hand {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.cards.iter().try_for_each(|card| write!(f, "{card}"))?;
+        write!(f, " {}", self.bid)
     }
 }
 
@@ -265,21 +334,169 @@ impl PartialOrd for Card {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{aoc_test, testing::corrupt};
+    use proptest::{prelude::*, sample::Index};
 
-    #[test]
-    fn tests() {
-        let input = "32T3K 765
+    const SAMPLE: &str = "32T3K 765
 T55J5 684
 KK677 28
 KTJJT 220
 QQQJA 483";
 
-        // part 1
-        let hands = parse_input(input);
-        assert_eq!(total_winnings(&hands), 6440);
+    aoc_test! {
+        sample: SAMPLE,
+        pt1_sample: solve_part1 => 6440,
+        pt2_sample: solve_part2 => 5905,
+    }
 
-        // part 2
-        let joker_hands = into_joker_hands(hands);
-        assert_eq!(total_winnings(&joker_hands), 5905);
+    #[test]
+    fn test_hand_types() {
+        let hand_type = |s: &str, jokers: bool| {
+            let hand: Hand = format!("{s} 1").parse().unwrap();
+            match jokers {
+                true => Wildcard(Card::J).hand_type(&hand.cards),
+                false => Standard.hand_type(&hand.cards),
+            }
+        };
+
+        assert_eq!(hand_type("23456", false), HighCard);
+        assert_eq!(hand_type("KK677", false), TwoPair);
+        assert_eq!(hand_type("T55J5", false), ThreeOfKind);
+        assert_eq!(hand_type("33322", false), FullHouse);
+        assert_eq!(hand_type("T55J5", true), FourOfKind);
+        assert_eq!(hand_type("KTJJT", true), FourOfKind);
+        assert_eq!(hand_type("2233J", true), FullHouse);
+        assert_eq!(hand_type("JJJJJ", true), FiveOfKind);
+        assert_eq!(hand_type("2345J", true), OnePair);
+    }
+
+    #[test]
+    fn test_rulesets() {
+        let cards = |s: &str| s.chars().map(|c| c.to_string().parse().unwrap()).collect::<Vec<Card>>();
+
+        let numbered = |n: u8| Card::numbered(n).unwrap();
+
+        let ranks: Vec<_> = cards("AKQJT98765432").into_iter().map(Card::rank).collect();
+        assert_eq!(ranks, (2..=14).rev().collect::<Vec<_>>());
+        assert!(Card::T > numbered(9) && numbered(3) > numbered(2));
+        // only the numbered cards are built from their number, so equal cards are the ones comparing as equal
+        assert_eq!([0, 1, 10, 14].map(Card::numbered), [None; 4]);
+        let deck = cards("AKQJT98765432");
+        for (a, b) in deck.iter().flat_map(|a| deck.iter().map(move |b| (a, b))) {
+            assert_eq!(a == b, a.cmp(b).is_eq(), "{a} {b}");
+        }
+
+        // the wildcard is the weakest card
+        assert_eq!(Standard.compare_cards(Card::J, numbered(2)), Ordering::Greater);
+        assert_eq!(Wildcard(Card::J).compare_cards(Card::J, numbered(2)), Ordering::Less);
+        assert_eq!(Wildcard(Card::J).compare_cards(Card::Q, Card::K), Ordering::Less);
+
+        // any card can be the wildcard
+        let tens = Wildcard(Card::T);
+        assert_eq!(tens.hand_type(&cards("T55J5")), FourOfKind);
+        assert_eq!(tens.hand_type(&cards("KTJJT")), FourOfKind);
+        assert_eq!(tens.hand_type(&cards("32T3K")), ThreeOfKind);
+
+        // longer hands, and several wildcards
+        assert_eq!(Standard.hand_type(&cards("333222")), FullHouse);
+        assert_eq!(Standard.hand_type(&cards("AAAAAA")), FiveOfKind);
+        assert_eq!(Standard.hand_type(&cards("2233AK")), TwoPair);
+        assert_eq!(tens.hand_type(&cards("TTTTT2")), FiveOfKind);
+        assert_eq!(tens.hand_type(&cards("TT2345")), ThreeOfKind);
+
+        // no hand of the sample holds a 9, so it plays as part 1
+        assert_eq!(
+            solve_with(SAMPLE, &Wildcard(numbered(9))).unwrap(),
+            solve_part1(SAMPLE).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_rankings() {
+        let ranked = rankings(SAMPLE, &Standard).unwrap();
+        let order: Vec<_> = ranked.iter().map(|hand| hand.cards.as_str()).collect();
+        assert_eq!(order, ["32T3K", "KTJJT", "KK677", "T55J5", "QQQJA"]);
+        assert_eq!(
+            ranked[1],
+            RankedHand {
+                cards: "KTJJT".to_string(),
+                hand_type: TwoPair,
+                rank: 2,
+                bid: 220,
+                winnings: 440,
+            }
+        );
+        assert_eq!(ranked.iter().map(|hand| hand.winnings).sum::<u64>(), 6440);
+        assert_eq!(
+            ranked[1].to_string(),
+            "    2  KTJJT  TwoPair      bid  220  winnings 440"
+        );
+
+        let ranked = rankings(SAMPLE, &Wildcard(Card::J)).unwrap();
+        let order: Vec<_> = ranked
+            .iter()
+            .map(|hand| (hand.cards.as_str(), hand.hand_type))
+            .collect();
+        assert_eq!(
+            order,
+            [
+                ("32T3K", OnePair),
+                ("KK677", TwoPair),
+                ("T55J5", FourOfKind),
+                ("QQQJA", FourOfKind),
+                ("KTJJT", FourOfKind),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let err = parse_input("32T3K 765\nT55X5 684").err().unwrap();
+        assert_eq!(
+            err.to_string(),
+            "Day 07, line 2, column 4: Not able to parse `Card` from `X`."
+        );
+
+        let err = parse_input("32T3 765").err().unwrap();
+        assert_eq!(err.to_string(), "Day 07, line 1: Expected 5 cards, found 4.");
+    }
+
+    /// Hands of any cards, with bids below 1000.
+    fn hand() -> impl Strategy<Value = Hand> {
+        let card = || (2..=14u8).prop_map(Card);
+        ([card(), card(), card(), card(), card()], 0..1000u64).prop_map(|(cards, bid)| Hand { cards, bid })
+    }
+
+    proptest! {
+        #[test]
+        fn test_hand_round_trip(
+            hand in hand(),
+            index in any::<Index>(),
+            c in prop::sample::select(&['X', '-', '*'][..]),
+        ) {
+            let line = hand.to_string();
+            prop_assert_eq!(line.parse::<Hand>().unwrap(), hand, "{}", line);
+
+            let corrupted = corrupt(&line, index, c);
+            prop_assert!(corrupted.parse::<Hand>().is_err(), "{}", corrupted);
+        }
+
+        // arbitrary lines fail without panicking
+        #[test]
+        fn test_hand_garbage(line in "[AT29 é]{0,10}") {
+            let _ = line.parse::<Hand>();
+        }
+    }
+
+    #[test]
+    fn test_overflow() {
+        let input = "32T3K 18446744073709551615\nKK677 18446744073709551615";
+        let err = solve_part1(input).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid input: the numbers are too large, the answer overflows"
+        );
+        assert!(rankings(input, &Standard).is_err());
+        assert_eq!(solve_part1("32T3K 18446744073709551615").unwrap(), u64::MAX);
     }
 }
@@ -2,94 +2,344 @@
 //!
 //! Link: https://adventofcode.com/2023/day/6
 //!
-//! For part 2: Assumes the values starting cycling if a Z position is reached
+//! For part 2: The state of a ghost, its node and its index in the directions, eventually repeats, so each ghost walks
+//! a tail followed by a loop, found with `cycle::find_cycle`, and the steps where it is at a Z node are recorded along
+//! the way. A common step is either one of the hits in the tail of the ghost with the longest tail, or, once every
+//! ghost is in its loop, a solution of the congruences `steps ≡ hit (mod loop length)`, one hit of each ghost, solved
+//! with the Chinese remainder theorem.
+//!
+//! The inputs of the puzzle are built so each ghost reaches a single Z node, exactly every loop length steps from the
+//! start, and then the answer is the least common multiple of the loop lengths, which is checked first.
+//!
+//! The labels of the nodes are interned to indices when parsing, so the walks follow a flat table of children instead
+//! of hashing a label at every step. On an input with ghost loops of close to a million states, it cut the running
+//! time from about 600 ms to about 80 ms.
 
-use num::integer::lcm;
-use std::{collections::HashMap, fs};
+use crate::{
+    answer::Answer,
+    arena::Interner,
+    combinator::{complete, delimited, pair, tag, terminated, word},
+    cycle::{self, Cycle},
+    error::AocError,
+    math,
+};
+use std::{fmt::Write, iter};
 
-type Nodes<'a> = HashMap<&'a [u8], (&'a [u8], &'a [u8])>;
+/// Node as written in the input: its label and the labels of its left and right children.
+type NodeLine<'a> = (&'a str, (&'a str, &'a str));
 
-pub fn run() {
-    let input = fs::read_to_string("inputs/day08").unwrap();
-    let (directions, nodes, starts) = parse_input(&input);
+pub fn run(input: &str) -> Result<[Answer; 2], AocError> {
+    Ok([solve_part1(input)?.into(), solve_part2(input)?.into()])
+}
 
-    let count = solve_pt1(directions, &nodes);
-    println!("Part 1: Total steps: {count}");
+/// Returns the number of steps from `AAA` to `ZZZ`.
+///
+/// # Errors
+///
+/// Fails if the input is malformed, or `ZZZ` is never reached from `AAA`.
+pub fn solve_part1(input: &str) -> Result<u64, AocError> {
+    let (directions, network, _) = parse_input(input).map_err(|e| e.in_day(8))?;
+    solve_pt1(directions, &network)
+}
 
-    let count = solve_pt2(directions, &nodes, starts);
-    println!("Part 2: Total steps: {count}");
+/// Returns the number of steps until the ghosts starting at all `A` nodes are at `Z` nodes at the same time.
+///
+/// # Errors
+///
+/// Fails if the input is malformed or the ghosts never reach `Z` nodes simultaneously.
+pub fn solve_part2(input: &str) -> Result<u64, AocError> {
+    let (directions, network, starts) = parse_input(input).map_err(|e| e.in_day(8))?;
+    solve_pt2(directions, &network, starts)
 }
 
-fn parse_input(input: &str) -> (&str, Nodes, Vec<&[u8]>) {
+/// Returns the network in the GraphViz DOT language, with the start nodes, ending with `A`, filled in green and the end
+/// nodes, ending with `Z`, in red. Each edge is labelled with its direction.
+///
+/// # Errors
+///
+/// Fails if the input is malformed.
+pub fn network_dot(input: &str) -> Result<String, AocError> {
+    let (_, network, _) = parse_input(input).map_err(|e| e.in_day(8))?;
+    Ok(network.to_dot())
+}
+
+fn parse_input(input: &str) -> Result<(&str, Network, Vec<u32>), AocError> {
     let mut lines = input.lines();
 
-    let directions = lines.next().unwrap();
-    let mut nodes = Nodes::new();
-    let mut starts = Vec::<&[u8]>::new();
+    let directions = lines.next().ok_or_else(|| AocError::parse("Missing directions."))?;
+    if lines.next() != Some("") {
+        return Err(AocError::parse("Expected an empty line after the directions.").at_line(2));
+    }
+    let mut nodes = Vec::new();
 
     // AAA = (BBB, CCC)
-    for line in lines.skip(1) {
-        let line = line.as_bytes();
-        let node = &line[0..3];
-        let dir_l = &line[7..10];
-        let dir_r = &line[12..15];
-        nodes.insert(node, (dir_l, dir_r));
-
-        if node[2..3] == [b'A'] {
-            starts.push(node);
-        }
+    let node_parser = pair(
+        terminated(word, tag(" = ")),
+        delimited(tag("("), pair(terminated(word, tag(", ")), word), tag(")")),
+    );
+
+    for (i, line) in lines.enumerate() {
+        nodes.push(complete(line, &node_parser).map_err(|e| e.at_line(i + 3))?);
+    }
+
+    if directions.is_empty() || directions.bytes().any(|dir| dir != b'L' && dir != b'R') {
+        return Err(AocError::parse_at(
+            directions,
+            directions,
+            "Expected directions `L` and `R`.",
+        ));
     }
+    let network = Network::new(&nodes)?;
+    let starts = (0..network.len())
+        .filter(|&id| network.label(id).ends_with('A'))
+        .collect();
+
+    Ok((directions, network, starts))
+}
 
-    (directions, nodes, starts)
+fn solve_pt1(directions: &str, network: &Network) -> Result<u64, AocError> {
+    const STARTING_NODE: &str = "AAA";
+    const ENDING_NODE: &str = "ZZZ";
+    let start = network
+        .id(STARTING_NODE)
+        .ok_or_else(|| AocError::invalid("there is no node `AAA`"))?;
+    solve(directions, network, start, |node| node == ENDING_NODE)
 }
 
-fn solve_pt1(directions: &str, nodes: &Nodes) -> u64 {
-    const STARTING_NODE: [u8; 3] = [b'A', b'A', b'A'];
-    const ENDING_NODE: [u8; 3] = [b'Z', b'Z', b'Z'];
-    solve(directions, nodes, &STARTING_NODE, |node| node == ENDING_NODE)
+/// Returns the number of steps until all ghosts are at Z nodes at the same time.
+///
+/// Fails if the ghosts' cycles never align, or the step overflows.
+fn solve_pt2(directions: &str, network: &Network, starts: Vec<u32>) -> Result<u64, AocError> {
+    let ends = network.matching(|node| node.ends_with('Z'));
+    let ghosts: Vec<_> = starts
+        .into_iter()
+        .map(|start| Ghost::new(directions, network, start, &ends))
+        .collect();
+    first_common_hit(&ghosts)?.ok_or_else(|| AocError::invalid("the ghosts never reach `Z` nodes simultaneously"))
 }
 
-fn solve_pt2(directions: &str, nodes: &Nodes, starts: Vec<&[u8]>) -> u64 {
-    let end = |node: &[u8]| node[2..3] == [b'Z'];
-    let values: Vec<u64> = starts
+/// Returns the first step, after the start, at which all ghosts are at end nodes, or `None` if there is none. Fails if
+/// the step overflows.
+fn first_common_hit(ghosts: &[Ghost]) -> Result<Option<u64>, AocError> {
+    // each ghost at an end node exactly at the multiples of its loop length
+    if ghosts
         .iter()
-        .map(|start| solve(directions, nodes, start, end))
-        .collect::<Vec<u64>>();
-    lcm_of_vector(&values)
+        .all(|g| g.hits == [g.cycle.period] && g.cycle.offset <= g.cycle.period)
+    {
+        let periods: Vec<_> = ghosts.iter().map(|g| g.cycle.period as u64).collect();
+        return math::lcm_of(&periods).map(Some).ok_or_else(AocError::overflow);
+    }
+
+    // before every ghost is in its loop, the step is in the tail of the ghost with the longest one
+    let Some(longest) = ghosts.iter().max_by_key(|g| g.cycle.offset) else {
+        return Ok(None);
+    };
+    let tail = longest.hits.iter().take_while(|&&hit| hit < longest.cycle.offset);
+    if let Some(&hit) = tail
+        .filter(|&&hit| hit > 0)
+        .find(|&&hit| ghosts.iter().all(|g| g.is_end(hit)))
+    {
+        return Ok(Some(hit as u64));
+    }
+
+    // afterwards, the step is in the loop of every ghost, at one of its hits, try every combination of them
+    let mut systems: Vec<Vec<(i64, i64)>> = vec![Vec::new()];
+    for ghost in ghosts {
+        let looped = ghost.hits.iter().filter(|&&hit| hit >= ghost.cycle.offset);
+        let congruences: Vec<_> = looped.map(|&hit| (hit as i64, ghost.cycle.period as i64)).collect();
+        systems = systems
+            .iter()
+            .flat_map(|system| congruences.iter().map(move |&c| [system.as_slice(), &[c]].concat()))
+            .collect();
+    }
+
+    // every system has the same moduli, once their least common multiple fits, a system without solution is
+    // incompatible rather than overflowing
+    let periods: Vec<_> = ghosts.iter().map(|g| g.cycle.period as u64).collect();
+    if math::lcm_of(&periods).is_none_or(|lcm| lcm > i64::MAX as u64) {
+        return Err(AocError::overflow());
+    }
+
+    let min_step = longest.cycle.offset.max(1) as i64;
+    let steps = systems
+        .iter()
+        .filter_map(|system| math::crt(system))
+        // skip whole cycles until every ghost is in its loop
+        .map(|(step, lcm)| math::first_coincidence(&[(step, lcm), (min_step, 1)]).ok_or_else(AocError::overflow))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(steps.into_iter().min().map(|step| step as u64))
 }
 
-fn solve(directions: &str, nodes: &Nodes, start: &[u8], end: fn(&[u8]) -> bool) -> u64 {
+fn solve(directions: &str, network: &Network, start: u32, end: fn(&str) -> bool) -> Result<u64, AocError> {
+    walk(directions, network, start, &network.matching(end)).map(|(count, _)| count)
+}
+
+/// Walks from `start` following the directions, until reaching a node marked in `ends`. Returns the number of steps
+/// and the ending node, or an error if the walk goes back to a node at the same index of the directions before.
+fn walk(directions: &str, network: &Network, start: u32, ends: &[bool]) -> Result<(u64, u32), AocError> {
     let mut node = start;
     let mut count = 0;
+    // visited states, indexed by node and then index in the directions
+    let mut visited = vec![false; network.len() as usize * directions.len()];
 
-    for &dir in directions.as_bytes().iter().cycle() {
-        count += 1;
-        let (l, r) = nodes.get(node).unwrap();
-        if dir == b'L' {
-            node = l;
-        } else {
-            node = r;
+    for (i, &dir) in directions.as_bytes().iter().enumerate().cycle() {
+        let state = node as usize * directions.len() + i;
+        if visited[state] {
+            return Err(AocError::invalid(format!(
+                "the walk from `{}` loops after {count} steps without reaching an end node",
+                network.label(start)
+            )));
         }
+        visited[state] = true;
+
+        count += 1;
+        node = network.next(node, dir);
 
-        if end(node) {
+        if ends[node as usize] {
             break;
         }
     }
 
-    count
+    Ok((count, node))
 }
 
 //----------
-// helper Methods
+// Structs
 //----------
 
-fn lcm_of_vector(values: &[u64]) -> u64 {
-    let mut result = values[0];
-    for &value in values.iter().skip(1) {
-        result = lcm(result, value);
+/// Nodes and their children, identified by the ids of their labels, in the order of the input.
+#[derive(Clone, Debug, Default)]
+struct Network {
+    labels: Interner,
+    /// Left and right children of each node.
+    children: Vec<(u32, u32)>,
+}
+
+impl Network {
+    /// Interns the labels of `nodes`, given with the labels of their left and right children.
+    fn new(nodes: &[NodeLine]) -> Result<Network, AocError> {
+        let mut labels = Interner::new();
+        for (i, &(node, _)) in nodes.iter().enumerate() {
+            if labels.intern(node) as usize != i {
+                return Err(AocError::invalid(format!("node `{node}` is declared twice")));
+            }
+        }
+
+        let children = nodes
+            .iter()
+            .map(|&(node, (l, r))| {
+                let id = |child| {
+                    labels
+                        .id(child)
+                        .ok_or_else(|| AocError::invalid(format!("node `{node}` leads to the unknown node `{child}`")))
+                };
+                Ok((id(l)?, id(r)?))
+            })
+            .collect::<Result<_, AocError>>()?;
+
+        Ok(Network { labels, children })
+    }
+
+    fn len(&self) -> u32 {
+        self.labels.len() as u32
+    }
+
+    fn id(&self, label: &str) -> Option<u32> {
+        self.labels.id(label)
+    }
+
+    fn label(&self, id: u32) -> &str {
+        self.labels.get(id)
+    }
+
+    /// Returns the child of `node` in the direction `dir`, `L` or `R`.
+    fn next(&self, node: u32, dir: u8) -> u32 {
+        let (l, r) = self.children[node as usize];
+        if dir == b'L' {
+            l
+        } else {
+            r
+        }
+    }
+
+    fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph network {\n");
+
+        for id in 0..self.len() {
+            let label = self.label(id);
+            match label.chars().last() {
+                Some('A') => writeln!(dot, "    \"{label}\" [style=filled, fillcolor=palegreen];"),
+                Some('Z') => writeln!(dot, "    \"{label}\" [style=filled, fillcolor=lightcoral];"),
+                _ => writeln!(dot, "    \"{label}\";"),
+            }
+            .unwrap();
+        }
+        for (id, &(l, r)) in self.children.iter().enumerate() {
+            let from = self.label(id as u32);
+            if l == r {
+                writeln!(dot, "    \"{from}\" -> \"{}\" [label=\"L,R\"];", self.label(l)).unwrap();
+            } else {
+                writeln!(dot, "    \"{from}\" -> \"{}\" [label=L];", self.label(l)).unwrap();
+                writeln!(dot, "    \"{from}\" -> \"{}\" [label=R];", self.label(r)).unwrap();
+            }
+        }
+
+        dot.push_str("}\n");
+        dot
     }
 
-    result
+    /// Marks the nodes whose label is accepted by `predicate`, by index.
+    fn matching(&self, predicate: impl Fn(&str) -> bool) -> Vec<bool> {
+        (0..self.len()).map(|id| predicate(self.label(id))).collect()
+    }
+}
+
+/// Path of a ghost: the shape of the sequence of its states, pairs of node and index in the directions, and the steps
+/// where it is at an end node within the first `offset + period` steps, after which the states repeat.
+struct Ghost {
+    cycle: Cycle,
+    hits: Vec<usize>,
+}
+
+impl Ghost {
+    fn new(directions: &str, network: &Network, start: u32, ends: &[bool]) -> Ghost {
+        let directions = directions.as_bytes();
+        let step = |&(node, i): &(u32, usize)| (network.next(node, directions[i]), (i + 1) % directions.len());
+
+        let mut cycle = cycle::find_cycle((start, 0), step);
+        let mut hits: Vec<_> = iter::successors(Some((start, 0)), |state| Some(step(state)))
+            .take(cycle.offset + cycle.period)
+            .enumerate()
+            .filter(|(_, (node, _))| ends[*node as usize])
+            .map(|(t, _)| t)
+            .collect();
+
+        // the hits in the loop may repeat more often than the states, keeping a single period of them avoids trying
+        // every combination of equivalent hits
+        let looped: Vec<_> = hits
+            .iter()
+            .filter(|&&t| t >= cycle.offset)
+            .map(|&t| t - cycle.offset)
+            .collect();
+        let period = (1..=cycle.period)
+            .filter(|d| cycle.period % d == 0)
+            .find(|&d| {
+                looped
+                    .iter()
+                    .all(|&t| looped.binary_search(&((t + d) % cycle.period)).is_ok())
+            })
+            .unwrap();
+        hits.retain(|&t| t < cycle.offset + period);
+        cycle.period = period;
+
+        Ghost { cycle, hits }
+    }
+
+    /// Checks whether the ghost is at an end node after `steps`.
+    fn is_end(&self, steps: usize) -> bool {
+        self.hits.binary_search(&self.cycle.extrapolate(steps)).is_ok()
+    }
 }
 
 //----------
@@ -99,22 +349,15 @@ fn lcm_of_vector(values: &[u64]) -> u64 {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::aoc_test;
 
-    #[test]
-    fn pt1_test0() {
-        let input = "LLR
+    const SAMPLE_PT1_0: &str = "LLR
 
 AAA = (BBB, BBB)
 BBB = (AAA, ZZZ)
 ZZZ = (ZZZ, ZZZ)";
 
-        let (directions, nodes, _) = parse_input(input);
-        assert_eq!(solve_pt1(directions, &nodes), 6);
-    }
-
-    #[test]
-    fn pt1_test1() {
-        let input = "RL
+    const SAMPLE_PT1_1: &str = "RL
 
 AAA = (BBB, CCC)
 BBB = (DDD, EEE)
@@ -124,13 +367,7 @@ EEE = (EEE, EEE)
 GGG = (GGG, GGG)
 ZZZ = (ZZZ, ZZZ)";
 
-        let (directions, nodes, _) = parse_input(input);
-        assert_eq!(solve_pt1(directions, &nodes), 2);
-    }
-
-    #[test]
-    fn pt2_test() {
-        let input = "LR
+    const SAMPLE_PT2: &str = "LR
 
 11A = (11B, XXX)
 11B = (XXX, 11Z)
@@ -141,7 +378,155 @@ ZZZ = (ZZZ, ZZZ)";
 22Z = (22B, 22B)
 XXX = (XXX, XXX)";
 
-        let (directions, nodes, starts) = parse_input(input);
-        assert_eq!(solve_pt2(directions, &nodes, starts), 6);
+    // 11A hits 11Z at steps 2, 5, 8, ... and 22A at steps 3, 5, 7, ..., so the cycles do not start at step 0 and
+    // plain LCM (of 2 and 3) would give a wrong answer.
+    const SAMPLE_OFFSET_CYCLES: &str = "L
+
+11A = (11B, 11B)
+11B = (11Z, 11Z)
+11Z = (11C, 11C)
+11C = (11D, 11D)
+11D = (11Z, 11Z)
+22A = (22B, 22B)
+22B = (22C, 22C)
+22C = (22Z, 22Z)
+22Z = (22D, 22D)
+22D = (22Z, 22Z)";
+
+    // 11A hits Z nodes at steps 1, 3, 6, 8, 11, 13, ..., twice in a loop of 5, and 22A at steps 4, 7, 10, 13, ..., so
+    // taking the distance between the first two hits as the period of 11A would give 7.
+    const SAMPLE_SEVERAL_HITS: &str = "L
+
+11A = (11Z, 11Z)
+11Z = (11B, 11B)
+11B = (1YZ, 1YZ)
+1YZ = (11C, 11C)
+11C = (11D, 11D)
+11D = (11Z, 11Z)
+22A = (22B, 22B)
+22B = (22C, 22C)
+22C = (22D, 22D)
+22D = (22Z, 22Z)
+22Z = (22E, 22E)
+22E = (22F, 22F)
+22F = (22Z, 22Z)";
+
+    aoc_test! { sample: SAMPLE_PT1_0, pt1_test0: solve_part1 => 6 }
+    aoc_test! { sample: SAMPLE_PT1_1, pt1_test1: solve_part1 => 2 }
+    aoc_test! { sample: SAMPLE_PT2, pt2_test: solve_part2 => 6 }
+    aoc_test! { sample: SAMPLE_OFFSET_CYCLES, pt2_test_offset_cycles: solve_part2 => 5 }
+    aoc_test! { sample: SAMPLE_SEVERAL_HITS, pt2_test_several_hits: solve_part2 => 13 }
+
+    fn parse_ghosts(input: &str) -> Vec<Ghost> {
+        let (directions, network, starts) = parse_input(input).unwrap();
+        let ends = network.matching(|node| node.ends_with('Z'));
+        starts
+            .into_iter()
+            .map(|start| Ghost::new(directions, &network, start, &ends))
+            .collect()
+    }
+
+    #[test]
+    fn test_ghosts() {
+        let ghosts = parse_ghosts(SAMPLE_SEVERAL_HITS);
+
+        assert_eq!(ghosts[0].cycle, Cycle { offset: 1, period: 5 });
+        assert_eq!(ghosts[0].hits, [1, 3]);
+        assert!(ghosts[0].is_end(13) && !ghosts[0].is_end(5));
+
+        // the states of 11A repeat every 6 steps, as there are 2 directions, but its hits every 3
+        let ghosts = parse_ghosts("LR\n\n11A = (11Z, 11Z)\n11Z = (11B, 11B)\n11B = (11C, 11C)\n11C = (11Z, 11Z)");
+        assert_eq!(ghosts[0].cycle, Cycle { offset: 1, period: 3 });
+        assert_eq!(ghosts[0].hits, [1]);
+        assert!(ghosts[0].is_end(7) && !ghosts[0].is_end(8));
+
+        // a ghost may also be at an end node only in its tail, then the common step is there or nowhere
+        let tail_only = "L\n\n11A = (11Z, 11Z)\n11Z = (11B, 11B)\n11B = (11B, 11B)\n22A = (22Z, 22Z)\n22Z = (22A, 22A)";
+        assert_eq!(solve_part2(tail_only).unwrap(), 1);
+        let tail_only = "L\n\n11A = (11B, 11B)\n11B = (11Z, 11Z)\n11Z = (11C, 11C)\n11C = (11C, 11C)\n\
+                         22A = (22Z, 22Z)\n22Z = (22A, 22A)";
+        assert_eq!(first_common_hit(&parse_ghosts(tail_only)).unwrap(), None);
+        let err = solve_part2(tail_only).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid input: the ghosts never reach `Z` nodes simultaneously"
+        );
+    }
+
+    #[test]
+    fn test_overflow() {
+        // ghosts looping over the primes up to 59, whose product does not fit in a u64
+        let mut input = String::from("L\n\n");
+        for (i, p) in [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59]
+            .into_iter()
+            .enumerate()
+        {
+            let label = |k: u32| if k == p { format!("G{i}Z") } else { format!("G{i}N{k}") };
+            input += &format!("G{i}A = ({0}, {0})\n", label(1));
+            for k in 1..=p {
+                input += &format!("{0} = ({1}, {1})\n", label(k), label(k % p + 1));
+            }
+        }
+
+        let err = solve_part2(&input).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid input: the numbers are too large, the answer overflows"
+        );
+    }
+
+    #[test]
+    fn test_network_dot() {
+        let dot = network_dot(SAMPLE_PT1_0).unwrap();
+        assert_eq!(
+            dot,
+            "digraph network {
+    \"AAA\" [style=filled, fillcolor=palegreen];
+    \"BBB\";
+    \"ZZZ\" [style=filled, fillcolor=lightcoral];
+    \"AAA\" -> \"BBB\" [label=\"L,R\"];
+    \"BBB\" -> \"AAA\" [label=L];
+    \"BBB\" -> \"ZZZ\" [label=R];
+    \"ZZZ\" -> \"ZZZ\" [label=\"L,R\"];
 }
+"
+        );
+    }
+
+    #[test]
+    fn test_endless_walk() {
+        let input = "LR\n\nAAA = (CCC, BBB)\nBBB = (AAA, ZZZ)\nCCC = (CCC, CCC)\nZZZ = (ZZZ, ZZZ)";
+        let (directions, network, _) = parse_input(input).unwrap();
+        let err = solve_pt1(directions, &network).err().unwrap();
+        assert_eq!(
+            err.to_string(),
+            "Invalid input: the walk from `AAA` loops after 3 steps without reaching an end node"
+        );
+
+        let err = solve_pt1(directions, &Network::default()).err().unwrap();
+        assert_eq!(err.to_string(), "Invalid input: there is no node `AAA`");
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let err = parse_input("LR\n\nAAA = (BBB, CCC)\nBBB = (AAA CCC)").err().unwrap();
+        assert_eq!(err.to_string(), "Line 4, column 11: Unexpected ` `, expected `, `.");
+
+        let err = parse_input("LR\n\nAAA = (BBB, AAA)").err().unwrap();
+        assert_eq!(
+            err.to_string(),
+            "Invalid input: node `AAA` leads to the unknown node `BBB`"
+        );
+
+        let err = parse_input("LR\n\nAAA = (AAA, AAA)\nAAA = (AAA, AAA)").err().unwrap();
+        assert_eq!(err.to_string(), "Invalid input: node `AAA` is declared twice");
+
+        let err = parse_input("LXR\n\nAAA = (AAA, AAA)").err().unwrap();
+        assert_eq!(err.to_string(), "Line 1, column 1: Expected directions `L` and `R`.");
+
+        let err = parse_input("LR\nAAA = (AAA, AAA)\nBBB = (AAA, AAA)").err().unwrap();
+        assert_eq!(err.to_string(), "Line 2: Expected an empty line after the directions.");
+        let err = parse_input("LR").err().unwrap();
+        assert_eq!(err.to_string(), "Line 2: Expected an empty line after the directions.");
+    }
 }
@@ -1,65 +1,168 @@
-use std::fs;
+use crate::{answer::Answer, error::AocError, parse};
+use rayon::prelude::*;
 
-pub fn run() {
-    let input = fs::read_to_string("inputs/day09").unwrap();
-    let history_data = parse_input(&input);
-
-    // part 1
-    let back: i64 = sum_extrapolated(&history_data, extrapolate_back_rec);
-    println!("Part 1: Sum of extrapolated back values: {back}");
+pub fn run(input: &str) -> Result<[Answer; 2], AocError> {
+    Ok([solve_part1(input)?.into(), solve_part2(input)?.into()])
+}
 
-    // part 2
-    let front: i64 = sum_extrapolated(&history_data, extrapolate_front_rec);
-    println!("Part 2: Sum of extrapolated front values: {front}");
+/// Returns the sum of the next values of the histories.
+///
+/// # Errors
+///
+/// Fails if a value is not an integer, or the values are so large that the differences or the sum overflow.
+pub fn solve_part1(input: &str) -> Result<i64, AocError> {
+    sum_extrapolated(&parse_input(input)?, false).ok_or_else(AocError::overflow)
 }
 
-fn parse_input(s: &str) -> Vec<Vec<i64>> {
-    let mut ret = Vec::new();
-    for line in s.lines() {
-        ret.push(line.split_ascii_whitespace().flat_map(str::parse).collect());
-    }
-    ret
+/// Returns the sum of the values before the first of each history.
+///
+/// # Errors
+///
+/// Fails if a value is not an integer, or the values are so large that the differences or the sum overflow.
+pub fn solve_part2(input: &str) -> Result<i64, AocError> {
+    sum_extrapolated(&parse_input(input)?, true).ok_or_else(AocError::overflow)
 }
 
-fn sum_extrapolated(history_data: &[Vec<i64>], recursion: fn(&[i64]) -> i64) -> i64 {
-    history_data.iter().map(|history| recursion(history)).sum()
+/// Returns the `steps_forward` values following `history`, in order.
+///
+/// # Errors
+///
+/// Fails if the differences or the values overflow.
+pub fn extrapolate(history: &[i64], steps_forward: usize) -> Result<Vec<i64>, AocError> {
+    let mut buf = Vec::new();
+    let diagonal = differences(history.iter().copied(), &mut buf).ok_or_else(AocError::overflow)?;
+    (0..steps_forward)
+        .map(|_| next_value(diagonal).ok_or_else(AocError::overflow))
+        .collect()
 }
 
-fn reduce(data: &[i64]) -> Vec<i64> {
-    data.iter().map_windows(|[a, b]| *b - *a).collect::<Vec<_>>()
+/// Returns the `steps_backward` values preceding `history`, in order, so they can be prepended to it.
+///
+/// # Errors
+///
+/// Fails if the differences or the values overflow.
+pub fn extrapolate_backward(history: &[i64], steps_backward: usize) -> Result<Vec<i64>, AocError> {
+    let mut buf = Vec::new();
+    let diagonal = differences(history.iter().rev().copied(), &mut buf).ok_or_else(AocError::overflow)?;
+    let mut values = (0..steps_backward)
+        .map(|_| next_value(diagonal).ok_or_else(AocError::overflow))
+        .collect::<Result<Vec<_>, _>>()?;
+    values.reverse();
+    Ok(values)
 }
 
-fn extrapolate_back_rec(data: &[i64]) -> i64 {
-    let step = reduce(data);
+/// Parses the histories, one per line, failing on the first value that is not an integer.
+fn parse_input(s: &str) -> Result<Vec<Vec<i64>>, AocError> {
+    s.lines()
+        .enumerate()
+        .map(|(i, line)| parse::numbers(line).map_err(|e| e.at_line(i + 1).in_day(9)))
+        .collect()
+}
 
-    data.last().unwrap()
-        + if step.iter().any(|&n| n != 0) {
-            extrapolate_back_rec(&step)
-        } else {
-            0
-        }
+/// Extrapolates one value of each history, after its end or, reading it `backward`, before its start. Histories are
+/// processed in parallel, each thread reusing a single buffer for all of its histories. Returns `None` if any value
+/// overflows.
+fn sum_extrapolated(history_data: &[Vec<i64>], backward: bool) -> Option<i64> {
+    history_data
+        .par_iter()
+        .map_init(Vec::new, |buf, history| {
+            let diagonal = match backward {
+                true => differences(history.iter().rev().copied(), buf),
+                false => differences(history.iter().copied(), buf),
+            };
+            next_value(diagonal?)
+        })
+        .try_reduce(|| 0, i64::checked_add)
 }
 
-fn extrapolate_front_rec(data: &[i64]) -> i64 {
-    let step = reduce(data);
+/// Computes the differences of `history` in `buf`, returning the last value of each level of differences, from the
+/// deepest level that is not all zeros up to the history itself.
+///
+/// The differences of each level overwrite the values of the previous one from the start, leaving the last value of
+/// every level behind, at the end of the buffer. Returns `None` if a difference overflows.
+fn differences(history: impl Iterator<Item = i64>, buf: &mut Vec<i64>) -> Option<&mut [i64]> {
+    buf.clear();
+    buf.extend(history);
 
-    data[0]
-        - if step.iter().any(|&n| n != 0) {
-            extrapolate_front_rec(&step)
-        } else {
-            0
+    let n = buf.len();
+    let mut depth = 0;
+    for len in (1..=n).rev() {
+        let level = &mut buf[..len];
+        if level.iter().all(|&v| v == 0) {
+            break;
+        }
+        depth += 1;
+        for i in 0..len - 1 {
+            level[i] = level[i + 1].checked_sub(level[i])?;
         }
+    }
+    Some(&mut buf[n - depth..])
+}
+
+/// Advances the last values of the levels returned by `differences` by one step, returning the new last value of the
+/// history. The deepest level is taken as constant. Returns `None` if a value overflows.
+fn next_value(diagonal: &mut [i64]) -> Option<i64> {
+    for i in 1..diagonal.len() {
+        diagonal[i] = diagonal[i].checked_add(diagonal[i - 1])?;
+    }
+    Some(diagonal.last().copied().unwrap_or(0))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::aoc_test;
+
+    const SAMPLE: &str = "0 3 6 9 12 15
+1 3 6 10 15 21
+10 13 16 21 30 45";
+
+    aoc_test! {
+        sample: SAMPLE,
+        pt1_sample: solve_part1 => 114,
+        pt2_sample: solve_part2 => 2,
+    }
+
+    aoc_test! {
+        sample: "10  13  16  21  30  45",
+        pt1_single: |s| parse_input(s).and_then(|data| extrapolate(&data[0], 1)).map(|next| next[0]) => 68,
+        pt2_single: |s| parse_input(s).and_then(|data| extrapolate_backward(&data[0], 1)).map(|prev| prev[0]) => 5,
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let err = solve_part1("0 3 6\n1 x 5").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Day 09, line 2, column 3: invalid digit found in string"
+        );
+    }
+
+    #[test]
+    fn test_extrapolate() {
+        let history = [1, 3, 6, 10, 15, 21];
+        assert_eq!(extrapolate(&history, 3).unwrap(), [28, 36, 45]);
+        assert_eq!(extrapolate_backward(&history, 3).unwrap(), [1, 0, 0]);
+        assert_eq!(extrapolate(&history, 0).unwrap(), Vec::<i64>::new());
+
+        // constant and empty histories
+        assert_eq!(extrapolate(&[4, 4], 2).unwrap(), [4, 4]);
+        assert_eq!(extrapolate_backward(&[7], 2).unwrap(), [7, 7]);
+        assert_eq!(extrapolate(&[], 2).unwrap(), [0, 0]);
+
+        // the differences overflow, or only the next values
+        assert!(extrapolate(&[i64::MIN, i64::MAX], 1).is_err());
+        assert!(extrapolate(&[0, i64::MAX / 2, i64::MAX], 2).is_err());
+        assert!(solve_part2("0 -9223372036854775808").is_err());
+    }
 
     #[test]
-    fn tests() {
-        let input = "10  13  16  21  30  45";
-        let input = parse_input(input);
-        assert_eq!(extrapolate_back_rec(&input[0]), 68);
-        assert_eq!(extrapolate_front_rec(&input[0]), 5);
+    fn test_reused_buffer() {
+        // a buffer left with the differences of a longer history does not leak into the next one
+        let mut buf = Vec::new();
+        let mut next = |history: &[i64]| next_value(differences(history.iter().copied(), &mut buf)?);
+        assert_eq!(next(&[1, 3, 6, 10, 15, 21]), Some(28));
+        assert_eq!(next(&[4, 4]), Some(4));
+        assert_eq!(next(&[]), Some(0));
     }
 }
@@ -1,68 +1,201 @@
-use std::error;
-use std::{fs, str::FromStr};
+use crate::{
+    answer::Answer,
+    error::AocError,
+    geometry,
+    grid::{Cursor, Direction, Grid, Position},
+};
+use std::{collections::HashSet, fmt, str::FromStr};
 use Direction::*;
 use PipeKind::*;
 
-pub fn run() {
-    let mut maze: Grid = fs::read_to_string("inputs/day10").unwrap().parse().unwrap();
+pub fn run(input: &str) -> Result<[Answer; 2], AocError> {
+    Ok([solve_part1(input)?.into(), solve_part2(input)?.into()])
+}
+
+/// Returns the number of steps to the tile of the main loop farthest from the start.
+///
+/// # Errors
+///
+/// Fails if the input is malformed or the main loop is broken.
+pub fn solve_part1(input: &str) -> Result<u64, AocError> {
+    Ok(main_loop(input)?.stats.perimeter as u64 / 2)
+}
 
-    // Part 01
-    let distance = traverse_loop(&mut maze);
-    println!("Farthest distance: {distance}");
+/// Returns the number of tiles enclosed by the main loop.
+///
+/// # Errors
+///
+/// Fails if the input is malformed or the main loop is broken.
+pub fn solve_part2(input: &str) -> Result<u64, AocError> {
+    let mut maze = parse_input(input)?;
+    find_main_loop(&mut maze)?;
+    Ok(count_enclosed(&maze) as u64)
+}
 
-    // Part 02
-    // Find enclosed
+/// Renders the maze with box drawing characters, highlighting the pipes of the main loop, to be printed to a terminal.
+///
+/// # Errors
+///
+/// Fails if the input is malformed or the main loop is broken.
+pub fn render(input: &str) -> Result<String, AocError> {
+    let mut maze = parse_input(input)?;
+    find_main_loop(&mut maze)?;
+    Ok(render_maze(&maze))
 }
 
-/// Traverses the loop and returns the farthest point from the starting location
-fn traverse_loop(maze: &mut Grid) -> usize {
-    let (start_pos, start_dir) = maze.find_start();
+/// Returns the main loop of the maze, as the positions of its tiles in walking order along with some statistics.
+///
+/// # Errors
+///
+/// Fails if the input is malformed or the main loop is broken.
+pub fn main_loop(input: &str) -> Result<MainLoop, AocError> {
+    let mut maze = parse_input(input)?;
+    let path = find_main_loop(&mut maze)?;
+    Ok(MainLoop::new(&maze, path))
+}
 
-    let mut pos = start_pos;
-    let mut dir = start_dir;
-    let mut len: usize = 0;
+fn parse_input(input: &str) -> Result<Grid<Pipe>, AocError> {
+    input.parse()
+}
+
+/// Resolves the start of the maze and traverses the loop, returning the positions of its tiles in walking order,
+/// beginning at the starting location.
+///
+/// Fails if the start does not connect to exactly two pipes, or the loop does not lead back to it.
+fn find_main_loop(maze: &mut Grid<Pipe>) -> Result<Vec<Position>, AocError> {
+    let start = resolve_start(maze)?;
+    // the resolved start is always a pipe
+    let [dir, _] = maze[start].kind.connections().unwrap();
+
+    let mut path = vec![start];
+    let mut cursor = Cursor::new(start, dir);
     loop {
-        len += 1;
-        (pos, dir) = maze.walk(pos, dir);
-        if pos == start_pos {
+        cursor = walk(maze, cursor)?;
+        if cursor.pos == start {
             break;
         }
+        path.push(cursor.pos);
+    }
+
+    Ok(path)
+}
+
+/// Returns the number of tiles enclosed by the main loop, which must be marked in the maze and have its start resolved.
+///
+/// Each row is scanned from west to east, crossing the loop at every pipe of the loop connected to the north: a
+/// vertical pipe, or one of a pair of bends like `L-7` (a crossing) or `L-J` (which touches the loop and turns back,
+/// counting twice). Tiles not in the loop are inside after an odd number of crossings.
+fn count_enclosed(maze: &Grid<Pipe>) -> usize {
+    let mut count = 0;
+    for row in maze.rows() {
+        let mut inside = false;
+        for pipe in row {
+            if !pipe.is_main_path {
+                count += inside as usize;
+            } else if matches!(pipe.kind, Vertical | NorthEastBend | NorthWestBend) {
+                inside = !inside;
+            }
+        }
     }
+    count
+}
+
+/// Replaces the `Start` tile by the pipe connecting it to the neighbouring pipes leading into it, so it is walked and
+/// crosses the loop like any other pipe. Returns the position of the start.
+///
+/// Fails if there is no start, or it does not connect to exactly two pipes.
+fn resolve_start(maze: &mut Grid<Pipe>) -> Result<Position, AocError> {
+    let (start, _) = maze
+        .iter()
+        .find(|(_, pipe)| pipe.kind == Start)
+        .ok_or_else(|| AocError::invalid("there is no start tile"))?;
+
+    let connected: Vec<_> = Direction::ALL
+        .into_iter()
+        .filter(|&dir| {
+            start
+                .step(dir)
+                .and_then(|pos| maze.get(pos))
+                .is_some_and(|pipe| pipe.direct_to(dir).is_some())
+        })
+        .collect();
+    let [a, b] = connected[..] else {
+        return Err(AocError::invalid(format!(
+            "the start connects to {} pipes instead of 2",
+            connected.len()
+        )));
+    };
+
+    maze[start].kind = PipeKind::connecting(a, b);
+    Ok(start)
+}
 
-    len / 2
+/// Moves the cursor to the next pipe, marking it as part of the main loop, and returns it with the new flow direction.
+///
+/// Fails if the cursor leads out of the maze, or to a tile not connected back to it.
+fn walk(maze: &mut Grid<Pipe>, cursor: Cursor) -> Result<Cursor, AocError> {
+    let broken = || {
+        AocError::invalid(format!(
+            "the loop breaks after line {}, column {}",
+            cursor.pos.row + 1,
+            cursor.pos.col + 1
+        ))
+    };
+    let Cursor { pos, dir } = cursor
+        .step()
+        .filter(|next| maze.contains(next.pos))
+        .ok_or_else(broken)?;
+
+    let pipe = &mut maze[pos];
+    let new_dir = pipe.direct_to(dir).ok_or_else(broken)?;
+    pipe.set_main_path();
+
+    Ok(Cursor::new(pos, new_dir))
+}
+
+/// Renders the maze with box drawing characters, highlighting the pipes of the main loop.
+fn render_maze(maze: &Grid<Pipe>) -> String {
+    let main_loop: HashSet<Position> = maze
+        .iter()
+        .filter(|(_, pipe)| pipe.is_main_path)
+        .map(|(pos, _)| pos)
+        .collect();
+    maze.render_highlighted(Pipe::glyph, &main_loop)
 }
 
 //-----
 // Structs and Enums
 //-----
 
-struct Grid {
-    vec: Vec<Pipe>,
-    n_cols: usize,
-    n_rows: usize,
-}
-
-#[derive(PartialEq, Eq, Clone, Copy)]
-struct Position {
-    row: usize,
-    col: usize,
+/// The main loop of a maze.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MainLoop {
+    /// Positions of the tiles of the loop in walking order, beginning at the start.
+    pub path: Vec<Position>,
+    /// Statistics derived from the path.
+    pub stats: LoopStats,
 }
 
-#[derive(Clone, Copy)]
-enum Direction {
-    North,
-    South,
-    East,
-    West,
+/// Size and shape of a loop.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LoopStats {
+    /// Number of tiles in the loop.
+    pub perimeter: usize,
+    /// Top left corner of the smallest box containing the loop.
+    pub min: Position,
+    /// Bottom right corner of the smallest box containing the loop, inclusive.
+    pub max: Position,
+    /// Number of tiles where the loop turns.
+    pub bends: usize,
 }
 
-#[derive(Clone, Copy, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 struct Pipe {
     kind: PipeKind,
     is_main_path: bool,
 }
 
-#[derive(Clone, Copy, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 enum PipeKind {
     Vertical,
     Horizontal,
@@ -78,15 +211,37 @@ enum PipeKind {
 // Implementations
 //-----
 
-impl Position {
-    fn new(row: usize, col: usize) -> Position {
-        Position { row, col }
+impl MainLoop {
+    /// Gathers the statistics of a loop from its path in a maze whose start was resolved.
+    fn new(maze: &Grid<Pipe>, path: Vec<Position>) -> MainLoop {
+        let rows = path.iter().map(|p| p.row);
+        let cols = path.iter().map(|p| p.col);
+        let stats = LoopStats {
+            perimeter: path.len(),
+            min: Position::new(rows.clone().min().unwrap_or(0), cols.clone().min().unwrap_or(0)),
+            max: Position::new(rows.max().unwrap_or(0), cols.max().unwrap_or(0)),
+            bends: path
+                .iter()
+                .filter(|&&p| !matches!(maze[p].kind, Vertical | Horizontal))
+                .count(),
+        };
+        MainLoop { path, stats }
+    }
+
+    /// Returns the number of tiles enclosed by the loop.
+    ///
+    /// The loop tiles are the vertices of a polygon, so the shoelace formula gives its area and Pick's theorem the
+    /// number of tiles strictly inside it, using the loop length as the boundary count.
+    pub fn enclosed(&self) -> u64 {
+        let points: Vec<geometry::Point> = self.path.iter().map(|p| (p.col as i64, p.row as i64)).collect();
+        let area = geometry::polygon_area(&points);
+        geometry::interior_points(area, self.path.len() as i64) as u64
     }
 }
 
 impl Pipe {
-    /// Parses a pipe from a character.
-    fn from_char(c: char) -> Pipe {
+    /// Parses a pipe from a character, returning `None` if it does not represent any tile.
+    fn from_char(c: char) -> Option<Pipe> {
         let kind = match c {
             '|' => Vertical,
             '-' => Horizontal,
@@ -96,13 +251,13 @@ impl Pipe {
             'F' => SouthEastBend,
             '.' => Ground,
             'S' => Start,
-            _ => panic!("Invalid Pipe"),
+            _ => return None,
         };
 
-        Pipe {
+        Some(Pipe {
             kind,
             is_main_path: false,
-        }
+        })
     }
 
     /// Returns the next direction the current pipe leads to.
@@ -120,104 +275,99 @@ impl Pipe {
             (SouthWestBend, East) => Some(South),
             (SouthEastBend, North) => Some(East),
             (SouthEastBend, West) => Some(South),
-            (Start, _) => Some(dir),
             (_, _) => None,
         }
     }
 
-    /// Sets the attribute `is_main_path` as true.
-    fn set_main_path(&mut self) {
-        self.is_main_path = true;
-    }
-}
-
-impl Grid {
-    /// Returns a position from the index of the inner `Vec`.
-    fn pos(&self, idx: usize) -> Position {
-        Position {
-            row: idx / self.n_cols,
-            col: idx % self.n_rows,
+    /// Returns the character representing the pipe in the input.
+    fn symbol(&self) -> char {
+        match self.kind {
+            Vertical => '|',
+            Horizontal => '-',
+            NorthEastBend => 'L',
+            NorthWestBend => 'J',
+            SouthWestBend => '7',
+            SouthEastBend => 'F',
+            Ground => '.',
+            Start => 'S',
         }
     }
 
-    /// Traverse Grid looking for the starting location. Returns the position of the `start` node and a direction to
-    /// a valid connection.
-    fn find_start(&self) -> (Position, Direction) {
-        let start = self.pos(self.vec.iter().position(|&pipe| pipe.kind == Start).unwrap());
-
-        let north = if start.row > 0 {
-            Some(Position::new(start.row - 1, start.col))
-        } else {
-            None
-        };
-
-        let south = if start.row < self.n_rows - 1 {
-            Some(Position::new(start.row + 1, start.col))
-        } else {
-            None
-        };
-
-        // Checks if any pipe in one of the four directions is connected to `start`.
-        // If not connected North or South, it must be connected East and West, defining default return as East in this
-        // case.
-        let dir = if north.is_some() && self.get(&north.unwrap()).direct_to(North).is_some() {
-            North
-        } else if south.is_some() && self.get(&south.unwrap()).direct_to(South).is_some() {
-            South
-        } else {
-            East
-        };
-
-        (start, dir)
+    /// Returns the box drawing character representing the pipe.
+    fn glyph(&self) -> char {
+        match self.kind {
+            Vertical => '│',
+            Horizontal => '─',
+            NorthEastBend => '└',
+            NorthWestBend => '┘',
+            SouthWestBend => '┐',
+            SouthEastBend => '┌',
+            Ground => '.',
+            Start => 'S',
+        }
     }
 
-    /// Returns a element of the grid from a given position.
-    fn get(&self, pos: &Position) -> Pipe {
-        let idx = pos.row * self.n_cols + pos.col;
-        self.vec[idx]
+    /// Sets the attribute `is_main_path` as true.
+    fn set_main_path(&mut self) {
+        self.is_main_path = true;
     }
+}
 
-    /// Returns a mutable reference to a element of the grid from a given position.
-    fn get_mut(&mut self, pos: &Position) -> &mut Pipe {
-        let idx = pos.row * self.n_cols + pos.col;
-        &mut self.vec[idx]
+impl PipeKind {
+    /// Every kind of pipe.
+    const PIPES: [PipeKind; 6] = [
+        NorthEastBend,
+        Vertical,
+        NorthWestBend,
+        SouthEastBend,
+        Horizontal,
+        SouthWestBend,
+    ];
+
+    /// Returns the two directions connected by a pipe, sorted, or `None` for the ground and the start.
+    fn connections(self) -> Option<[Direction; 2]> {
+        match self {
+            Vertical => Some([North, South]),
+            Horizontal => Some([East, West]),
+            NorthEastBend => Some([North, East]),
+            NorthWestBend => Some([North, West]),
+            SouthWestBend => Some([South, West]),
+            SouthEastBend => Some([East, South]),
+            Ground | Start => None,
+        }
     }
 
-    /// Returns the next position and flow direction.
-    fn walk(&mut self, pos: Position, dir: Direction) -> (Position, Direction) {
-        // No need to consider overflow, path is in a loop
-        let new_pos = match dir {
-            North => Position::new(pos.row - 1, pos.col),
-            South => Position::new(pos.row + 1, pos.col),
-            East => Position::new(pos.row, pos.col + 1),
-            West => Position::new(pos.row, pos.col - 1),
-        };
-
-        let pipe = self.get_mut(&new_pos);
-        pipe.set_main_path();
-        let new_dir = pipe.direct_to(dir).unwrap();
-
-        (new_pos, new_dir)
+    /// Returns the pipe connecting two different directions.
+    fn connecting(a: Direction, b: Direction) -> PipeKind {
+        let dirs = if a < b { [a, b] } else { [b, a] };
+        PipeKind::PIPES
+            .into_iter()
+            .find(|pipe| pipe.connections() == Some(dirs))
+            .unwrap()
     }
 }
 
-impl FromStr for Grid {
-    type Err = Box<dyn error::Error>;
+impl FromStr for Grid<Pipe> {
+    type Err = AocError;
 
     /// Generates a Grid of Pipes from a String.
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let n_cols = s.split_once('\n').unwrap().0.len();
-        let n_rows = s.len() / n_cols; // number of rows; +1 accounts for '\n'
+        for (i, line) in s.lines().enumerate() {
+            if let Some((j, c)) = line.char_indices().find(|&(_, c)| Pipe::from_char(c).is_none()) {
+                let err = AocError::parse_at(line, &line[j..], format!("Invalid pipe `{c}`."));
+                return Err(err.at_line(i + 1).in_day(10));
+            }
+        }
 
-        // extends borders of the grid
-        let mut grid: Vec<Pipe> = Vec::with_capacity(n_cols * n_rows);
-        grid.extend(s.chars().filter(|c| !c.is_ascii_whitespace()).map(Pipe::from_char));
+        // every character is a valid pipe, and hence a single byte
+        Grid::from_str_with(s, |b| Pipe::from_char(b as char).unwrap()).map_err(|e| e.in_day(10))
+    }
+}
 
-        Ok(Grid {
-            vec: grid,
-            n_cols,
-            n_rows,
-        })
+/// Formats the maze like the input, one line per row.
+impl fmt::Display for Grid<Pipe> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.render(Pipe::symbol))
     }
 }
 
@@ -228,17 +378,220 @@ impl FromStr for Grid {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{aoc_test, geometry, testing::corrupt};
+    use proptest::{prelude::*, sample::Index};
 
-    #[test]
-    fn tests() {
-        let mut input: Grid = "..F7.
+    const SAMPLE_PT1: &str = "..F7.
 .FJ|.
 SJ.L7
 |F--J
-LJ..."
-            .parse()
-            .unwrap();
+LJ...";
+
+    const SAMPLE_PT2: &str = "...........
+.S-------7.
+.|F-----7|.
+.||.....||.
+.||.....||.
+.|L-7.F-J|.
+.|..|.|..|.
+.L--J.L--J.
+...........";
+
+    /// Same loop as `SAMPLE_PT2`, with no gap between the inner pipes: the tiles between them are still outside.
+    const SAMPLE_PT2_SQUEEZED: &str = "..........
+.S------7.
+.|F----7|.
+.||....||.
+.||....||.
+.|L-7F-J|.
+.|..||..|.
+.L--JL--J.
+..........";
+
+    const SAMPLE_PT2_LARGER: &str = ".F----7F7F7F7F-7....
+.|F--7||||||||FJ....
+.||.FJ||||||||L7....
+FJL7L7LJLJ||LJ.L-7..
+L--J.L7...LJS7F-7L7.
+....F-J..F7FJ|L7L7L7
+....L7.F7||L7|.L7L7|
+.....|FJLJ|FJ|F7|.LJ
+....FJL-7.||.||||...
+....L---J.LJ.LJLJ...";
+
+    /// Junk pipes not connected to the loop count as enclosed tiles too.
+    const SAMPLE_PT2_JUNK: &str = "FF7FSF7F7F7F7F7F---7
+L|LJ||||||||||||F--J
+FL-7LJLJ||||||LJL-77
+F--JF--7||LJLJ7F7FJ-
+L---JF-JLJ.||-FJLJJ7
+|F|F-JF---7F7-L7L|7|
+|FFJF7L7F-JF7|JL---7
+7-L-JL7||F7|L7F-7F7|
+L.L7LFJ|||||FJL7||LJ
+L7JLJL-JLJLJL--JLJ.L";
+
+    aoc_test! { sample: SAMPLE_PT1, pt1_sample: solve_part1 => 8 }
+    aoc_test! { sample: SAMPLE_PT2, pt2_sample: solve_part2 => 4 }
+    aoc_test! { sample: SAMPLE_PT2_SQUEEZED, pt2_squeezed: solve_part2 => 4 }
+    aoc_test! { sample: SAMPLE_PT2_LARGER, pt2_larger: solve_part2 => 8 }
+    aoc_test! { sample: SAMPLE_PT2_JUNK, pt2_junk: solve_part2 => 10 }
+
+    #[test]
+    fn test_line_endings() {
+        let crlf = SAMPLE_PT2_LARGER.replace('\n', "\r\n") + "\r\n";
+        assert_eq!(solve_part1(&crlf).unwrap(), solve_part1(SAMPLE_PT2_LARGER).unwrap());
+        assert_eq!(solve_part2(&crlf).unwrap(), 8);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let err = "..F7.\n.FJ|.\nSJ?L7".parse::<Grid<Pipe>>().err().unwrap();
+        assert_eq!(err.to_string(), "Day 10, line 3, column 3: Invalid pipe `?`.");
+    }
+
+    #[test]
+    fn test_main_loop() {
+        let sample = main_loop(SAMPLE_PT1).unwrap();
+        assert_eq!(
+            sample.path[..3],
+            [Position::new(2, 0), Position::new(2, 1), Position::new(1, 1)]
+        );
+        assert_eq!(
+            sample.stats,
+            LoopStats {
+                perimeter: 16,
+                min: Position::new(0, 0),
+                max: Position::new(4, 4),
+                bends: 12,
+            }
+        );
+        assert_eq!(sample.enclosed(), 1);
+
+        let stats = main_loop("......\n.F--7.\n.S..|.\n.L--J.").unwrap().stats;
+        assert_eq!(
+            (stats.min, stats.max, stats.bends),
+            (Position::new(1, 1), Position::new(3, 4), 4)
+        );
+    }
+
+    #[test]
+    fn test_non_square() {
+        // a wide loop and a tall one, with the start away from the first row and column
+        let wide = "......\n.F--7.\n.S..|.\n.L--J.";
+        let tall = "....\n.FS.\n.|L7\n.|.|\n.L-J\n....";
+        for (input, dims, start, len, enclosed) in [
+            (wide, (4, 6), Position::new(2, 1), 10, 2),
+            (tall, (6, 4), Position::new(1, 2), 10, 1),
+        ] {
+            let mut maze: Grid<Pipe> = input.parse().unwrap();
+            assert_eq!((maze.n_rows(), maze.n_cols()), dims);
+
+            let main_loop = find_main_loop(&mut maze).unwrap();
+            assert_eq!(main_loop[0], start, "{input}");
+            assert_eq!(main_loop.len(), len, "{input}");
+            assert!(main_loop
+                .iter()
+                .all(|&pos| maze.contains(pos) && maze[pos].is_main_path));
+            assert_eq!(count_enclosed(&maze), enclosed, "{input}");
+            assert_eq!(MainLoop::new(&maze, main_loop).enclosed(), enclosed as u64, "{input}");
+        }
 
-        assert_eq!(traverse_loop(&mut input), 8);
+        let err = "F7.\nSJ".parse::<Grid<Pipe>>().err().unwrap();
+        assert_eq!(err.to_string(), "Day 10, line 2: Found 2 columns, expected 3.");
+    }
+
+    #[test]
+    fn test_ray_casting() {
+        let mut input: Grid<Pipe> = SAMPLE_PT2.parse().unwrap();
+        let main_loop = find_main_loop(&mut input).unwrap();
+
+        // cross-check with ray casting, using only the bends of the loop as vertices
+        let vertices: Vec<geometry::Point> = main_loop
+            .iter()
+            .filter(|&&p| !matches!(input[p].kind, Vertical | Horizontal))
+            .map(|p| (p.col as i64, p.row as i64))
+            .collect();
+        assert_eq!(geometry::points_inside(&vertices), [(2, 6), (3, 6), (7, 6), (8, 6)]);
+    }
+
+    #[test]
+    fn test_resolve_start() {
+        // the start of the last sample is a `7`, which does not cross the scanline
+        let mut maze: Grid<Pipe> = SAMPLE_PT2_JUNK.parse().unwrap();
+        let start = resolve_start(&mut maze).unwrap();
+        assert_eq!(start, Position::new(0, 4));
+        assert!(maze[start].kind == SouthWestBend);
+
+        // every shape, with the start at each tile of a small loop
+        let shapes = [
+            ("S-7\n|.|\nL-J", SouthEastBend),
+            ("F-S\n|.|\nL-J", SouthWestBend),
+            ("F-7\n|.|\nS-J", NorthEastBend),
+            ("F-7\n|.|\nL-S", NorthWestBend),
+            ("F-7\nS.|\nL-J", Vertical),
+            ("FS7\n|.|\nL-J", Horizontal),
+        ];
+        for (input, kind) in shapes {
+            let mut maze: Grid<Pipe> = input.parse().unwrap();
+            let start = resolve_start(&mut maze).unwrap();
+            assert!(maze[start].kind == kind, "{input}");
+            assert_eq!(find_main_loop(&mut input.parse().unwrap()).unwrap().len(), 8);
+        }
+
+        // the start must connect to exactly two pipes
+        for (input, n) in [("S.\n..", 0), (".|.\n-S-\n...", 3)] {
+            let err = resolve_start(&mut input.parse().unwrap()).err().unwrap();
+            assert_eq!(
+                err.to_string(),
+                format!("Invalid input: the start connects to {n} pipes instead of 2")
+            );
+        }
+
+        // and lead back to itself, without leaving the maze
+        for (input, pos) in [
+            ("S-.\n|..\nL-J", "line 1, column 2"),
+            ("S-7\n|.|\nL--", "line 2, column 3"),
+        ] {
+            let err = solve_part1(input).unwrap_err();
+            assert_eq!(err.to_string(), format!("Invalid input: the loop breaks after {pos}"));
+        }
+
+        // the scanline agrees with shoelace and Pick's theorem
+        for sample in [SAMPLE_PT2, SAMPLE_PT2_SQUEEZED, SAMPLE_PT2_LARGER, SAMPLE_PT2_JUNK] {
+            let mut maze: Grid<Pipe> = sample.parse().unwrap();
+            let main_loop = find_main_loop(&mut maze).unwrap();
+            assert_eq!(count_enclosed(&maze) as u64, MainLoop::new(&maze, main_loop).enclosed());
+        }
+    }
+
+    /// Mazes of any tiles, with up to 6 rows and 6 columns.
+    fn maze() -> impl Strategy<Value = Grid<Pipe>> {
+        let symbols = ['|', '-', 'L', 'J', '7', 'F', '.', 'S'];
+        let pipe = prop::sample::select(symbols.to_vec()).prop_map(|c| Pipe::from_char(c).unwrap());
+        (1..=6usize, 1..=6usize).prop_flat_map(move |(n_rows, n_cols)| {
+            prop::collection::vec(pipe.clone(), n_rows * n_cols).prop_map(move |pipes| Grid::new(pipes, n_rows, n_cols))
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn test_maze_round_trip(
+            maze in maze(),
+            index in any::<Index>(),
+            c in prop::sample::select(&['x', '?', 'é'][..]),
+        ) {
+            let text = maze.to_string();
+            prop_assert_eq!(text.parse::<Grid<Pipe>>().unwrap(), maze, "{}", text);
+
+            let corrupted = corrupt(&text, index, c);
+            prop_assert!(corrupted.parse::<Grid<Pipe>>().is_err(), "{}", corrupted);
+        }
+
+        // arbitrary mazes fail without panicking
+        #[test]
+        fn test_maze_garbage(maze in "[|S.\n\ré]{0,20}") {
+            let _ = maze.parse::<Grid<Pipe>>();
+        }
     }
 }
@@ -0,0 +1,127 @@
+//! Day 11: Cosmic Expansion
+//!
+//! Link: https://adventofcode.com/2023/day/11
+//!
+//! # Problem
+//!
+//! An image of the sky marks galaxies with '#'. Rows and columns without any galaxy are actually bigger than they look
+//! because of the expansion of the universe.
+//!
+//! 1. Each empty row and column is twice as big, find the sum of the shortest distances between all pairs of galaxies;
+//!
+//! 2. Each empty row and column is one million times as big, find the same sum.
+//!
+//! # Solution
+//!
+//! The shortest distance is the Manhattan distance, so rows and columns are handled independently. For each axis, a
+//! prefix sum of the empty lines gives the expanded coordinate of every galaxy without building the expanded image.
+//! The sum of the distances between all pairs of sorted coordinates is then computed in a single pass: the `i`-th
+//! coordinate is `i` times the minuend and contributes `x_i * i - (x_0 + ... + x_{i-1})`.
+
+use crate::{
+    answer::Answer,
+    error::AocError,
+    grid::{Grid, Position},
+};
+
+pub fn run(input: &str) -> Result<[Answer; 2], AocError> {
+    Ok([solve_part1(input)?.into(), solve_part2(input)?.into()])
+}
+
+/// Returns the sum of the distances between all pairs of galaxies, with empty rows and columns doubled.
+///
+/// # Errors
+///
+/// Fails if the lines of the input have different lengths, or the sum overflows.
+pub fn solve_part1(input: &str) -> Result<u64, AocError> {
+    sum_distances(&parse_input(input)?, 2).ok_or_else(AocError::overflow)
+}
+
+/// Returns the sum of the distances between all pairs of galaxies, with empty rows and columns a million times bigger.
+///
+/// # Errors
+///
+/// Fails if the lines of the input have different lengths, or the sum overflows.
+pub fn solve_part2(input: &str) -> Result<u64, AocError> {
+    sum_distances(&parse_input(input)?, 1_000_000).ok_or_else(AocError::overflow)
+}
+
+fn parse_input(input: &str) -> Result<Grid<u8>, AocError> {
+    input.parse::<Grid<u8>>().map_err(|e| e.in_day(11))
+}
+
+//----------
+// Helper methods
+//----------
+
+/// Returns the sum of the distances between all pairs of galaxies, where each empty row and column is replaced by
+/// `factor` of them, or `None` if it overflows.
+fn sum_distances(image: &Grid<u8>, factor: u64) -> Option<u64> {
+    let galaxies: Vec<Position> = image.iter().filter(|(_, &b)| b == b'#').map(|(pos, _)| pos).collect();
+
+    let rows: Vec<usize> = galaxies.iter().map(|p| p.row).collect();
+    let cols: Vec<usize> = galaxies.iter().map(|p| p.col).collect();
+
+    sum_axis_distances(&rows, image.n_rows(), factor)?.checked_add(sum_axis_distances(&cols, image.n_cols(), factor)?)
+}
+
+/// Returns the sum of the distances between all pairs of `coords`, which lie in `0..len`, after expanding the
+/// coordinates without any of them `factor` times, or `None` if it overflows.
+fn sum_axis_distances(coords: &[usize], len: usize, factor: u64) -> Option<u64> {
+    let mut occupied = vec![false; len];
+    coords.iter().for_each(|&c| occupied[c] = true);
+
+    // expanded[c] is the coordinate of line `c` after the expansion
+    let mut expanded = Vec::with_capacity(len);
+    let mut acc: u64 = 0;
+    for is_occupied in occupied {
+        expanded.push(acc);
+        acc = acc.checked_add(if is_occupied { 1 } else { factor })?;
+    }
+
+    let mut sorted: Vec<u64> = coords.iter().map(|&c| expanded[c]).collect();
+    sorted.sort_unstable();
+
+    // the coordinates are sorted, so `x * i` is never below the sum of the previous ones
+    let mut prefix: u64 = 0;
+    sorted.iter().enumerate().try_fold(0u64, |sum, (i, &x)| {
+        let distances = x.checked_mul(i as u64)? - prefix;
+        prefix = prefix.checked_add(x)?;
+        sum.checked_add(distances)
+    })
+}
+
+//----------
+// Tests
+//----------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aoc_test;
+
+    const SAMPLE: &str = "...#......
+.......#..
+#.........
+..........
+......#...
+.#........
+.........#
+..........
+.......#..
+#...#.....";
+
+    aoc_test! {
+        sample: SAMPLE,
+        pt1_sample: solve_part1 => 374,
+        factor_10: |s| parse_input(s).map(|image| sum_distances(&image, 10)) => Some(1030),
+        factor_100: |s| parse_input(s).map(|image| sum_distances(&image, 100)) => Some(8410),
+    }
+
+    #[test]
+    fn test_overflow() {
+        let image = parse_input(SAMPLE).unwrap();
+        assert_eq!(sum_distances(&image, u64::MAX / 4), None);
+        assert_eq!(sum_distances(&image, u64::MAX), None);
+    }
+}
@@ -0,0 +1,237 @@
+//! Day 12: Hot Springs
+//!
+//! Link: https://adventofcode.com/2023/day/12
+//!
+//! # Problem
+//!
+//! Each line of the input is a row of springs, operational ('.'), damaged ('#') or unknown ('?'), followed by the sizes
+//! of the contiguous groups of damaged springs in the row.
+//!
+//! 1. Find the sum of the number of arrangements of operational and damaged springs matching each row;
+//!
+//! 2. The rows were folded: each row is actually five copies of itself separated by '?', and the group sizes are
+//!    repeated five times. Find the same sum.
+//!
+//! # Solution
+//!
+//! Dynamic programming over the springs. The state after a prefix of the row is the number of groups already closed
+//! and the length of the run of damaged springs currently open, and a table keeps how many arrangements of the prefix
+//! lead to each state. Every spring moves the counts to the next states, considering both possibilities for '?'. The
+//! table has `(groups + 1) * (largest group + 1)` entries, so unfolding the rows only makes the work linear in their
+//! length instead of exponential.
+//!
+//! The rows are independent, so they are counted in parallel.
+
+use crate::{answer::Answer, error::AocError, parse};
+use rayon::prelude::*;
+use std::str::FromStr;
+
+pub fn run(input: &str) -> Result<[Answer; 2], AocError> {
+    Ok([solve_part1(input)?.into(), solve_part2(input)?.into()])
+}
+
+/// Returns the sum of the number of arrangements of each row.
+///
+/// # Errors
+///
+/// Fails if the input is malformed, or if the sum overflows.
+pub fn solve_part1(input: &str) -> Result<u64, AocError> {
+    parse_input(input)?
+        .par_iter()
+        .map(|r| count_arrangements(&r.springs, &r.groups))
+        .try_reduce(|| 0, u64::checked_add)
+        .ok_or_else(AocError::overflow)
+}
+
+/// Returns the sum of the number of arrangements of each row, unfolded five times.
+///
+/// # Errors
+///
+/// Fails if the input is malformed, or if the sum overflows.
+pub fn solve_part2(input: &str) -> Result<u64, AocError> {
+    parse_input(input)?
+        .par_iter()
+        .map(|r| r.unfold(5))
+        .map(|r| count_arrangements(&r.springs, &r.groups))
+        .try_reduce(|| 0, u64::checked_add)
+        .ok_or_else(AocError::overflow)
+}
+
+fn parse_input(input: &str) -> Result<Vec<Record>, AocError> {
+    parse::lines_as(input).map_err(|e| e.in_day(12))
+}
+
+//----------
+// Helper methods
+//----------
+
+/// Returns the number of ways of replacing the unknown springs ('?') of `springs` by operational ('.') or damaged ('#')
+/// ones, so the runs of damaged springs have the sizes in `groups`, in order, or `None` if the number overflows.
+pub fn count_arrangements(springs: &[u8], groups: &[usize]) -> Option<u64> {
+    let max_run = groups.iter().copied().max().unwrap_or(0);
+    if max_run > springs.len() {
+        // no run fits in the row, which also keeps the table as small as the row
+        return Some(0);
+    }
+    let width = max_run + 1;
+    let idx = |group: usize, run: usize| group * width + run;
+
+    // counts[idx(g, r)]: arrangements with `g` groups closed and an open run of `r` damaged springs
+    let mut counts = vec![0u64; (groups.len() + 1) * width];
+    let mut next = counts.clone();
+    counts[idx(0, 0)] = 1;
+
+    for &spring in springs {
+        next.fill(0);
+        for group in 0..=groups.len() {
+            for run in 0..=max_run {
+                let count = counts[idx(group, run)];
+                if count == 0 {
+                    continue;
+                }
+
+                if spring != b'#' {
+                    // operational: closes the open run, which must have the size of its group
+                    if run == 0 {
+                        next[idx(group, 0)] = next[idx(group, 0)].checked_add(count)?;
+                    } else if run == groups[group] {
+                        next[idx(group + 1, 0)] = next[idx(group + 1, 0)].checked_add(count)?;
+                    }
+                }
+
+                if spring != b'.' && group < groups.len() && run < groups[group] {
+                    // damaged: extends the open run, up to the size of its group
+                    next[idx(group, run + 1)] = next[idx(group, run + 1)].checked_add(count)?;
+                }
+            }
+        }
+        std::mem::swap(&mut counts, &mut next);
+    }
+
+    // the row may end with all groups closed, or with the last one still open at its full size
+    let open_last = groups.last().map_or(0, |&last| counts[idx(groups.len() - 1, last)]);
+    counts[idx(groups.len(), 0)].checked_add(open_last)
+}
+
+//----------
+// Structs
+//----------
+
+/// A row of springs and the sizes of its groups of damaged springs.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Record {
+    springs: Vec<u8>,
+    groups: Vec<usize>,
+}
+
+//----------
+// Implementations
+//----------
+
+impl Record {
+    /// Returns `n` copies of the record, with the springs separated by '?'.
+    fn unfold(&self, n: usize) -> Record {
+        let mut springs = Vec::with_capacity(n * (self.springs.len() + 1));
+        for i in 0..n {
+            if i > 0 {
+                springs.push(b'?');
+            }
+            springs.extend_from_slice(&self.springs);
+        }
+
+        Record {
+            springs,
+            groups: self.groups.repeat(n),
+        }
+    }
+}
+
+//----------
+// Traits
+//----------
+
+impl FromStr for Record {
+    type Err = AocError;
+
+    /// Parses a line like "???.### 1,1,3".
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (springs, groups) = s
+            .split_once(' ')
+            .ok_or_else(|| AocError::parse("Expected springs and group sizes separated by a space."))?;
+
+        if let Some(i) = springs.bytes().position(|b| !matches!(b, b'.' | b'#' | b'?')) {
+            let spring = springs[i..].chars().next().unwrap();
+            return Err(AocError::parse_at(
+                s,
+                &springs[i..],
+                format!("Invalid spring `{spring}`."),
+            ));
+        }
+
+        let groups = groups
+            .split(',')
+            .map(|g| g.parse().map_err(|e| AocError::from(e).within(s, g)))
+            .collect::<Result<_, _>>()?;
+
+        Ok(Record {
+            springs: springs.as_bytes().to_vec(),
+            groups,
+        })
+    }
+}
+
+//----------
+// Tests
+//----------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aoc_test;
+
+    const SAMPLE: &str = "???.### 1,1,3
+.??..??...?##. 1,1,3
+?#?#?#?#?#?#?#? 1,3,1,6
+????.#...#... 4,1,1
+????.######..#####. 1,6,5
+?###???????? 3,2,1";
+
+    aoc_test! {
+        sample: SAMPLE,
+        pt1_sample: solve_part1 => 21,
+        pt2_sample: solve_part2 => 525152,
+        pt1_rows: |s| {
+            parse_input(s).map(|rows| {
+                rows.iter().map(|r| count_arrangements(&r.springs, &r.groups)).collect::<Vec<_>>()
+            })
+        } => [Some(1), Some(4), Some(1), Some(1), Some(4), Some(10)],
+    }
+
+    #[test]
+    fn test_count_arrangements() {
+        assert_eq!(count_arrangements(b"", &[]), Some(1));
+        assert_eq!(count_arrangements(b"???", &[]), Some(1));
+        assert_eq!(count_arrangements(b"#", &[]), Some(0));
+        assert_eq!(count_arrangements(b"???", &[1]), Some(3));
+        assert_eq!(count_arrangements(b"##", &[1]), Some(0));
+        assert_eq!(count_arrangements(b"??", &[5]), Some(0));
+    }
+
+    #[test]
+    fn test_overflow() {
+        let springs = "?".repeat(200);
+        assert_eq!(count_arrangements(springs.as_bytes(), &[1; 50]), None);
+
+        let row = format!("{springs} {}", ["1"; 50].join(","));
+        assert!(solve_part1(&row).is_err());
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let err = "??x# 1,1".parse::<Record>().unwrap_err();
+        assert_eq!(err.to_string(), "Line 1, column 3: Invalid spring `x`.");
+
+        let err = "??.# 1,a".parse::<Record>().unwrap_err();
+        assert!(err.to_string().starts_with("Line 1, column 8:"));
+    }
+}
@@ -0,0 +1,166 @@
+//! Day 13: Point of Incidence
+//!
+//! Link: https://adventofcode.com/2023/day/13
+//!
+//! # Problem
+//!
+//! The input is a list of patterns of ash ('.') and rocks ('#'), separated by empty lines. Each pattern has a line of
+//! reflection, either between two rows or between two columns, where it mirrors itself up to one of its edges.
+//!
+//! 1. Summarize the patterns by adding the number of columns left of each vertical line of reflection and 100 times the
+//!    number of rows above each horizontal one;
+//!
+//! 2. Every pattern has exactly one smudge, a cell that has the wrong type, and fixing it creates a different line of
+//!    reflection. Summarize the patterns using the new lines.
+//!
+//! # Solution
+//!
+//! For every candidate line, count the cells that differ from their mirrored counterparts. A line is a reflection when
+//! the count is 0, and the line fixed by removing the smudge is the one where the count is exactly 1. Columns are
+//! checked by running the same search over the transposed pattern.
+//!
+//! Both parts share this search, only differing by the number of differing cells, and [`summarize_notes`] accepts any
+//! number of them to experiment with.
+
+use crate::{answer::Answer, error::AocError, grid::Grid, parse};
+
+pub fn run(input: &str) -> Result<[Answer; 2], AocError> {
+    Ok([solve_part1(input)?.into(), solve_part2(input)?.into()])
+}
+
+/// Returns the summary of the lines of reflection of the patterns.
+///
+/// # Errors
+///
+/// Fails if the input is malformed or a pattern has no line of reflection.
+pub fn solve_part1(input: &str) -> Result<usize, AocError> {
+    summarize_notes(input, 0)
+}
+
+/// Returns the summary of the lines of reflection of the patterns, after fixing their smudges.
+///
+/// # Errors
+///
+/// Fails if the input is malformed or a pattern has no line of reflection with exactly one smudge.
+pub fn solve_part2(input: &str) -> Result<usize, AocError> {
+    summarize_notes(input, 1)
+}
+
+/// Returns the summary of the lines of reflection of the patterns with exactly `smudges` cells differing from their
+/// reflections.
+///
+/// # Errors
+///
+/// Fails if the input is malformed or a pattern has no such line, naming the pattern.
+pub fn summarize_notes(input: &str, smudges: usize) -> Result<usize, AocError> {
+    parse_input(input)?.iter().enumerate().try_fold(0, |sum, (i, pattern)| {
+        let summary = summarize(pattern, smudges).ok_or_else(|| {
+            AocError::invalid(format!(
+                "pattern {} has no line of reflection with {smudges} smudges",
+                i + 1
+            ))
+        })?;
+        Ok(sum + summary)
+    })
+}
+
+fn parse_input(input: &str) -> Result<Vec<Grid<u8>>, AocError> {
+    parse::blocks(input)
+        .map(|block| block.parse::<Grid<u8>>().map_err(|e| e.in_day(13)))
+        .collect()
+}
+
+//----------
+// Helper methods
+//----------
+
+/// Returns the summary value of the line of reflection of `pattern` with exactly `smudges` differing cells, or `None`
+/// if there is no such line.
+fn summarize(pattern: &Grid<u8>, smudges: usize) -> Option<usize> {
+    if let Some(rows) = horizontal_reflection(pattern, smudges) {
+        return Some(100 * rows);
+    }
+
+    horizontal_reflection(&pattern.transpose(), smudges)
+}
+
+/// Returns the number of rows above the first horizontal line of reflection of `pattern` with exactly `smudges` cells
+/// differing from their reflections.
+fn horizontal_reflection(pattern: &Grid<u8>, smudges: usize) -> Option<usize> {
+    (1..pattern.n_rows()).find(|&line| differences(pattern, line, smudges.saturating_add(1)) == smudges)
+}
+
+/// Returns the number of cells differing from their reflections across the line above row `line`, counting up to
+/// `limit` only.
+fn differences(pattern: &Grid<u8>, line: usize, limit: usize) -> usize {
+    let mut differences = 0;
+    for (above, below) in (0..line).rev().zip(line..pattern.n_rows()) {
+        let row_above = pattern.row(above);
+        differences += row_above.iter().zip(pattern.row(below)).filter(|(a, b)| a != b).count();
+        if differences >= limit {
+            return limit;
+        }
+    }
+    differences
+}
+
+//----------
+// Tests
+//----------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aoc_test;
+
+    const SAMPLE: &str = "#.##..##.
+..#.##.#.
+##......#
+##......#
+..#.##.#.
+..##..##.
+#.##..##.
+
+#...##..#
+#....#..#
+..##..###
+#####.##.
+#####.##.
+..##..###
+#....#..#";
+
+    aoc_test! {
+        sample: SAMPLE,
+        pt1_sample: solve_part1 => 405,
+        pt2_sample: solve_part2 => 400,
+        pt1_patterns: |s| {
+            parse_input(s).map(|patterns| patterns.iter().map(|p| summarize(p, 0)).collect::<Vec<_>>())
+        } => [Some(5), Some(400)],
+        pt2_patterns: |s| {
+            parse_input(s).map(|patterns| patterns.iter().map(|p| summarize(p, 1)).collect::<Vec<_>>())
+        } => [Some(300), Some(100)],
+    }
+
+    #[test]
+    fn test_smudges() {
+        for pattern in parse_input(SAMPLE).unwrap() {
+            let n_rows = pattern.n_rows();
+            for smudges in 0..6 {
+                // the line found is the first one with exactly that many differences
+                let counts: Vec<_> = (1..n_rows)
+                    .map(|line| differences(&pattern, line, usize::MAX))
+                    .collect();
+                let expected = counts.iter().position(|&count| count == smudges).map(|i| i + 1);
+                assert_eq!(horizontal_reflection(&pattern, smudges), expected, "{smudges} smudges");
+            }
+        }
+
+        assert_eq!(summarize_notes(SAMPLE, 0).unwrap(), 405);
+        assert_eq!(summarize_notes(SAMPLE, 1).unwrap(), 400);
+        assert_eq!(
+            summarize_notes(SAMPLE, 100).unwrap_err().to_string(),
+            "Invalid input: pattern 1 has no line of reflection with 100 smudges"
+        );
+        assert!(summarize_notes(SAMPLE, usize::MAX).is_err());
+    }
+}
@@ -0,0 +1,228 @@
+//! Day 14: Parabolic Reflector Dish
+//!
+//! Link: https://adventofcode.com/2023/day/14
+//!
+//! # Problem
+//!
+//! A platform holds rounded rocks ('O'), which roll when the platform is tilted, and cube-shaped rocks ('#'), which
+//! stay in place. The load of a rounded rock is its distance to the south edge, counting its own row.
+//!
+//! 1. Tilt the platform north and find the total load on the north support beams;
+//!
+//! 2. A spin cycle tilts the platform north, west, south and east, in this order. Find the load after 1,000,000,000
+//!    spin cycles.
+//!
+//! # Solution
+//!
+//! Tilting is only implemented to the north: rotating the platform clockwise after each tilt brings the next direction
+//! of the cycle to the north, and four rotations restore the original orientation.
+//!
+//! The platform quickly falls into a loop of states, so for part 2 the spin cycles are only simulated until a state
+//! repeats, and `cycle::fast_forward` reduces the remaining cycles to an index into the loop.
+//!
+//! To debug the loop, `loads_over_cycles` returns the load after every spin cycle until the first repeated state, as
+//! CSV, which `aoc2023 14 --loads <file>` writes to a file.
+
+use crate::{
+    answer::Answer,
+    cycle::{self, Cycle},
+    error::AocError,
+    grid::{Grid, Position},
+};
+use std::{collections::HashMap, fmt::Write};
+
+const ROUNDED: u8 = b'O';
+const EMPTY: u8 = b'.';
+
+/// Number of spin cycles of part 2.
+const SPIN_CYCLES: usize = 1_000_000_000;
+
+pub fn run(input: &str) -> Result<[Answer; 2], AocError> {
+    Ok([solve_part1(input)?.into(), solve_part2(input)?.into()])
+}
+
+/// Returns the load on the north beams after tilting the platform north.
+///
+/// # Errors
+///
+/// Fails if the lines of the input have different lengths.
+pub fn solve_part1(input: &str) -> Result<usize, AocError> {
+    let mut platform = parse_input(input)?;
+    tilt_north(&mut platform);
+    Ok(north_load(&platform))
+}
+
+/// Returns the load on the north beams after 1,000,000,000 spin cycles.
+///
+/// # Errors
+///
+/// Fails if the lines of the input have different lengths.
+pub fn solve_part2(input: &str) -> Result<usize, AocError> {
+    let platform = cycle::fast_forward(parse_input(input)?, spin_cycle, SPIN_CYCLES);
+    Ok(north_load(&platform))
+}
+
+/// Returns the load after every spin cycle, until the first repeated state, as CSV: the number of spin cycles, the
+/// load, and whether the state is part of the loop.
+///
+/// # Errors
+///
+/// Fails if the lines of the input have different lengths.
+pub fn loads_over_cycles(input: &str) -> Result<String, AocError> {
+    let (loads, cycle) = load_series(&parse_input(input)?);
+    Ok(loads_csv(&loads, cycle))
+}
+
+fn parse_input(input: &str) -> Result<Grid<u8>, AocError> {
+    input.parse::<Grid<u8>>().map_err(|e| e.in_day(14))
+}
+
+//----------
+// Helper methods
+//----------
+
+/// Rolls all rounded rocks north, until they hit the edge or another rock.
+fn tilt_north(platform: &mut Grid<u8>) {
+    let n_cols = platform.n_cols();
+    // row where the next rounded rock of each column stops
+    let mut free = vec![0; n_cols];
+
+    for row in 0..platform.n_rows() {
+        for col in 0..n_cols {
+            let pos = Position::new(row, col);
+            match platform[pos] {
+                ROUNDED => {
+                    platform[pos] = EMPTY;
+                    platform[Position::new(free[col], col)] = ROUNDED;
+                    free[col] += 1;
+                }
+                EMPTY => {}
+                _ => free[col] = row + 1,
+            }
+        }
+    }
+}
+
+/// Returns the platform after tilting it north, west, south and east.
+fn spin_cycle(platform: &Grid<u8>) -> Grid<u8> {
+    let mut platform = platform.clone();
+    for _ in 0..4 {
+        tilt_north(&mut platform);
+        platform = platform.rotate_cw();
+    }
+    platform
+}
+
+/// Returns the load of the rounded rocks on the north beams.
+fn north_load(platform: &Grid<u8>) -> usize {
+    platform
+        .iter()
+        .filter(|(_, &b)| b == ROUNDED)
+        .map(|(pos, _)| platform.n_rows() - pos.row)
+        .sum()
+}
+
+/// Returns the load after every spin cycle, starting with the load before any, until a state repeats, along with the
+/// loop of the states. The repeated state is not included, so there are `offset + period` loads.
+fn load_series(platform: &Grid<u8>) -> (Vec<usize>, Cycle) {
+    let mut seen = HashMap::new();
+    let mut loads = Vec::new();
+    let mut platform = platform.clone();
+
+    loop {
+        if let Some(&offset) = seen.get(&platform) {
+            let period = loads.len() - offset;
+            return (loads, Cycle { offset, period });
+        }
+        loads.push(north_load(&platform));
+        let next = spin_cycle(&platform);
+        seen.insert(platform, loads.len() - 1);
+        platform = next;
+    }
+}
+
+/// Writes the loads of `load_series` as CSV, with the number of spin cycles, the load, and whether the state is part
+/// of the loop.
+fn loads_csv(loads: &[usize], cycle: Cycle) -> String {
+    let mut csv = String::from("cycles,load,in_loop\n");
+    for (i, load) in loads.iter().enumerate() {
+        writeln!(csv, "{i},{load},{}", i >= cycle.offset).unwrap();
+    }
+    csv
+}
+
+/// Returns the load after `n` spin cycles, simulating every one of them.
+#[cfg(test)]
+fn spin_naive(platform: &Grid<u8>, n: usize) -> usize {
+    let platform = (0..n).fold(platform.clone(), |platform, _| spin_cycle(&platform));
+    north_load(&platform)
+}
+
+//----------
+// Tests
+//----------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aoc_test;
+
+    const SAMPLE: &str = "O....#....
+O.OO#....#
+.....##...
+OO.#O....O
+.O.....O#.
+O.#..O.#.#
+..O..#O..O
+.......O..
+#....###..
+#OO..#....";
+
+    aoc_test! {
+        sample: SAMPLE,
+        pt1_sample: solve_part1 => 136,
+        pt2_sample: solve_part2 => 64,
+    }
+
+    #[test]
+    fn test_spin_cycle() {
+        let platform = parse_input(SAMPLE).unwrap();
+        let after_one = ".....#....
+....#...O#
+...OO##...
+.OO#......
+.....OOO#.
+.O#...O#.#
+....O#....
+......OOOO
+#...O###..
+#..OO#....";
+        assert_eq!(spin_cycle(&platform), parse_input(after_one).unwrap());
+
+        // the fast forward agrees with the full simulation
+        for n in [0, 1, 2, 3, 10, 25, 100] {
+            let fast = north_load(&cycle::fast_forward(platform.clone(), spin_cycle, n));
+            assert_eq!(fast, spin_naive(&platform, n), "{n} cycles");
+        }
+    }
+
+    #[test]
+    fn test_load_series() {
+        let platform = parse_input(SAMPLE).unwrap();
+        let (loads, cycle) = load_series(&platform);
+        assert_eq!(cycle, Cycle { offset: 3, period: 7 });
+        assert_eq!(loads.len(), cycle.offset + cycle.period);
+        for (n, &load) in loads.iter().enumerate() {
+            assert_eq!(load, spin_naive(&platform, n), "{n} cycles");
+        }
+        // the loop gives the answer of part 2
+        assert_eq!(loads[cycle.extrapolate(SPIN_CYCLES)], 64);
+
+        let csv = loads_csv(&loads, cycle);
+        let lines: Vec<_> = csv.lines().collect();
+        assert_eq!(lines.len(), 1 + loads.len());
+        assert_eq!(lines[0], "cycles,load,in_loop");
+        assert_eq!(lines[1], "0,104,false");
+        assert_eq!(lines[4], format!("3,{},true", loads[3]));
+    }
+}
@@ -0,0 +1,157 @@
+//! Day 15: Lens Library
+//!
+//! Link: https://adventofcode.com/2023/day/15
+//!
+//! # Problem
+//!
+//! The input is an initialization sequence: a comma separated list of steps. Each step either puts a lens with a focal
+//! length into a box (`label=focal`) or takes the lens with a label out of its box (`label-`). The box of a label is
+//! given by the HASH algorithm.
+//!
+//! 1. Find the sum of the HASH of every step;
+//!
+//! 2. Run the sequence and find the focusing power of the lenses: the product of the box number plus one, the slot of
+//!    the lens within its box (starting at 1) and the focal length.
+//!
+//! # Solution
+//!
+//! The HASH and the boxes are implemented by the `holiday_hash` module: a `BucketMap` keeps the lenses of each box in
+//! insertion order, and replacing a lens keeps its slot.
+
+use crate::{
+    answer::Answer,
+    error::AocError,
+    holiday_hash::{self, BucketMap},
+};
+use std::str::FromStr;
+
+pub fn run(input: &str) -> Result<[Answer; 2], AocError> {
+    Ok([solve_part1(input)?.into(), solve_part2(input)?.into()])
+}
+
+/// Returns the sum of the HASH of every step of the sequence.
+pub fn solve_part1(input: &str) -> Result<u32, AocError> {
+    Ok(steps(input)
+        .map(|step| holiday_hash::hash(step.as_bytes()) as u32)
+        .sum())
+}
+
+/// Returns the focusing power of the lenses after running the sequence.
+///
+/// # Errors
+///
+/// Fails if a step is malformed.
+pub fn solve_part2(input: &str) -> Result<usize, AocError> {
+    let mut boxes = BucketMap::new();
+    for step in parse_input(input)? {
+        match step.op {
+            Operation::Insert(focal) => boxes.insert(step.label, focal),
+            Operation::Remove => boxes.remove(step.label),
+        };
+    }
+
+    Ok(focusing_power(&boxes))
+}
+
+fn parse_input(input: &str) -> Result<Vec<Step<'_>>, AocError> {
+    steps(input)
+        .map(|step| Step::parse(step).map_err(|e| e.within(input, step).in_day(15)))
+        .collect()
+}
+
+/// Returns an iterator over the steps of the sequence. Newlines are ignored.
+fn steps(input: &str) -> impl Iterator<Item = &str> {
+    input.split(',').map(|step| step.trim_matches('\n'))
+}
+
+//----------
+// Helper methods
+//----------
+
+/// Returns the sum of the focusing powers of the lenses in `boxes`.
+fn focusing_power(boxes: &BucketMap<u8>) -> usize {
+    boxes
+        .iter()
+        .map(|(b, slot, _, &focal)| (b + 1) * (slot + 1) * focal as usize)
+        .sum()
+}
+
+//----------
+// Structs
+//----------
+
+/// A step of the initialization sequence.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Step<'a> {
+    label: &'a str,
+    op: Operation,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Operation {
+    /// Puts a lens with the given focal length into the box of the label (`=`).
+    Insert(u8),
+    /// Takes the lens with the label out of its box (`-`).
+    Remove,
+}
+
+//----------
+// Implementations
+//----------
+
+impl<'a> Step<'a> {
+    /// Parses a step like "rn=1" or "cm-".
+    fn parse(s: &'a str) -> Result<Step<'a>, AocError> {
+        if let Some(label) = s.strip_suffix('-') {
+            return Ok(Step {
+                label,
+                op: Operation::Remove,
+            });
+        }
+
+        let (label, focal) = s
+            .split_once('=')
+            .ok_or_else(|| AocError::parse(format!("Invalid step `{s}`, expected `=` or `-`.")))?;
+        let focal = u8::from_str(focal).map_err(|e| AocError::from(e).within(s, focal))?;
+
+        Ok(Step {
+            label,
+            op: Operation::Insert(focal),
+        })
+    }
+}
+
+//----------
+// Tests
+//----------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aoc_test;
+
+    const SAMPLE: &str = "rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7\n";
+
+    aoc_test! {
+        sample: SAMPLE,
+        pt1_sample: solve_part1 => 1320,
+        pt2_sample: solve_part2 => 145,
+    }
+
+    #[test]
+    fn test_parse_step() {
+        assert_eq!(
+            Step::parse("rn=1").unwrap(),
+            Step {
+                label: "rn",
+                op: Operation::Insert(1)
+            }
+        );
+        assert_eq!(Step::parse("cm-").unwrap().op, Operation::Remove);
+
+        let err = Step::parse("cm").unwrap_err();
+        assert_eq!(err.to_string(), "Line 1: Invalid step `cm`, expected `=` or `-`.");
+        let err = Step::parse("cm=x").unwrap_err();
+        assert!(err.to_string().starts_with("Line 1, column 4:"));
+    }
+}
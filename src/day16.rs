@@ -0,0 +1,169 @@
+//! Day 16: The Floor Will Be Lava
+//!
+//! Link: https://adventofcode.com/2023/day/16
+//!
+//! # Problem
+//!
+//! A beam of light enters a contraption of empty space ('.'), mirrors ('/' and '\') and splitters ('|' and '-').
+//! Mirrors reflect the beam 90 degrees, and splitters hit on their flat side split it into two beams leaving through
+//! their ends. A tile is energized if at least one beam passes through it.
+//!
+//! 1. The beam enters the top-left corner heading east, find the number of energized tiles;
+//!
+//! 2. The beam may enter from any edge tile, heading away from that edge. Find the largest number of energized tiles.
+//!
+//! # Solution
+//!
+//! The beams are traced with a stack of cursors (position and heading). Beams can loop, so every tile keeps a bit mask
+//! of the directions of the beams that already crossed it, and a beam stops when it repeats one. For part 2 every entry
+//! point is independent, so they are traced in parallel.
+
+use crate::{
+    answer::Answer,
+    error::AocError,
+    grid::{Cursor, Direction, Grid, Position},
+};
+use rayon::prelude::*;
+use Direction::*;
+
+pub fn run(input: &str) -> Result<[Answer; 2], AocError> {
+    Ok([solve_part1(input)?.into(), solve_part2(input)?.into()])
+}
+
+/// Returns the number of energized tiles for a beam entering the top-left corner heading east.
+///
+/// # Errors
+///
+/// Fails if the input is empty, or its lines have different lengths.
+pub fn solve_part1(input: &str) -> Result<usize, AocError> {
+    Ok(energize(&parse_input(input)?, Cursor::new(Position::new(0, 0), East)))
+}
+
+/// Returns the largest number of energized tiles among all entry points of the beam.
+///
+/// # Errors
+///
+/// Fails if the input is empty, or its lines have different lengths.
+pub fn solve_part2(input: &str) -> Result<usize, AocError> {
+    let contraption = parse_input(input)?;
+    Ok(entry_points(&contraption)
+        .par_iter()
+        .map(|&start| energize(&contraption, start))
+        .max()
+        .unwrap_or(0))
+}
+
+/// Parses the contraption, failing if it is empty.
+fn parse_input(input: &str) -> Result<Grid<u8>, AocError> {
+    let contraption = input.parse::<Grid<u8>>().map_err(|e| e.in_day(16))?;
+    if contraption.n_rows() == 0 || contraption.n_cols() == 0 {
+        return Err(AocError::invalid("the contraption is empty"));
+    }
+    Ok(contraption)
+}
+
+//----------
+// Helper methods
+//----------
+
+/// Returns the number of tiles crossed by the beam starting at `start`.
+fn energize(contraption: &Grid<u8>, start: Cursor) -> usize {
+    if !contraption.contains(start.pos) {
+        return 0;
+    }
+
+    // directions of the beams that crossed each tile, one bit per direction
+    let mut seen = Grid::filled(0u8, contraption.n_rows(), contraption.n_cols());
+    let mut beams = vec![start];
+
+    while let Some(beam) = beams.pop() {
+        let bit = 1 << beam.dir as u8;
+        if seen[beam.pos] & bit != 0 {
+            continue;
+        }
+        seen[beam.pos] |= bit;
+
+        let horizontal = matches!(beam.dir, East | West);
+        let outgoing = match (contraption[beam.pos], horizontal) {
+            (b'/', true) | (b'\\', false) => [Some(beam.turn_left()), None],
+            (b'/', false) | (b'\\', true) => [Some(beam.turn_right()), None],
+            (b'|', true) | (b'-', false) => [Some(beam.turn_left()), Some(beam.turn_right())],
+            _ => [Some(beam), None],
+        };
+
+        beams.extend(
+            outgoing
+                .into_iter()
+                .flatten()
+                .filter_map(Cursor::step)
+                .filter(|next| contraption.contains(next.pos)),
+        );
+    }
+
+    seen.iter().filter(|(_, &dirs)| dirs != 0).count()
+}
+
+/// Returns the cursors entering every edge tile of the contraption, heading away from the edge.
+fn entry_points(contraption: &Grid<u8>) -> Vec<Cursor> {
+    let (last_row, last_col) = (contraption.n_rows() - 1, contraption.n_cols() - 1);
+
+    let rows = (0..contraption.n_rows()).flat_map(|row| {
+        [
+            Cursor::new(Position::new(row, 0), East),
+            Cursor::new(Position::new(row, last_col), West),
+        ]
+    });
+    let cols = (0..contraption.n_cols()).flat_map(|col| {
+        [
+            Cursor::new(Position::new(0, col), South),
+            Cursor::new(Position::new(last_row, col), North),
+        ]
+    });
+
+    rows.chain(cols).collect()
+}
+
+//----------
+// Tests
+//----------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aoc_test;
+
+    const SAMPLE: &str = r".|...\....
+|.-.\.....
+.....|-...
+........|.
+..........
+.........\
+..../.\\..
+.-.-/..|..
+.|....-|.\
+..//.|....";
+
+    aoc_test! {
+        sample: SAMPLE,
+        pt1_sample: solve_part1 => 46,
+        pt2_sample: solve_part2 => 51,
+    }
+
+    #[test]
+    fn test_loops() {
+        // the beam enters a loop of mirrors and is split back into it
+        let contraption = parse_input("/.\\\n|.-\n\\./").unwrap();
+        assert_eq!(energize(&contraption, Cursor::new(Position::new(0, 1), East)), 9);
+    }
+
+    #[test]
+    fn test_empty() {
+        for input in ["", "\n\n"] {
+            assert_eq!(
+                solve_part1(input).unwrap_err().to_string(),
+                "Invalid input: the contraption is empty"
+            );
+            assert!(solve_part2(input).is_err());
+        }
+    }
+}
@@ -0,0 +1,212 @@
+//! Day 17: Clumsy Crucible
+//!
+//! Link: https://adventofcode.com/2023/day/17
+//!
+//! # Problem
+//!
+//! A map of city blocks gives the heat lost (a single digit) when a crucible enters each block. The crucible starts at
+//! the top-left block and must reach the bottom-right one, turning 90 degrees or going straight at each block, but
+//! never reversing.
+//!
+//! 1. The crucible can move at most three blocks in a straight line. Find the least heat loss;
+//!
+//! 2. An ultra crucible must move at least four blocks in a straight line before turning or stopping, and at most ten.
+//!    Find the least heat loss.
+//!
+//! # Solution
+//!
+//! Dijkstra's algorithm, from the shared `graph` module, over the states (position, heading, length of the current
+//! straight run). Both crucibles only differ by the limits of the run.
+//!
+//! The heat lost on a block is at most 9, so by default the queue is a ring of buckets, one per cost, instead of a
+//! binary heap. [`run_with`] takes the queue to use, selected with `aoc2023 17 --strategy heap` (or `buckets`), to
+//! benchmark them.
+
+use crate::{
+    answer::Answer,
+    error::AocError,
+    graph::{self, Queue},
+    grid::{Direction, Grid, Position},
+    parse,
+};
+
+/// Highest heat loss of a block.
+const MAX_HEAT_LOSS: u64 = 9;
+
+pub fn run(input: &str) -> Result<[Answer; 2], AocError> {
+    run_with(input, Queue::Buckets)
+}
+
+/// Solves both parts, searching with the given `queue`.
+///
+/// # Errors
+///
+/// Fails if the input is not a rectangular grid of digits, or the crucible can not reach the factory.
+pub fn run_with(input: &str, queue: Queue) -> Result<[Answer; 2], AocError> {
+    let city = parse_input(input)?;
+    Ok([
+        least_heat_loss(&city, 1, 3, queue).ok_or_else(no_path)?.into(),
+        least_heat_loss(&city, 4, 10, queue).ok_or_else(no_path)?.into(),
+    ])
+}
+
+/// Returns the least heat loss of a crucible moving at most three blocks in a straight line.
+///
+/// # Errors
+///
+/// Fails if the input is not a rectangular grid of digits, or the crucible can not reach the factory.
+pub fn solve_part1(input: &str) -> Result<u64, AocError> {
+    least_heat_loss(&parse_input(input)?, 1, 3, Queue::Buckets).ok_or_else(no_path)
+}
+
+/// Returns the least heat loss of an ultra crucible moving between four and ten blocks in a straight line.
+///
+/// # Errors
+///
+/// Fails if the input is not a rectangular grid of digits, or the crucible can not reach the factory.
+pub fn solve_part2(input: &str) -> Result<u64, AocError> {
+    least_heat_loss(&parse_input(input)?, 4, 10, Queue::Buckets).ok_or_else(no_path)
+}
+
+/// Parses the city map, failing if it is empty.
+fn parse_input(input: &str) -> Result<Grid<u8>, AocError> {
+    let city = parse::digit_grid(input).map_err(|e| e.in_day(17))?;
+    if city.n_rows() == 0 || city.n_cols() == 0 {
+        return Err(AocError::invalid("the city map is empty"));
+    }
+    Ok(city)
+}
+
+/// Returns the error of a crucible that can not reach the factory.
+fn no_path() -> AocError {
+    AocError::invalid("there is no path to the factory")
+}
+
+//----------
+// Helper methods
+//----------
+
+/// Returns the least heat loss from the top-left to the bottom-right block, for a crucible that moves between
+/// `min_run` and `max_run` blocks in a straight line, searching with `queue`. Returns `None` if the bottom-right block
+/// can not be reached.
+fn least_heat_loss(city: &Grid<u8>, min_run: u8, max_run: u8, queue: Queue) -> Option<u64> {
+    let end = Position::new(city.n_rows() - 1, city.n_cols() - 1);
+    if end == Position::new(0, 0) {
+        // already at the factory, without moving
+        return Some(0);
+    }
+    let start = Crucible {
+        pos: Position::new(0, 0),
+        dir: Direction::East,
+        run: 0,
+    };
+
+    let moves = |c: &Crucible| c.moves(city, min_run, max_run);
+    let is_goal = |c: &Crucible| c.pos == end && c.run >= min_run;
+    match queue {
+        Queue::Heap => graph::dijkstra(start, moves, is_goal),
+        Queue::Buckets => graph::dijkstra_buckets(start, moves, is_goal, MAX_HEAT_LOSS),
+    }
+}
+
+//----------
+// Structs
+//----------
+
+/// State of the crucible: its position, heading and number of blocks moved straight in that heading.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+struct Crucible {
+    pos: Position,
+    dir: Direction,
+    run: u8,
+}
+
+//----------
+// Implementations
+//----------
+
+impl Crucible {
+    /// Returns the states reachable with one move, and the heat lost entering their blocks.
+    ///
+    /// At the start (`run` 0) the crucible can head in any direction.
+    fn moves(&self, city: &Grid<u8>, min_run: u8, max_run: u8) -> Vec<(Crucible, u64)> {
+        let can_turn = self.run == 0 || self.run >= min_run;
+
+        [self.dir, self.dir.turn_left(), self.dir.turn_right()]
+            .into_iter()
+            .filter_map(|dir| {
+                let straight = dir == self.dir;
+                if (straight && self.run == max_run) || (!straight && !can_turn) {
+                    return None;
+                }
+
+                let pos = self.pos.step(dir).filter(|&p| city.contains(p))?;
+                let run = if straight { self.run + 1 } else { 1 };
+                Some((Crucible { pos, dir, run }, city[pos] as u64))
+            })
+            .collect()
+    }
+}
+
+//----------
+// Tests
+//----------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aoc_test;
+
+    const SAMPLE: &str = "2413432311323
+3215453535623
+3255245654254
+3446585845452
+4546657867536
+1438598798454
+4457876987766
+3637877979653
+4654967986887
+4564679986453
+1224686865563
+2546548887735
+4322674655533";
+
+    const SAMPLE_ULTRA: &str = "111111111111
+999999999991
+999999999991
+999999999991
+999999999991";
+
+    aoc_test! {
+        sample: SAMPLE,
+        pt1_sample: solve_part1 => 102,
+        pt2_sample: solve_part2 => 94,
+    }
+
+    aoc_test! {
+        sample: SAMPLE_ULTRA,
+        pt2_ultra: solve_part2 => 71,
+    }
+
+    #[test]
+    fn test_queues() {
+        for sample in [SAMPLE, SAMPLE_ULTRA] {
+            let city = parse_input(sample).unwrap();
+            for (min_run, max_run) in [(1, 3), (4, 10)] {
+                assert_eq!(
+                    least_heat_loss(&city, min_run, max_run, Queue::Heap),
+                    least_heat_loss(&city, min_run, max_run, Queue::Buckets)
+                );
+            }
+        }
+        assert_eq!(run_with(SAMPLE, "heap".parse().unwrap()).unwrap(), run(SAMPLE).unwrap());
+        assert!("stack".parse::<Queue>().is_err());
+    }
+
+    #[test]
+    fn test_single_block() {
+        assert_eq!(solve_part1("5\n").unwrap(), 0);
+        assert_eq!(solve_part2("5\n").unwrap(), 0);
+        assert!(solve_part2("11\n11\n").is_err());
+    }
+}
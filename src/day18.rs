@@ -0,0 +1,219 @@
+//! Day 18: Lavaduct Lagoon
+//!
+//! Link: https://adventofcode.com/2023/day/18
+//!
+//! # Problem
+//!
+//! A dig plan lists the moves of a digger, one cubic meter wide, as a direction, a number of meters and a color code
+//! (`R 6 (#70c710)`). The digger digs a closed trench, and then the interior of the trench is dug out too.
+//!
+//! 1. Find the number of cubic meters of lava the lagoon can hold;
+//!
+//! 2. The color codes are the real instructions: the first five hexadecimal digits are the number of meters and the
+//!    last one is the direction (0 is R, 1 is D, 2 is L and 3 is U). Find the volume of the much bigger lagoon.
+//!
+//! # Solution
+//!
+//! The trench is a rectilinear polygon on the lattice of the cube centers. The shoelace formula gives its area and
+//! Pick's theorem the number of cubes strictly inside it, which added to the cubes of the trench itself give the
+//! volume. No grid is ever built, so the size of the part 2 lagoon does not matter.
+
+use crate::{
+    answer::Answer,
+    combinator::{complete, delimited, integer, pair, tag, terminated, word},
+    error::AocError,
+    geometry::{self, Point},
+    grid::Direction,
+};
+
+pub fn run(input: &str) -> Result<[Answer; 2], AocError> {
+    Ok([solve_part1(input)?.into(), solve_part2(input)?.into()])
+}
+
+/// Returns the volume of the lagoon dug by following the directions and lengths of the plan.
+///
+/// # Errors
+///
+/// Fails if the input is malformed, the trench does not close, or the lagoon is too large.
+pub fn solve_part1(input: &str) -> Result<i64, AocError> {
+    let moves: Vec<_> = parse_input(input)?.iter().map(|i| (i.dir, i.len)).collect();
+    lagoon_volume(&moves)
+}
+
+/// Returns the volume of the lagoon dug by following the instructions hidden in the color codes.
+///
+/// # Errors
+///
+/// Fails if the input is malformed, the trench does not close, or the lagoon is too large.
+pub fn solve_part2(input: &str) -> Result<i64, AocError> {
+    let moves: Vec<_> = parse_input(input)?.iter().map(|i| i.color_move).collect();
+    lagoon_volume(&moves)
+}
+
+fn parse_input(input: &str) -> Result<Vec<Instruction>, AocError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| Instruction::parse(line).map_err(|e| e.at_line(i + 1).in_day(18)))
+        .collect()
+}
+
+//----------
+// Helper methods
+//----------
+
+/// Returns the number of cubes of the trench dug by `moves` plus the cubes it encloses.
+///
+/// Fails if the trench does not return to its start, or the lagoon is too large for an `i64`.
+fn lagoon_volume(moves: &[(Direction, i64)]) -> Result<i64, AocError> {
+    let mut vertices: Vec<Point> = Vec::with_capacity(moves.len());
+    let (mut x, mut y) = (0i64, 0i64);
+    for &(dir, len) in moves {
+        match dir {
+            Direction::North => y = y.checked_sub(len).ok_or_else(AocError::overflow)?,
+            Direction::South => y = y.checked_add(len).ok_or_else(AocError::overflow)?,
+            Direction::East => x = x.checked_add(len).ok_or_else(AocError::overflow)?,
+            Direction::West => x = x.checked_sub(len).ok_or_else(AocError::overflow)?,
+        }
+        vertices.push((x, y));
+    }
+    if (x, y) != (0, 0) {
+        return Err(AocError::invalid("the trench does not close"));
+    }
+
+    let volume = || {
+        let boundary = geometry::checked_boundary_points(&vertices)?;
+        let interior = geometry::interior_points(geometry::checked_polygon_area(&vertices)?, boundary);
+        interior.checked_add(boundary)
+    };
+    volume().ok_or_else(AocError::overflow)
+}
+
+/// Converts a direction letter of the dig plan.
+fn direction(s: &str) -> Option<Direction> {
+    match s {
+        "U" => Some(Direction::North),
+        "D" => Some(Direction::South),
+        "R" => Some(Direction::East),
+        "L" => Some(Direction::West),
+        _ => None,
+    }
+}
+
+//----------
+// Structs
+//----------
+
+/// A line of the dig plan.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Instruction {
+    dir: Direction,
+    len: i64,
+    /// Direction and length encoded by the color code.
+    color_move: (Direction, i64),
+}
+
+//----------
+// Implementations
+//----------
+
+impl Instruction {
+    /// Parses a line like "R 6 (#70c710)".
+    fn parse(line: &str) -> Result<Instruction, AocError> {
+        let parser = pair(
+            terminated(word, tag(" ")),
+            pair(integer::<u32>, delimited(tag(" (#"), word, tag(")"))),
+        );
+        let (dir_str, (len, color)) = complete(line, parser)?;
+
+        let dir = direction(dir_str)
+            .ok_or_else(|| AocError::parse_at(line, dir_str, format!("Invalid direction `{dir_str}`.")))?;
+
+        let invalid_color = || AocError::parse_at(line, color, format!("Invalid color code `{color}`."));
+        if color.len() != 6 {
+            return Err(invalid_color());
+        }
+        let color_len = i64::from_str_radix(&color[..5], 16).map_err(|_| invalid_color())?;
+        let color_dir = match &color[5..] {
+            "0" => Direction::East,
+            "1" => Direction::South,
+            "2" => Direction::West,
+            "3" => Direction::North,
+            _ => return Err(invalid_color()),
+        };
+
+        Ok(Instruction {
+            dir,
+            len: len.into(),
+            color_move: (color_dir, color_len),
+        })
+    }
+}
+
+//----------
+// Tests
+//----------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aoc_test;
+
+    const SAMPLE: &str = "R 6 (#70c710)
+D 5 (#0dc571)
+L 2 (#5713f0)
+D 2 (#d2c081)
+R 2 (#59c680)
+D 2 (#411b91)
+L 5 (#8ceee2)
+U 2 (#caa173)
+L 1 (#1b58a2)
+U 2 (#caa171)
+R 2 (#7807d2)
+U 3 (#a77fa3)
+L 2 (#015232)
+U 2 (#7a21e3)";
+
+    aoc_test! {
+        sample: SAMPLE,
+        pt1_sample: solve_part1 => 62,
+        pt2_sample: solve_part2 => 952408144115,
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let instruction = Instruction::parse("R 6 (#70c710)").unwrap();
+        assert_eq!(instruction.color_move, (Direction::East, 461937));
+
+        let err = Instruction::parse("X 6 (#70c710)").unwrap_err();
+        assert_eq!(err.to_string(), "Line 1, column 1: Invalid direction `X`.");
+
+        let err = Instruction::parse("R 6 (#70c714)").unwrap_err();
+        assert_eq!(err.to_string(), "Line 1, column 7: Invalid color code `70c714`.");
+
+        let err = Instruction::parse("R 6 #70c710").unwrap_err();
+        assert_eq!(err.to_string(), "Line 1, column 4: Unexpected ` `, expected ` (#`.");
+    }
+
+    #[test]
+    fn test_huge_lagoon() {
+        let max = u32::MAX;
+        let plan = format!("R {max} (#000000)\nD {max} (#000000)\nL {max} (#000000)\nU {max} (#000000)");
+        let err = solve_part1(&plan).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid input: the numbers are too large, the answer overflows"
+        );
+    }
+
+    #[test]
+    fn test_open_trench() {
+        let err = solve_part1("R 6 (#70c710)\nD 5 (#0dc571)").unwrap_err();
+        assert_eq!(err.to_string(), "Invalid input: the trench does not close");
+        let err = solve_part2("R 6 (#70c710)\nD 5 (#0dc571)").unwrap_err();
+        assert_eq!(err.to_string(), "Invalid input: the trench does not close");
+
+        let err = solve_part1("R -6 (#70c710)\nL -6 (#70c712)").unwrap_err();
+        assert!(err.to_string().starts_with("Day 18, line 1, column 3:"), "{err}");
+    }
+}
@@ -0,0 +1,520 @@
+//! Day 19: Aplenty
+//!
+//! Link: https://adventofcode.com/2023/day/19
+//!
+//! # Problem
+//!
+//! Machine parts are rated in four categories: `x`, `m`, `a` and `s`. A part starts at the workflow named `in`, and
+//! each workflow is a list of rules like `a<2006:qkq`: the first rule whose condition holds sends the part to another
+//! workflow, or accepts (`A`) or rejects (`R`) it. The last rule of a workflow has no condition.
+//!
+//! 1. Find the sum of the ratings of all accepted parts;
+//!
+//! 2. Ratings range from 1 to 4000. Find how many combinations of ratings are accepted by the workflows.
+//!
+//! # Solution
+//!
+//! Part 1 runs every part through the workflows.
+//!
+//! Part 2 runs a set of parts instead: one interval of ratings per category, starting with `[1, 4001)`. Each rule
+//! splits the interval of its category in the ratings that follow the rule and the ones that go on to the next rule,
+//! so every accepted set is a disjoint box, whose volume is the number of combinations it holds.
+//!
+//! Running the same sets through the workflows also finds their dead branches, see [`diagnose`]: the workflows never
+//! reached from `in`, and the rules that never receive a rating satisfying them.
+
+use crate::{
+    answer::Answer,
+    combinator::{complete, delimited, either, integer, opt, pair, preceded, separated_list, tag, terminated, word},
+    error::AocError,
+    interval::Interval,
+    iter_ext::IterExt,
+};
+use std::collections::{HashMap, HashSet};
+
+/// Categories of the ratings, in the order they are stored.
+const CATEGORIES: &str = "xmas";
+
+/// Name of the workflow where every part starts.
+const START: &str = "in";
+
+/// Ratings of part 2, `[1, 4000]`.
+const RATINGS: Interval = Interval { start: 1, end: 4001 };
+
+pub fn run(input: &str) -> Result<[Answer; 2], AocError> {
+    Ok([solve_part1(input)?.into(), solve_part2(input)?.into()])
+}
+
+/// Returns the sum of all ratings of the parts accepted by the workflows.
+///
+/// # Errors
+///
+/// Fails if the input is malformed, or the sum overflows.
+pub fn solve_part1(input: &str) -> Result<i64, AocError> {
+    let (workflows, parts) = parse_input(input)?;
+    parts
+        .iter()
+        .filter(|part| is_accepted(&workflows, part))
+        .try_fold(0i64, |total, part| {
+            total.checked_add(part.iter().copied().checked_sum()?)
+        })
+        .ok_or_else(AocError::overflow)
+}
+
+/// Returns the number of combinations of ratings, from 1 to 4000, accepted by the workflows.
+///
+/// # Errors
+///
+/// Fails if the input is malformed.
+pub fn solve_part2(input: &str) -> Result<i64, AocError> {
+    let (workflows, _) = parse_input(input)?;
+    Ok(accepted_combinations(&workflows, Target::Workflow(START), [RATINGS; 4]))
+}
+
+/// Returns the dead branches of the workflows: the workflows unreachable from `in`, and the rules of the reachable
+/// workflows that can never match a part.
+///
+/// # Errors
+///
+/// Fails if the input is malformed.
+pub fn diagnose(input: &str) -> Result<Diagnostics, AocError> {
+    let (workflows, _) = parse_input(input)?;
+    let mut live = HashSet::new();
+    find_live_rules(&workflows, Target::Workflow(START), [RATINGS; 4], &mut live);
+
+    let reached: HashSet<_> = live.iter().map(|&(name, _)| name).collect();
+    let mut unreachable: Vec<_> = workflows
+        .keys()
+        .filter(|name| !reached.contains(*name))
+        .map(|name| name.to_string())
+        .collect();
+    let mut dead_rules: Vec<_> = workflows
+        .iter()
+        .filter(|(name, _)| reached.contains(*name))
+        .flat_map(|(&name, workflow)| (0..=workflow.rules.len()).map(move |i| (name, i)))
+        .filter(|rule| !live.contains(rule))
+        .map(|(name, i)| (name.to_string(), i))
+        .collect();
+
+    unreachable.sort_unstable();
+    dead_rules.sort_unstable();
+    Ok(Diagnostics {
+        unreachable,
+        dead_rules,
+    })
+}
+
+fn parse_input(input: &str) -> Result<(Workflows<'_>, Vec<Part>), AocError> {
+    parse_system(input).map_err(|e| e.in_day(19))
+}
+
+fn parse_system(input: &str) -> Result<(Workflows<'_>, Vec<Part>), AocError> {
+    let mut workflows = Workflows::new();
+    let mut parts = Vec::new();
+
+    let mut lines = input.lines().enumerate();
+    for (i, line) in lines.by_ref().take_while(|(_, line)| !line.is_empty()) {
+        let (name, workflow) = Workflow::parse(line).map_err(|e| e.at_line(i + 1))?;
+        workflows.insert(name, workflow);
+    }
+    for (i, line) in lines {
+        parts.push(parse_part(line).map_err(|e| e.at_line(i + 1))?);
+    }
+
+    // every part starts at `in`, and every rule must send it somewhere known
+    if !workflows.contains_key(START) {
+        return Err(AocError::invalid(format!("missing the `{START}` workflow")));
+    }
+    let targets = workflows
+        .values()
+        .flat_map(|w| w.rules.iter().map(|r| r.target).chain([w.fallback]));
+    for target in targets {
+        if let Target::Workflow(name) = target {
+            if !workflows.contains_key(name) {
+                return Err(AocError::invalid(format!("unknown workflow `{name}`")));
+            }
+        }
+    }
+
+    // a part sent around a cycle would never be accepted nor rejected
+    if let Some(name) = find_cycle(&workflows) {
+        return Err(AocError::invalid(format!("workflow `{name}` is part of a cycle")));
+    }
+
+    Ok((workflows, parts))
+}
+
+/// Returns the name of a workflow in a cycle reachable from `in`, or `None` if the workflows reached form a DAG.
+///
+/// Runs a depth-first search with an explicit stack, a workflow being on the current path from when it is pushed until
+/// all its targets are explored.
+fn find_cycle<'a>(workflows: &Workflows<'a>) -> Option<&'a str> {
+    let targets = |name: &'a str| -> Vec<&'a str> {
+        let workflow = &workflows[name];
+        let targets = workflow.rules.iter().map(|r| r.target).chain([workflow.fallback]);
+        targets
+            .filter_map(|target| match target {
+                Target::Workflow(next) => Some(next),
+                _ => None,
+            })
+            .collect()
+    };
+
+    let mut on_path = HashSet::from([START]);
+    let mut done = HashSet::new();
+    let mut stack = vec![(START, targets(START))];
+    while let Some((name, next)) = stack.last_mut() {
+        match next.pop() {
+            Some(next) if on_path.contains(next) => return Some(next),
+            Some(next) if !done.contains(next) => {
+                on_path.insert(next);
+                stack.push((next, targets(next)));
+            }
+            Some(_) => (),
+            None => {
+                on_path.remove(*name);
+                done.insert(*name);
+                stack.pop();
+            }
+        }
+    }
+
+    None
+}
+
+/// Parses a part like "{x=787,m=2655,a=1222,s=2876}".
+fn parse_part(line: &str) -> Result<Part, AocError> {
+    let parser = delimited(
+        tag("{"),
+        pair(
+            pair(preceded(tag("x="), integer), preceded(tag(",m="), integer)),
+            pair(preceded(tag(",a="), integer), preceded(tag(",s="), integer)),
+        ),
+        tag("}"),
+    );
+    let ((x, m), (a, s)) = complete(line, parser)?;
+    Ok([x, m, a, s])
+}
+
+//----------
+// Helper methods
+//----------
+
+/// Checks whether `part` ends up accepted, starting at the `in` workflow.
+fn is_accepted(workflows: &Workflows, part: &Part) -> bool {
+    let mut name = START;
+    loop {
+        match workflows[name].target(part) {
+            Target::Accept => return true,
+            Target::Reject => return false,
+            Target::Workflow(next) => name = next,
+        }
+    }
+}
+
+/// Returns the number of parts, with ratings within `ranges`, accepted when sent to `target`.
+fn accepted_combinations(workflows: &Workflows, target: Target, mut ranges: PartRanges) -> i64 {
+    let workflow = match target {
+        Target::Accept => return ranges.iter().map(Interval::len).product(),
+        Target::Reject => return 0,
+        Target::Workflow(name) => &workflows[name],
+    };
+
+    let mut total = 0;
+    for rule in &workflow.rules {
+        let (matching, rest) = rule.split(&ranges[rule.category]);
+        if let Some(matching) = matching {
+            let mut sent = ranges;
+            sent[rule.category] = matching;
+            total += accepted_combinations(workflows, rule.target, sent);
+        }
+        match rest {
+            Some(rest) => ranges[rule.category] = rest,
+            None => return total,
+        }
+    }
+
+    total + accepted_combinations(workflows, workflow.fallback, ranges)
+}
+
+/// Inserts into `live` every rule matching some of the parts, with ratings within `ranges`, sent to `target`. Rules
+/// are identified by the name of their workflow and their index, the fallback being the last one.
+fn find_live_rules<'a>(
+    workflows: &Workflows<'a>,
+    target: Target<'a>,
+    mut ranges: PartRanges,
+    live: &mut HashSet<(&'a str, usize)>,
+) {
+    let Target::Workflow(name) = target else {
+        return;
+    };
+    let workflow = &workflows[name];
+
+    for (i, rule) in workflow.rules.iter().enumerate() {
+        let (matching, rest) = rule.split(&ranges[rule.category]);
+        if let Some(matching) = matching {
+            live.insert((name, i));
+            let mut sent = ranges;
+            sent[rule.category] = matching;
+            find_live_rules(workflows, rule.target, sent, live);
+        }
+        match rest {
+            Some(rest) => ranges[rule.category] = rest,
+            None => return,
+        }
+    }
+
+    live.insert((name, workflow.rules.len()));
+    find_live_rules(workflows, workflow.fallback, ranges, live);
+}
+
+//----------
+// Structs
+//----------
+
+/// Dead branches of the workflows, returned by [`diagnose`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Diagnostics {
+    /// Names of the workflows never reached from `in`, sorted.
+    pub unreachable: Vec<String>,
+    /// Rules that never match a part, as the name of their workflow and the index of the rule, sorted. The rule
+    /// without condition is the last one, at the index equal to the number of conditional rules.
+    pub dead_rules: Vec<(String, usize)>,
+}
+
+/// Ratings of a part, in the order of `CATEGORIES`.
+type Part = [i64; 4];
+
+/// Ranges of the ratings of a set of parts, in the order of `CATEGORIES`.
+type PartRanges = [Interval; 4];
+
+type Workflows<'a> = HashMap<&'a str, Workflow<'a>>;
+
+/// Where a rule sends a part.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Target<'a> {
+    Accept,
+    Reject,
+    Workflow(&'a str),
+}
+
+/// A rule with a condition, like `a<2006:qkq`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Rule<'a> {
+    /// Index of the rated category.
+    category: usize,
+    /// Whether the rating must be greater than `value`, instead of less.
+    greater: bool,
+    value: i64,
+    target: Target<'a>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Workflow<'a> {
+    rules: Vec<Rule<'a>>,
+    /// Target of the parts that do not match any rule.
+    fallback: Target<'a>,
+}
+
+//----------
+// Implementations
+//----------
+
+impl<'a> Target<'a> {
+    fn new(name: &'a str) -> Target<'a> {
+        match name {
+            "A" => Target::Accept,
+            "R" => Target::Reject,
+            _ => Target::Workflow(name),
+        }
+    }
+}
+
+impl<'a> Rule<'a> {
+    /// Checks whether `part` satisfies the condition of the rule.
+    fn matches(&self, part: &Part) -> bool {
+        let rating = part[self.category];
+        if self.greater {
+            rating > self.value
+        } else {
+            rating < self.value
+        }
+    }
+
+    /// Splits `range` in the ratings that satisfy the condition and the ones that do not.
+    fn split(&self, range: &Interval) -> (Option<Interval>, Option<Interval>) {
+        if self.greater {
+            // no rating is greater than the largest one
+            let Some(bound) = self.value.checked_add(1) else {
+                return (None, Some(*range));
+            };
+            let (rest, matching) = range.split_at(bound);
+            (matching, rest)
+        } else {
+            range.split_at(self.value)
+        }
+    }
+}
+
+impl<'a> Workflow<'a> {
+    /// Parses a workflow like "px{a<2006:qkq,m>2090:A,rfg}", returning its name.
+    fn parse(line: &'a str) -> Result<(&'a str, Workflow<'a>), AocError> {
+        // rule without the target, like `a<2006`
+        let condition = pair(word, pair(either(tag("<"), tag(">")), integer::<i64>));
+        let step = pair(opt(terminated(condition, tag(":"))), word);
+        let parser = pair(word, delimited(tag("{"), separated_list(step, tag(",")), tag("}")));
+        let (name, steps) = complete(line, parser)?;
+
+        let mut rules = Vec::with_capacity(steps.len());
+        let mut fallback = None;
+        for (condition, target) in steps {
+            let target = Target::new(target);
+            if fallback.is_some() {
+                return Err(AocError::parse_at(
+                    line,
+                    name,
+                    format!("Rule after the last rule of `{name}`."),
+                ));
+            }
+            let Some((category, (op, value))) = condition else {
+                fallback = Some(target);
+                continue;
+            };
+
+            let category = CATEGORIES
+                .find(category)
+                .filter(|_| category.len() == 1)
+                .ok_or_else(|| AocError::parse_at(line, category, format!("Invalid category `{category}`.")))?;
+            rules.push(Rule {
+                category,
+                greater: op == ">",
+                value,
+                target,
+            });
+        }
+
+        let fallback = fallback.ok_or_else(|| {
+            AocError::parse_at(line, name, format!("Workflow `{name}` has no rule without condition."))
+        })?;
+        Ok((name, Workflow { rules, fallback }))
+    }
+
+    /// Returns where `part` is sent by the workflow.
+    fn target(&self, part: &Part) -> Target<'a> {
+        self.rules
+            .iter()
+            .find(|rule| rule.matches(part))
+            .map_or(self.fallback, |rule| rule.target)
+    }
+}
+
+//----------
+// Tests
+//----------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aoc_test;
+
+    const SAMPLE: &str = "px{a<2006:qkq,m>2090:A,rfg}
+pv{a>1716:R,A}
+lnx{m>1548:A,A}
+rfg{s<537:gd,x>2440:R,A}
+qs{s>3448:A,lnx}
+qkq{x<1416:A,crn}
+crn{x>2662:A,R}
+in{s<1351:px,qqz}
+qqz{s>2770:qs,m<1801:hdj,R}
+gd{a>3333:R,R}
+hdj{m>838:A,pv}
+
+{x=787,m=2655,a=1222,s=2876}
+{x=1679,m=44,a=2067,s=496}
+{x=2036,m=264,a=79,s=2244}
+{x=2461,m=1339,a=466,s=291}
+{x=2127,m=1623,a=2188,s=1013}";
+
+    aoc_test! {
+        sample: SAMPLE,
+        pt1_sample: solve_part1 => 19114,
+        pt2_sample: solve_part2 => 167409079868000,
+    }
+
+    #[test]
+    fn test_rule_split() {
+        let (_, workflow) = Workflow::parse("in{a<2006:qkq,m>2090:A,R}").unwrap();
+        let (less, greater) = (workflow.rules[0], workflow.rules[1]);
+
+        assert_eq!(
+            less.split(&RATINGS),
+            (Some(Interval::new(1, 2006)), Some(Interval::new(2006, 4001)))
+        );
+        assert_eq!(
+            greater.split(&RATINGS),
+            (Some(Interval::new(2091, 4001)), Some(Interval::new(1, 2091)))
+        );
+        assert_eq!(
+            less.split(&Interval::new(3000, 4001)),
+            (None, Some(Interval::new(3000, 4001)))
+        );
+
+        // the split agrees with the condition on single parts
+        for rating in [2005, 2006, 2090, 2091] {
+            let part = [rating; 4];
+            for rule in [less, greater] {
+                let (matching, _) = rule.split(&RATINGS);
+                assert_eq!(rule.matches(&part), matching.unwrap().contains(rating));
+            }
+        }
+
+        // no rating is greater than the largest one
+        let (_, workflow) = Workflow::parse("in{a>9223372036854775807:R,A}").unwrap();
+        assert_eq!(workflow.rules[0].split(&RATINGS), (None, Some(RATINGS)));
+        assert_eq!(solve_part2("in{a>9223372036854775807:R,A}").unwrap(), 4000i64.pow(4));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let err = Workflow::parse("px{b<2006:qkq,rfg}").unwrap_err();
+        assert_eq!(err.to_string(), "Line 1, column 4: Invalid category `b`.");
+
+        let err = Workflow::parse("px{a<2006:qkq}").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Line 1, column 1: Workflow `px` has no rule without condition."
+        );
+
+        let err = Workflow::parse("px{a=2006:qkq,rfg}").unwrap_err();
+        assert_eq!(err.to_string(), "Line 1, column 5: Unexpected `=`, expected `}`.");
+
+        let err = parse_part("{x=787,m=2655,s=2876}").unwrap_err();
+        assert_eq!(err.to_string(), "Line 1, column 14: Unexpected `,`, expected `,a=`.");
+
+        let err = parse_system("in{x<5:px,A}\n\n{x=1,m=2,a=3,s=4}").unwrap_err();
+        assert_eq!(err.to_string(), "Invalid input: unknown workflow `px`");
+
+        let err = parse_system("in{a<5:in,A}\n\n{x=1,m=2,a=3,s=4}").unwrap_err();
+        assert_eq!(err.to_string(), "Invalid input: workflow `in` is part of a cycle");
+        let err = parse_system("in{a<5:px,qs}\npx{A}\nqs{m>5:A,rf}\nrf{s<2:px,qs}").unwrap_err();
+        assert_eq!(err.to_string(), "Invalid input: workflow `qs` is part of a cycle");
+
+        // the same workflow reached twice, or a cycle never reached, are not a cycle
+        assert!(parse_system("in{a<5:px,qs}\npx{A}\nqs{m>5:px,R}\nrf{rf}").is_ok());
+    }
+
+    #[test]
+    fn test_diagnose() {
+        // every branch of the sample is alive
+        assert_eq!(diagnose(SAMPLE).unwrap(), Diagnostics::default());
+
+        // `x<5` never matches after `x<10`, so `b` is never reached, and `a` only receives ratings below 10
+        let input = "in{x<10:a,x<5:b,R}\na{x>20:A,m<4001:R,A}\nb{A}\n\n{x=1,m=2,a=3,s=4}";
+        let diagnostics = diagnose(input).unwrap();
+        assert_eq!(diagnostics.unreachable, ["b"]);
+        assert_eq!(
+            diagnostics.dead_rules,
+            [("a".to_string(), 0), ("a".to_string(), 2), ("in".to_string(), 1)]
+        );
+    }
+}
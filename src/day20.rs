@@ -0,0 +1,484 @@
+//! Day 20: Pulse Propagation
+//!
+//! Link: https://adventofcode.com/2023/day/20
+//!
+//! # Problem
+//!
+//! Modules send high or low pulses to their outputs. The broadcaster repeats every pulse it receives, a flip-flop (`%`)
+//! ignores high pulses and toggles on a low one, sending high when it turns on and low when it turns off, and a
+//! conjunction (`&`) remembers the last pulse of each input, sending low if they were all high and high otherwise.
+//! Pressing the button sends a low pulse to the broadcaster, and pulses are processed in the order they are sent.
+//!
+//! 1. Press the button 1000 times and find the product of the number of low pulses and the number of high pulses sent;
+//!
+//! 2. Find the fewest button presses needed to deliver a single low pulse to the module `rx`.
+//!
+//! # Solution
+//!
+//! Part 1 simulates the pulses with a queue.
+//!
+//! Brute force is hopeless for part 2. In the inputs, `rx` is fed by a single conjunction, which sends a low pulse
+//! when all its inputs sent it a high pulse during the same press. Each input is the end of an independent counter
+//! that pulses high periodically, so the simulation only runs until every input has pulsed high twice, giving the
+//! first hit and the period of each one, and the answer is the first press where they all coincide.
+//!
+//! The structure is easier to see drawn: [`network_dot`] writes the network in the GraphViz DOT language, printed by
+//! `aoc2023 20 --dot`.
+
+use crate::{
+    answer::Answer,
+    arena::Interner,
+    combinator::{complete, either, opt, pair, separated_list, tag, terminated, word},
+    error::AocError,
+    math,
+};
+use std::{collections::VecDeque, fmt::Write};
+
+/// Name of the module receiving the pulses of the button.
+const BROADCASTER: &str = "broadcaster";
+
+/// Name of the module that must receive a low pulse in part 2.
+const RX: &str = "rx";
+
+/// Button presses of part 1.
+const PRESSES: usize = 1000;
+
+/// Presses after which part 2 gives up looking for the periods of the inputs of the `rx` feeder.
+const MAX_PRESSES: u64 = 1 << 16;
+
+/// Pulses after which a button press is taken as never ending, as a conjunction feeding itself keeps pulsing.
+const MAX_PULSES: usize = 1 << 20;
+
+pub fn run(input: &str) -> Result<[Answer; 2], AocError> {
+    Ok([solve_part1(input)?.into(), solve_part2(input)?.into()])
+}
+
+/// Returns the product of the low and high pulses sent during 1000 button presses.
+///
+/// # Errors
+///
+/// Fails if the input is malformed, or a button press never ends.
+pub fn solve_part1(input: &str) -> Result<u64, AocError> {
+    let network = parse_input(input)?;
+    let mut machine = Machine::new(&network);
+
+    let mut counts = [0u64; 2];
+    for _ in 0..PRESSES {
+        machine.press(|pulse| counts[pulse.high as usize] += 1)?;
+    }
+
+    Ok(counts[0] * counts[1])
+}
+
+/// Returns the fewest button presses needed to send a low pulse to `rx`.
+///
+/// # Errors
+///
+/// Fails if the input is malformed, `rx` is not fed by a single conjunction, its inputs are not periodic, a button
+/// press never ends, or the answer overflows.
+pub fn solve_part2(input: &str) -> Result<u64, AocError> {
+    presses_until_rx(&parse_input(input)?)?.ok_or_else(|| AocError::invalid("`rx` never receives a low pulse"))
+}
+
+/// Returns the module network in the GraphViz DOT language, with a shape for each type of module: a double circle for
+/// the broadcaster, boxes for flip-flops, inverted trapezia for conjunctions and plain circles for the modules that
+/// only receive pulses.
+///
+/// # Errors
+///
+/// Fails if the input is malformed.
+pub fn network_dot(input: &str) -> Result<String, AocError> {
+    parse_input(input).map(|network| network.to_dot())
+}
+
+fn parse_input(input: &str) -> Result<Network, AocError> {
+    Network::parse(input).map_err(|e| e.in_day(20))
+}
+
+//----------
+// Helper methods
+//----------
+
+/// Returns the fewest button presses needed to send a low pulse to `rx`, from the periods of the inputs of its feeder.
+///
+/// Returns `None` if `rx` is not fed by a single conjunction, or if any of its inputs does not pulse high in two
+/// different presses within `MAX_PRESSES`. Fails if a button press never ends, or the answer overflows.
+fn presses_until_rx(network: &Network) -> Result<Option<u64>, AocError> {
+    let Some(rx) = network.labels.id(RX) else {
+        return Ok(None);
+    };
+    let mut feeders = (0..network.modules.len() as u32).filter(|&id| network.modules[id as usize].outputs_to(rx));
+    let Some(feeder) = feeders.next().filter(|_| feeders.next().is_none()) else {
+        return Ok(None);
+    };
+    if network.modules[feeder as usize].kind != Kind::Conjunction {
+        return Ok(None);
+    }
+
+    // presses where each input of the feeder sent it a high pulse, the first two are enough. An input may pulse high
+    // more than once in a press, only the press counts
+    let n_inputs = network.modules[feeder as usize].inputs.len();
+    let mut hits: Vec<Vec<i64>> = vec![Vec::with_capacity(2); n_inputs];
+    let mut machine = Machine::new(network);
+
+    for press in 1..=MAX_PRESSES as i64 {
+        machine.press(|pulse| {
+            if pulse.to == feeder && pulse.high {
+                let hits = &mut hits[pulse.slot];
+                if hits.len() < 2 && hits.last() != Some(&press) {
+                    hits.push(press);
+                }
+            }
+        })?;
+
+        if hits.iter().all(|h| h.len() == 2) {
+            let events: Vec<_> = hits.iter().map(|h| (h[0], h[1] - h[0])).collect();
+
+            // once the least common multiple of the periods fits, congruences without solution are incompatible, and a
+            // solution can only overflow when skipping to the last first hit
+            let periods: Vec<_> = events.iter().map(|&(_, period)| period as u64).collect();
+            if math::lcm_of(&periods).is_none_or(|lcm| lcm > i64::MAX as u64) {
+                return Err(AocError::overflow());
+            }
+            return match math::first_coincidence(&events) {
+                Some(press) => Ok(Some(press as u64)),
+                None if math::crt(&events).is_some() => Err(AocError::overflow()),
+                None => Ok(None),
+            };
+        }
+    }
+
+    Ok(None)
+}
+
+/// Returns the fewest button presses needed to send a low pulse to `rx`, pressing the button until it happens.
+#[cfg(test)]
+fn presses_until_rx_naive(network: &Network, max_presses: u64) -> Option<u64> {
+    let rx = network.labels.id(RX)?;
+    let mut machine = Machine::new(network);
+
+    (1..=max_presses).find(|_| {
+        let mut reached = false;
+        machine.press(|pulse| reached |= pulse.to == rx && !pulse.high).unwrap();
+        reached
+    })
+}
+
+//----------
+// Structs
+//----------
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Kind {
+    Broadcaster,
+    FlipFlop,
+    Conjunction,
+    /// A module that only receives pulses, like `rx`.
+    Sink,
+}
+
+#[derive(Clone, Debug)]
+struct Module {
+    kind: Kind,
+    /// Destination modules, and the slot of this module among the inputs of each of them.
+    outputs: Vec<(u32, usize)>,
+    inputs: Vec<u32>,
+}
+
+/// The modules and their connections. Modules are identified by the ids of their names.
+#[derive(Clone, Debug)]
+struct Network {
+    labels: Interner,
+    modules: Vec<Module>,
+    broadcaster: u32,
+}
+
+/// A pulse being delivered. `slot` is the position of the sender among the inputs of the receiver.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Pulse {
+    to: u32,
+    slot: usize,
+    high: bool,
+}
+
+/// State of the modules of a network.
+struct Machine<'a> {
+    network: &'a Network,
+    /// Whether each flip-flop is on.
+    on: Vec<bool>,
+    /// Last pulse received from each input, for every conjunction.
+    memory: Vec<Vec<bool>>,
+    /// Number of inputs whose last pulse was high, for every conjunction.
+    n_high: Vec<usize>,
+    queue: VecDeque<Pulse>,
+}
+
+//----------
+// Implementations
+//----------
+
+impl Module {
+    fn outputs_to(&self, id: u32) -> bool {
+        self.outputs.iter().any(|&(to, _)| to == id)
+    }
+}
+
+impl Network {
+    /// Parses the module configuration, one module per line like "%a -> inv, con".
+    fn parse(input: &str) -> Result<Network, AocError> {
+        let parser = pair(
+            opt(either(tag("%"), tag("&"))),
+            pair(terminated(word, tag(" -> ")), separated_list(word, tag(", "))),
+        );
+
+        let mut labels = Interner::new();
+        let mut declared = Vec::new();
+        for (i, line) in input.lines().enumerate() {
+            let (prefix, (name, outputs)) = complete(line, &parser).map_err(|e| e.at_line(i + 1))?;
+            let kind = match prefix {
+                Some("%") => Kind::FlipFlop,
+                Some(_) => Kind::Conjunction,
+                None if name == BROADCASTER => Kind::Broadcaster,
+                None => {
+                    return Err(
+                        AocError::parse_at(line, name, format!("Module `{name}` without a type.")).at_line(i + 1),
+                    )
+                }
+            };
+
+            let id = labels.intern(name);
+            let outputs: Vec<_> = outputs.into_iter().map(|out| labels.intern(out)).collect();
+            declared.push((id, kind, outputs));
+        }
+
+        let mut modules = vec![
+            Module {
+                kind: Kind::Sink,
+                outputs: Vec::new(),
+                inputs: Vec::new(),
+            };
+            labels.len()
+        ];
+        for (id, kind, outputs) in declared {
+            modules[id as usize].kind = kind;
+            for out in outputs {
+                let slot = modules[out as usize].inputs.len();
+                modules[out as usize].inputs.push(id);
+                modules[id as usize].outputs.push((out, slot));
+            }
+        }
+
+        let broadcaster = labels
+            .id(BROADCASTER)
+            .ok_or_else(|| AocError::invalid(format!("missing the `{BROADCASTER}` module")))?;
+
+        Ok(Network {
+            labels,
+            modules,
+            broadcaster,
+        })
+    }
+
+    /// Writes the network in the DOT language, see [`network_dot`].
+    fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph network {\n");
+
+        for (id, module) in self.modules.iter().enumerate() {
+            let shape = match module.kind {
+                Kind::Broadcaster => "doublecircle",
+                Kind::FlipFlop => "box",
+                Kind::Conjunction => "invtrapezium",
+                Kind::Sink => "circle",
+            };
+            writeln!(dot, "    \"{}\" [shape={shape}];", self.labels.get(id as u32)).unwrap();
+        }
+        for (id, module) in self.modules.iter().enumerate() {
+            for &(to, _) in &module.outputs {
+                writeln!(
+                    dot,
+                    "    \"{}\" -> \"{}\";",
+                    self.labels.get(id as u32),
+                    self.labels.get(to)
+                )
+                .unwrap();
+            }
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+impl<'a> Machine<'a> {
+    /// Creates a machine with all flip-flops off and all conjunctions remembering low pulses.
+    fn new(network: &'a Network) -> Machine<'a> {
+        Machine {
+            network,
+            on: vec![false; network.modules.len()],
+            memory: network.modules.iter().map(|m| vec![false; m.inputs.len()]).collect(),
+            n_high: vec![0; network.modules.len()],
+            queue: VecDeque::new(),
+        }
+    }
+
+    /// Presses the button, calling `observe` with every pulse delivered, including the one sent by the button.
+    ///
+    /// Fails if the press delivers more than `MAX_PULSES` pulses, as it would never end.
+    fn press(&mut self, mut observe: impl FnMut(&Pulse)) -> Result<(), AocError> {
+        self.queue.push_back(Pulse {
+            to: self.network.broadcaster,
+            slot: 0,
+            high: false,
+        });
+
+        let mut n_pulses = 0;
+        while let Some(pulse) = self.queue.pop_front() {
+            n_pulses += 1;
+            if n_pulses > MAX_PULSES {
+                self.queue.clear();
+                return Err(AocError::invalid(format!(
+                    "a button press sends more than {MAX_PULSES} pulses, it never ends"
+                )));
+            }
+            observe(&pulse);
+
+            let id = pulse.to as usize;
+            let module = &self.network.modules[id];
+            let high = match module.kind {
+                Kind::Broadcaster => pulse.high,
+                Kind::FlipFlop if pulse.high => continue,
+                Kind::FlipFlop => {
+                    self.on[id] = !self.on[id];
+                    self.on[id]
+                }
+                Kind::Conjunction => {
+                    let remembered = &mut self.memory[id][pulse.slot];
+                    if *remembered != pulse.high {
+                        *remembered = pulse.high;
+                        if pulse.high {
+                            self.n_high[id] += 1;
+                        } else {
+                            self.n_high[id] -= 1;
+                        }
+                    }
+                    self.n_high[id] != module.inputs.len()
+                }
+                Kind::Sink => continue,
+            };
+
+            self.queue
+                .extend(module.outputs.iter().map(|&(to, slot)| Pulse { to, slot, high }));
+        }
+
+        Ok(())
+    }
+}
+
+//----------
+// Tests
+//----------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aoc_test;
+
+    const SAMPLE_1: &str = "broadcaster -> a, b, c
+%a -> b
+%b -> c
+%c -> inv
+&inv -> a";
+
+    const SAMPLE_2: &str = "broadcaster -> a
+%a -> inv, con
+&inv -> b
+%b -> con
+&con -> output";
+
+    aoc_test! {
+        sample: SAMPLE_1,
+        pt1_sample_1: solve_part1 => 32000000,
+    }
+
+    aoc_test! {
+        sample: SAMPLE_2,
+        pt1_sample_2: solve_part1 => 11687500,
+    }
+
+    /// Two counters built like the ones of the puzzle inputs: four flip-flops counting button presses, and a
+    /// conjunction that resets them when they reach 13 and 11 respectively, and pulses high through an inverter.
+    const COUNTERS: &str = "broadcaster -> a0, b0
+%a0 -> a1, ka
+%a1 -> a2
+%a2 -> a3, ka
+%a3 -> ka
+&ka -> a0, a1, ia
+&ia -> feed
+%b0 -> b1, kb
+%b1 -> b2, kb
+%b2 -> b3
+%b3 -> kb
+&kb -> b0, b2, ib
+&ib -> feed
+&feed -> rx";
+
+    #[test]
+    fn test_periodic_feeder() {
+        let network = parse_input(COUNTERS).unwrap();
+        let expected = presses_until_rx_naive(&network, 1000);
+        assert_eq!(expected, Some(143));
+        assert_eq!(presses_until_rx(&network).unwrap(), expected);
+
+        // an input pulsing high twice in every press has a period of 1 press, not 0
+        let network = parse_input("broadcaster -> a, b\n&a -> f\n&b -> a\n&f -> rx").unwrap();
+        assert_eq!(presses_until_rx_naive(&network, 10), Some(1));
+        assert_eq!(presses_until_rx(&network).unwrap(), Some(1));
+    }
+
+    #[test]
+    fn test_endless_press() {
+        // a conjunction feeding itself keeps sending high pulses
+        let input = "broadcaster -> c\n&c -> c, rx";
+        let err = solve_part1(input).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid input: a button press sends more than 1048576 pulses, it never ends"
+        );
+        assert!(solve_part2(input).is_err());
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let err = Network::parse("broadcaster -> a\na -> b").unwrap_err();
+        assert_eq!(err.to_string(), "Line 2, column 1: Module `a` without a type.");
+
+        let err = Network::parse("broadcaster -> a\n%a => b").unwrap_err();
+        assert_eq!(err.to_string(), "Line 2, column 3: Unexpected ` `, expected ` -> `.");
+
+        let err = Network::parse("%a -> b").unwrap_err();
+        assert_eq!(err.to_string(), "Invalid input: missing the `broadcaster` module");
+    }
+
+    #[test]
+    fn test_dot() {
+        let dot = network_dot(SAMPLE_2).unwrap();
+        assert_eq!(
+            dot,
+            r#"digraph network {
+    "broadcaster" [shape=doublecircle];
+    "a" [shape=box];
+    "inv" [shape=invtrapezium];
+    "con" [shape=invtrapezium];
+    "b" [shape=box];
+    "output" [shape=circle];
+    "broadcaster" -> "a";
+    "a" -> "inv";
+    "a" -> "con";
+    "inv" -> "b";
+    "con" -> "output";
+    "b" -> "con";
+}
+"#
+        );
+    }
+}
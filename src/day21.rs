@@ -0,0 +1,325 @@
+//! Day 21: Step Counter
+//!
+//! Link: https://adventofcode.com/2023/day/21
+//!
+//! # Problem
+//!
+//! A map of a garden shows the starting position of an elf ('S'), garden plots ('.') and rocks ('#'). Each step the
+//! elf moves to a neighbouring plot, north, south, east or west.
+//!
+//! 1. Find how many plots the elf can reach in exactly 64 steps;
+//!
+//! 2. The map repeats infinitely in every direction. Find how many plots the elf can reach in exactly 26501365 steps.
+//!
+//! # Solution
+//!
+//! Since the elf can always step back and forth, a plot is reachable in exactly `n` steps if its shortest distance is
+//! at most `n` and has the same parity as `n`. A single breadth-first search answers part 1.
+//!
+//! For part 2, the inputs are square, with the start at the center and clear rows and columns through it and along the
+//! borders, so the reachable area grows as a diamond across the tiles. With `size` the side of the map and
+//! `n = rem + k * size`, the number of reachable plots is a quadratic function of `k`, so it is sampled for `k` equal
+//! to 0, 1 and 2 with a search on the infinite map, and extrapolated with Newton's forward differences.
+
+use crate::{
+    answer::Answer,
+    error::AocError,
+    geometry::Point,
+    graph,
+    grid::{Grid, Position},
+    sparse_grid::SparseGrid,
+};
+
+const START: u8 = b'S';
+const ROCK: u8 = b'#';
+
+/// Steps of part 1.
+const STEPS_PT1: usize = 64;
+
+/// Steps of part 2.
+const STEPS_PT2: usize = 26501365;
+
+pub fn run(input: &str) -> Result<[Answer; 2], AocError> {
+    Ok([solve_part1(input)?.into(), solve_part2(input)?.into()])
+}
+
+/// Returns the number of plots reachable in exactly 64 steps.
+///
+/// # Errors
+///
+/// Fails if the input is not a rectangular map with a starting position.
+pub fn solve_part1(input: &str) -> Result<usize, AocError> {
+    let (garden, start) = parse_input(input)?;
+    Ok(reachable(&garden, start, STEPS_PT1))
+}
+
+/// Returns the number of plots reachable in exactly 26501365 steps on the infinite map.
+///
+/// # Errors
+///
+/// Fails if the input is not a square map with a starting position.
+pub fn solve_part2(input: &str) -> Result<u64, AocError> {
+    let (garden, start) = parse_input(input)?;
+    if garden.n_rows() != garden.n_cols() {
+        return Err(AocError::invalid(format!(
+            "the map must be square, it has {} rows and {} columns",
+            garden.n_rows(),
+            garden.n_cols()
+        )));
+    }
+    reachable_extrapolated(&garden, start, STEPS_PT2)
+        .ok_or_else(|| AocError::invalid("the reachable plots do not grow quadratically"))
+}
+
+fn parse_input(input: &str) -> Result<(Grid<u8>, Position), AocError> {
+    let garden = input.parse::<Grid<u8>>().map_err(|e| e.in_day(21))?;
+    let start = garden
+        .iter()
+        .find(|(_, &b)| b == START)
+        .map(|(pos, _)| pos)
+        .ok_or_else(|| AocError::invalid("missing starting position"))?;
+    Ok((garden, start))
+}
+
+//----------
+// Helper methods
+//----------
+
+/// Checks whether `n` steps can end at a plot at shortest distance `dist`.
+fn reachable_at(dist: usize, n: usize) -> bool {
+    dist <= n && dist % 2 == n % 2
+}
+
+/// Returns the number of plots of the map reachable in exactly `n` steps.
+fn reachable(garden: &Grid<u8>, start: Position, n: usize) -> usize {
+    graph::bfs(start, |&pos| garden.neighbors4(pos).filter(|&p| garden[p] != ROCK))
+        .take_while(|&(_, dist)| dist <= n)
+        .filter(|&(_, dist)| reachable_at(dist, n))
+        .count()
+}
+
+/// Returns the shortest distances from the start to the plots of the infinite map within `max_steps` steps, with the
+/// map repeating around the copy at the origin.
+fn distances_infinite(garden: &Grid<u8>, start: Position, max_steps: usize) -> SparseGrid<usize> {
+    let (n_rows, n_cols) = (garden.n_rows() as i64, garden.n_cols() as i64);
+    let is_plot = |(x, y): Point| {
+        let pos = Position::new(y.rem_euclid(n_rows) as usize, x.rem_euclid(n_cols) as usize);
+        garden[pos] != ROCK
+    };
+
+    let start = (start.col as i64, start.row as i64);
+    graph::bfs(start, |&(x, y)| {
+        [(x, y - 1), (x, y + 1), (x - 1, y), (x + 1, y)]
+            .into_iter()
+            .filter(|&p| is_plot(p))
+    })
+    .take_while(|&(_, dist)| dist <= max_steps)
+    .collect()
+}
+
+/// Returns, for every step count of `steps`, the number of plots of the infinite map reachable in exactly that many
+/// steps.
+fn reachable_infinite(garden: &Grid<u8>, start: Position, steps: &[usize]) -> Vec<u64> {
+    let max_steps = steps.iter().copied().max().unwrap_or(0);
+    let distances = distances_infinite(garden, start, max_steps);
+
+    steps
+        .iter()
+        .map(|&n| distances.iter().filter(|(_, &dist)| reachable_at(dist, n)).count() as u64)
+        .collect()
+}
+
+/// Returns the number of plots of the infinite map reachable in exactly `n` steps, extrapolating the counts for
+/// `n % size`, `n % size + size` and `n % size + 2 * size` steps.
+///
+/// The result is only exact for maps with the properties of the puzzle inputs, see the module documentation. For other
+/// maps it may be meaningless, and `None` is returned if it is negative or overflows.
+///
+/// # Panics
+///
+/// Panics if the map is not square.
+fn reachable_extrapolated(garden: &Grid<u8>, start: Position, n: usize) -> Option<u64> {
+    assert_eq!(garden.n_rows(), garden.n_cols(), "the map must be square");
+    let size = garden.n_rows();
+    let (rem, k) = (n % size, (n / size) as u64);
+
+    let samples = reachable_infinite(garden, start, &[rem, rem + size, rem + 2 * size]);
+    if k < 3 {
+        return Some(samples[k as usize]);
+    }
+
+    // f(k) = f(0) + k * Δf(0) + k * (k - 1) / 2 * Δ²f(0)
+    let [f0, f1, f2] = [0, 1, 2].map(|i| samples[i] as i128);
+    let (k, first, second) = (k as i128, f1 - f0, f2 + f0 - 2 * f1);
+    let count = k
+        .checked_mul(first)?
+        .checked_add((k * (k - 1) / 2).checked_mul(second)?)?;
+    u64::try_from(f0 + count).ok()
+}
+
+/// Returns the number of plots reachable in exactly `n` steps, moving the elf to every possible plot one step at a
+/// time. Only usable for small step counts.
+#[cfg(test)]
+fn simulate(garden: &Grid<u8>, start: Position, n: usize, infinite: bool) -> usize {
+    use std::collections::HashSet;
+
+    let (n_rows, n_cols) = (garden.n_rows() as i64, garden.n_cols() as i64);
+    let is_plot = |(row, col): (i64, i64)| {
+        let inside = (0..n_rows).contains(&row) && (0..n_cols).contains(&col);
+        let pos = Position::new(row.rem_euclid(n_rows) as usize, col.rem_euclid(n_cols) as usize);
+        (infinite || inside) && garden[pos] != ROCK
+    };
+
+    let mut positions = HashSet::from([(start.row as i64, start.col as i64)]);
+    for _ in 0..n {
+        positions = positions
+            .iter()
+            .flat_map(|&(row, col)| [(row - 1, col), (row + 1, col), (row, col - 1), (row, col + 1)])
+            .filter(|&p| is_plot(p))
+            .collect();
+    }
+
+    positions.len()
+}
+
+/// Returns `copies` repetitions of the map in every direction around it, and the start in the central copy. Within
+/// `copies * size` steps of the start, a search on the expanded map behaves as on the infinite one.
+#[cfg(test)]
+fn expand(garden: &Grid<u8>, start: Position, copies: usize) -> (Grid<u8>, Position) {
+    let (n_rows, n_cols) = (garden.n_rows(), garden.n_cols());
+    let tiles = 2 * copies + 1;
+
+    let vec = (0..tiles * n_rows)
+        .flat_map(|row| (0..tiles * n_cols).map(move |col| (row, col)))
+        .map(|(row, col)| garden[Position::new(row % n_rows, col % n_cols)])
+        .collect();
+    let start = Position::new(start.row + copies * n_rows, start.col + copies * n_cols);
+
+    (Grid::new(vec, tiles * n_rows, tiles * n_cols), start)
+}
+
+//----------
+// Tests
+//----------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "...........
+.....###.#.
+.###.##..#.
+..#.#...#..
+....#.#....
+.##..S####.
+.##..#...#.
+.......##..
+.##.#.####.
+.##..##.##.
+...........";
+
+    /// A map with the properties of the puzzle inputs: square, with the start at the center of clear middle and border
+    /// rows and columns.
+    const CLEAR: &str = "...........
+....#..#...
+.###.......
+...#....#..
+.#.........
+.....S.....
+.......#...
+..##...#.#.
+.......#...
+.#.#.......
+...........";
+
+    #[test]
+    fn test_finite() {
+        let (garden, start) = parse_input(SAMPLE).unwrap();
+        assert_eq!(reachable(&garden, start, 6), 16);
+        for n in 0..=12 {
+            assert_eq!(
+                reachable(&garden, start, n),
+                simulate(&garden, start, n, false),
+                "{n} steps"
+            );
+        }
+    }
+
+    #[test]
+    fn test_infinite() {
+        let (garden, start) = parse_input(SAMPLE).unwrap();
+        assert_eq!(
+            reachable_infinite(&garden, start, &[6, 10, 50, 100]),
+            [16, 50, 1594, 6536]
+        );
+
+        let steps: Vec<_> = (0..=30).collect();
+        let counts = reachable_infinite(&garden, start, &steps);
+        for (&n, &count) in steps.iter().zip(&counts) {
+            assert_eq!(count, simulate(&garden, start, n, true) as u64, "{n} steps");
+        }
+    }
+
+    #[test]
+    fn test_distances_infinite() {
+        // the clear middle row and column let the search reach `n` steps away in the four directions, across copies
+        let (garden, start) = parse_input(CLEAR).unwrap();
+        let distances = distances_infinite(&garden, start, 30);
+        assert_eq!(distances.bounding_box(), Some(((-25, -25), (35, 35))));
+        assert_eq!(distances.get((5, 5)), Some(&0));
+        assert_eq!(distances.get((-25, 5)), Some(&30));
+        assert_eq!(distances.get((-26, 5)), None);
+        // the whole copy to the left is within reach, and every distance has the parity of the coordinates
+        let left: Vec<_> = distances.within((-11, 0), (-1, 10)).collect();
+        assert_eq!(left.len(), garden.iter().filter(|(_, &b)| b != ROCK).count());
+        assert!(left
+            .iter()
+            .all(|(&(x, y), &dist)| dist % 2 != ((x + y) % 2 == 0) as usize));
+    }
+
+    #[test]
+    fn test_extrapolation() {
+        let (garden, start) = parse_input(CLEAR).unwrap();
+        for k in 0..6 {
+            let n = 5 + k * garden.n_rows();
+            assert_eq!(
+                reachable_extrapolated(&garden, start, n),
+                Some(reachable_infinite(&garden, start, &[n])[0]),
+                "{n} steps"
+            );
+        }
+    }
+
+    #[test]
+    fn test_expanded() {
+        // the side of the map is odd, so the parity of the plots flips from one copy to the next
+        let (garden, start) = parse_input(CLEAR).unwrap();
+        let size = garden.n_rows();
+        let (expanded, center) = expand(&garden, start, 5);
+
+        for rem in 0..size {
+            for k in 0..5 {
+                let n = rem + k * size;
+                let brute_force = reachable(&expanded, center, n) as u64;
+                assert_eq!(reachable_infinite(&garden, start, &[n])[0], brute_force, "{n} steps");
+                assert_eq!(
+                    reachable_extrapolated(&garden, start, n),
+                    Some(brute_force),
+                    "{n} steps"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_missing_start() {
+        let err = solve_part1("...\n.#.\n...").unwrap_err();
+        assert_eq!(err.to_string(), "Invalid input: missing starting position");
+
+        let err = solve_part2("...\n.S.").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid input: the map must be square, it has 2 rows and 3 columns"
+        );
+    }
+}
@@ -0,0 +1,248 @@
+//! Day 22: Sand Slabs
+//!
+//! Link: https://adventofcode.com/2023/day/22
+//!
+//! # Problem
+//!
+//! A snapshot lists falling bricks as the coordinates of their two ends, like `1,0,1~1,2,1`. Bricks fall straight down
+//! until they rest on the ground (`z` is 1) or on other bricks.
+//!
+//! 1. Once settled, a brick can be safely disintegrated if no other brick would fall. Find how many bricks are safe;
+//!
+//! 2. For each brick, find how many other bricks would fall if it were disintegrated, and sum these numbers.
+//!
+//! # Solution
+//!
+//! The bricks are settled from the lowest up, keeping a height map with the top height of every column and the brick
+//! occupying it. Each brick lands at the top of the highest column under it, and rests on the bricks at that height,
+//! which gives the support graph.
+//!
+//! A brick is safe if every brick it supports has another support. The chain reaction of a brick propagates through the
+//! support graph: a brick falls once all its supports fell. Every chain reaction is independent, so they run in
+//! parallel.
+
+use crate::{
+    answer::Answer,
+    combinator::{complete, integer, pair, preceded, tag, terminated},
+    error::AocError,
+    grid::{Grid, Position},
+};
+use rayon::prelude::*;
+use std::collections::VecDeque;
+
+/// Bound on the x and y coordinates, as the heights of the columns are kept in a dense grid.
+const MAX_SIDE: usize = 1_000;
+
+/// Bound on the z coordinates, so the heights of the stacked bricks cannot overflow.
+const MAX_HEIGHT: usize = 1_000_000;
+
+pub fn run(input: &str) -> Result<[Answer; 2], AocError> {
+    Ok([solve_part1(input)?.into(), solve_part2(input)?.into()])
+}
+
+/// Returns the number of bricks that can be disintegrated without making other bricks fall.
+///
+/// # Errors
+///
+/// Fails if the input is malformed.
+pub fn solve_part1(input: &str) -> Result<usize, AocError> {
+    let supports = Supports::settle(parse_input(input)?);
+    Ok((0..supports.len()).filter(|&brick| supports.is_safe(brick)).count())
+}
+
+/// Returns the sum, over all bricks, of the number of other bricks that fall when it is disintegrated.
+///
+/// # Errors
+///
+/// Fails if the input is malformed.
+pub fn solve_part2(input: &str) -> Result<usize, AocError> {
+    let supports = Supports::settle(parse_input(input)?);
+    Ok((0..supports.len())
+        .into_par_iter()
+        .map(|brick| supports.chain_reaction(brick))
+        .sum())
+}
+
+fn parse_input(input: &str) -> Result<Vec<Brick>, AocError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| Brick::parse(line).map_err(|e| e.at_line(i + 1).in_day(22)))
+        .collect()
+}
+
+//----------
+// Structs
+//----------
+
+/// A brick, as the coordinates of its lowest and highest corners.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Brick {
+    low: [usize; 3],
+    high: [usize; 3],
+}
+
+/// Support graph of the settled bricks, which are numbered from the lowest up.
+#[derive(Clone, Debug)]
+struct Supports {
+    /// Bricks resting on each brick.
+    above: Vec<Vec<usize>>,
+    /// Bricks each brick rests on. Empty for bricks on the ground.
+    below: Vec<Vec<usize>>,
+}
+
+//----------
+// Implementations
+//----------
+
+impl Brick {
+    /// Parses a brick like "1,0,1~1,2,1".
+    fn parse(line: &str) -> Result<Brick, AocError> {
+        let coords = || {
+            pair(
+                terminated(integer::<usize>, tag(",")),
+                pair(terminated(integer::<usize>, tag(",")), integer::<usize>),
+            )
+        };
+        let ((x0, (y0, z0)), (x1, (y1, z1))) = complete(line, pair(coords(), preceded(tag("~"), coords())))?;
+
+        if z0.min(z1) == 0 {
+            return Err(AocError::parse(format!("Brick `{line}` below the ground.")));
+        }
+        if [x0, x1, y0, y1].iter().any(|&c| c >= MAX_SIDE) || z0.max(z1) >= MAX_HEIGHT {
+            return Err(AocError::parse(format!("Brick `{line}` too far away.")));
+        }
+
+        Ok(Brick {
+            low: [x0.min(x1), y0.min(y1), z0.min(z1)],
+            high: [x0.max(x1), y0.max(y1), z0.max(z1)],
+        })
+    }
+
+    /// Returns the positions of the columns occupied by the brick, as seen from above.
+    fn footprint(&self) -> impl Iterator<Item = Position> + '_ {
+        (self.low[0]..=self.high[0]).flat_map(move |x| (self.low[1]..=self.high[1]).map(move |y| Position::new(x, y)))
+    }
+}
+
+impl Supports {
+    /// Lets the bricks fall until they settle, and returns which bricks support which.
+    fn settle(mut bricks: Vec<Brick>) -> Supports {
+        bricks.sort_unstable_by_key(|b| b.low[2]);
+
+        let n_x = bricks.iter().map(|b| b.high[0] + 1).max().unwrap_or(0);
+        let n_y = bricks.iter().map(|b| b.high[1] + 1).max().unwrap_or(0);
+        // top height of each column, and the brick at the top
+        let mut heights = Grid::filled((0, None), n_x, n_y);
+
+        let mut above = vec![Vec::new(); bricks.len()];
+        let mut below = vec![Vec::new(); bricks.len()];
+        for (id, brick) in bricks.iter().enumerate() {
+            let rest = brick.footprint().map(|pos| heights[pos].0).max().unwrap_or(0);
+
+            let mut supporters: Vec<usize> = brick
+                .footprint()
+                .filter_map(|pos| heights[pos].1.filter(|_| heights[pos].0 == rest))
+                .collect();
+            supporters.sort_unstable();
+            supporters.dedup();
+            for &s in &supporters {
+                above[s].push(id);
+            }
+            below[id] = supporters;
+
+            let top = rest + brick.high[2] - brick.low[2] + 1;
+            for pos in brick.footprint() {
+                heights[pos] = (top, Some(id));
+            }
+        }
+
+        Supports { above, below }
+    }
+
+    /// Returns the number of bricks.
+    fn len(&self) -> usize {
+        self.above.len()
+    }
+
+    /// Checks whether `brick` can be disintegrated without making other bricks fall.
+    fn is_safe(&self, brick: usize) -> bool {
+        self.above[brick].iter().all(|&b| self.below[b].len() > 1)
+    }
+
+    /// Returns the number of other bricks that fall when `brick` is disintegrated.
+    fn chain_reaction(&self, brick: usize) -> usize {
+        // supports of each brick that already fell
+        let mut fallen_supports = vec![0; self.len()];
+        let mut falling = VecDeque::from([brick]);
+        let mut count = 0;
+
+        while let Some(b) = falling.pop_front() {
+            for &next in &self.above[b] {
+                fallen_supports[next] += 1;
+                if fallen_supports[next] == self.below[next].len() {
+                    falling.push_back(next);
+                    count += 1;
+                }
+            }
+        }
+
+        count
+    }
+}
+
+//----------
+// Tests
+//----------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aoc_test;
+
+    const SAMPLE: &str = "1,0,1~1,2,1
+0,0,2~2,0,2
+0,2,3~2,2,3
+0,0,4~0,2,4
+2,0,5~2,2,5
+0,1,6~2,1,6
+1,1,8~1,1,9";
+
+    aoc_test! {
+        sample: SAMPLE,
+        pt1_sample: solve_part1 => 5,
+        pt2_sample: solve_part2 => 7,
+    }
+
+    #[test]
+    fn test_settle() {
+        let supports = Supports::settle(parse_input(SAMPLE).unwrap());
+        // A supports B and C, which both support D and E, F rests on D and E and G on F
+        assert_eq!(supports.above[0], [1, 2]);
+        assert_eq!(supports.below[5], [3, 4]);
+        assert_eq!(supports.below[6], [5]);
+        assert_eq!(supports.chain_reaction(0), 6);
+        assert_eq!(supports.chain_reaction(5), 1);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            Brick::parse("2,2,2~0,2,1").unwrap(),
+            Brick {
+                low: [0, 2, 1],
+                high: [2, 2, 2]
+            }
+        );
+
+        let err = Brick::parse("1,0,1-1,2,1").unwrap_err();
+        assert_eq!(err.to_string(), "Line 1, column 6: Unexpected `-`, expected `~`.");
+
+        let err = Brick::parse("1,0,0~1,2,0").unwrap_err();
+        assert_eq!(err.to_string(), "Line 1: Brick `1,0,0~1,2,0` below the ground.");
+
+        let err = Brick::parse("0,0,1~1000,0,1").unwrap_err();
+        assert_eq!(err.to_string(), "Line 1: Brick `0,0,1~1000,0,1` too far away.");
+        assert!(Brick::parse("0,0,1~0,0,18446744073709551615").is_err());
+    }
+}
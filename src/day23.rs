@@ -0,0 +1,269 @@
+//! Day 23: A Long Walk
+//!
+//! Link: https://adventofcode.com/2023/day/23
+//!
+//! # Problem
+//!
+//! A map of hiking trails shows paths ('.'), forest ('#') and steep slopes ('^', '>', 'v' and '<'). The hike starts at
+//! the only path tile of the top row, ends at the only path tile of the bottom row, and never steps onto the same tile
+//! twice.
+//!
+//! 1. Stepping onto a slope forces the next step downhill, in the direction of the arrow. Find the longest hike;
+//!
+//! 2. The slopes are as easy to walk as paths. Find the longest hike.
+//!
+//! # Solution
+//!
+//! Longest path is NP-hard, but the trails are long corridors joined by a few dozen junctions (tiles with three or more
+//! neighbouring paths). The map is contracted into a graph of junctions, with edges weighted by the length of the
+//! corridors between them, and every hike over that graph is explored by a depth-first search keeping the visited
+//! junctions in a `BitSet`.
+
+use crate::{
+    answer::Answer,
+    bitset::BitSet,
+    error::AocError,
+    grid::{Direction, Grid, Position},
+};
+use std::collections::HashMap;
+
+const FOREST: u8 = b'#';
+const PATH: u8 = b'.';
+
+pub fn run(input: &str) -> Result<[Answer; 2], AocError> {
+    Ok([solve_part1(input)?.into(), solve_part2(input)?.into()])
+}
+
+/// Returns the length of the longest hike, following the slopes downhill.
+///
+/// # Errors
+///
+/// Fails if the input is malformed, has too many junctions or there is no hike from the start to the end.
+pub fn solve_part1(input: &str) -> Result<usize, AocError> {
+    Trails::contract(&parse_input(input)?, true)?
+        .longest_hike()
+        .ok_or_else(no_hike)
+}
+
+/// Returns the length of the longest hike, walking the slopes in any direction.
+///
+/// # Errors
+///
+/// Fails if the input is malformed, has too many junctions or there is no hike from the start to the end.
+pub fn solve_part2(input: &str) -> Result<usize, AocError> {
+    Trails::contract(&parse_input(input)?, false)?
+        .longest_hike()
+        .ok_or_else(no_hike)
+}
+
+/// Parses the map, failing if its first or last row has no path tile.
+fn parse_input(input: &str) -> Result<Grid<u8>, AocError> {
+    let map = input.parse::<Grid<u8>>().map_err(|e| e.in_day(23))?;
+    if map.n_rows() == 0 || [0, map.n_rows() - 1].iter().any(|&r| !map.row(r).contains(&PATH)) {
+        return Err(AocError::invalid("missing path tile at the edge of the map"));
+    }
+    Ok(map)
+}
+
+/// Returns the error of a map without hikes from the start to the end.
+fn no_hike() -> AocError {
+    AocError::invalid("no hike reaches the end")
+}
+
+//----------
+// Helper methods
+//----------
+
+/// Returns the direction a slope forces the hiker to, or `None` for other tiles.
+fn slope(tile: u8) -> Option<Direction> {
+    match tile {
+        b'^' => Some(Direction::North),
+        b'>' => Some(Direction::East),
+        b'v' => Some(Direction::South),
+        b'<' => Some(Direction::West),
+        _ => None,
+    }
+}
+
+/// Returns the position of the only path tile of `row`.
+fn entrance(map: &Grid<u8>, row: usize) -> Position {
+    let col = map
+        .row(row)
+        .iter()
+        .position(|&b| b == PATH)
+        .expect("edge rows are checked when parsing");
+    Position::new(row, col)
+}
+
+//----------
+// Structs
+//----------
+
+/// Graph of the junctions of the trails.
+#[derive(Clone, Debug)]
+struct Trails {
+    /// Junctions reachable from each junction, and the length of the corridor leading to them.
+    edges: Vec<Vec<(usize, usize)>>,
+    start: usize,
+    end: usize,
+}
+
+//----------
+// Implementations
+//----------
+
+impl Trails {
+    /// Contracts the corridors of `map` into a graph of junctions. The start and the end of the hike are junctions too.
+    ///
+    /// With `slippery` slopes, the corridors are only walked downhill.
+    ///
+    /// Fails if there are more than `BitSet::CAPACITY` junctions, which the search can not track.
+    fn contract(map: &Grid<u8>, slippery: bool) -> Result<Trails, AocError> {
+        let (start, end) = (entrance(map, 0), entrance(map, map.n_rows() - 1));
+
+        let open = |pos: Position| map.get(pos).is_some_and(|&b| b != FOREST);
+        let mut junctions: Vec<Position> = map
+            .iter()
+            .filter(|&(pos, &b)| b != FOREST && map.neighbors4(pos).filter(|&p| open(p)).count() >= 3)
+            .map(|(pos, _)| pos)
+            .collect();
+        junctions.extend([start, end]);
+        if junctions.len() > BitSet::<2>::CAPACITY {
+            return Err(AocError::invalid(format!(
+                "the map has more than {} junctions",
+                BitSet::<2>::CAPACITY
+            )));
+        }
+        let ids: HashMap<Position, usize> = junctions.iter().enumerate().map(|(id, &pos)| (pos, id)).collect();
+
+        // next tile when leaving `pos` in `dir`, if the step is allowed
+        let step = |pos: Position, dir: Direction| {
+            let forced = slope(map[pos]).filter(|_| slippery);
+            pos.step(dir)
+                .filter(|&next| open(next) && forced.is_none_or(|f| f == dir))
+        };
+
+        let edges = junctions
+            .iter()
+            .map(|&junction| {
+                Direction::ALL
+                    .into_iter()
+                    .filter_map(|dir| {
+                        // walk the corridor until the next junction, or a dead end
+                        let (mut prev, mut pos, mut len) = (junction, step(junction, dir)?, 1);
+                        while !ids.contains_key(&pos) {
+                            let next = Direction::ALL
+                                .into_iter()
+                                .filter_map(|d| step(pos, d))
+                                .find(|&next| next != prev)?;
+                            (prev, pos, len) = (pos, next, len + 1);
+                        }
+                        Some((ids[&pos], len))
+                    })
+                    .collect()
+            })
+            .collect();
+
+        Ok(Trails {
+            edges,
+            start: ids[&start],
+            end: ids[&end],
+        })
+    }
+
+    /// Returns the length of the longest hike from the start to the end, or `None` if there is none.
+    fn longest_hike(&self) -> Option<usize> {
+        self.longest_from(self.start, &mut BitSet::new())
+    }
+
+    /// Returns the length of the longest hike from `junction` to the end, not crossing the `visited` junctions.
+    fn longest_from(&self, junction: usize, visited: &mut BitSet) -> Option<usize> {
+        if junction == self.end {
+            return Some(0);
+        }
+
+        visited.insert(junction);
+        let mut longest = None;
+        for &(next, len) in &self.edges[junction] {
+            if !visited.contains(next) {
+                let hike = self.longest_from(next, visited).map(|rest| len + rest);
+                longest = longest.max(hike);
+            }
+        }
+        visited.remove(junction);
+
+        longest
+    }
+}
+
+//----------
+// Tests
+//----------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aoc_test;
+
+    const SAMPLE: &str = "#.#####################
+#.......#########...###
+#######.#########.#.###
+###.....#.>.>.###.#.###
+###v#####.#v#.###.#.###
+###.>...#.#.#.....#...#
+###v###.#.#.#########.#
+###...#.#.#.......#...#
+#####.#.#.#######.#.###
+#.....#.#.#.......#...#
+#.#####.#.#.#########v#
+#.#...#...#...###...>.#
+#.#.#v#######v###.###v#
+#...#.>.#...>.>.#.###.#
+#####v#.#.###v#.#.###.#
+#.....#...#...#.#.#...#
+#.#########.###.#.#.###
+#...###...#...#...#.###
+###.###.#.###v#####v###
+#...#...#.#.>.>.#.>.###
+#.###.###.#.###.#.#v###
+#.....###...###...#...#
+#####################.#";
+
+    aoc_test! {
+        sample: SAMPLE,
+        pt1_sample: solve_part1 => 94,
+        pt2_sample: solve_part2 => 154,
+    }
+
+    #[test]
+    fn test_contract() {
+        let map = parse_input(SAMPLE).unwrap();
+        let slippery = Trails::contract(&map, true).unwrap();
+        let flat = Trails::contract(&map, false).unwrap();
+
+        // 7 junctions, plus the start and the end
+        assert_eq!(flat.edges.len(), 9);
+        // every corridor can be walked both ways without slopes, but only downhill with them
+        assert_eq!(flat.edges.iter().map(Vec::len).sum::<usize>(), 24);
+        assert_eq!(slippery.edges.iter().map(Vec::len).sum::<usize>(), 12);
+    }
+
+    #[test]
+    fn test_errors() {
+        let err = solve_part1("#.#\n###").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid input: missing path tile at the edge of the map"
+        );
+
+        let err = solve_part2("#.#\n###\n#.#").unwrap_err();
+        assert_eq!(err.to_string(), "Invalid input: no hike reaches the end");
+
+        // an open field, where every tile away from the corners is a junction
+        let mut rows = vec![format!("#.{}", "#".repeat(12))];
+        rows.extend((0..12).map(|_| format!("#{}#", ".".repeat(12))));
+        rows.push(format!("{}.#", "#".repeat(12)));
+        let err = solve_part2(&rows.join("\n")).unwrap_err();
+        assert_eq!(err.to_string(), "Invalid input: the map has more than 128 junctions");
+    }
+}
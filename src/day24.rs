@@ -0,0 +1,412 @@
+//! Day 24: Never Tell Me The Odds
+//!
+//! Link: https://adventofcode.com/2023/day/24
+//!
+//! # Problem
+//!
+//! Each hailstone has a position and a velocity, like `19, 13, 30 @ -2, 1, -2`, and moves in a straight line.
+//!
+//! 1. Ignoring the z axis, find how many pairs of hailstones have paths that cross in the future inside the test area,
+//!    where x and y are both between 200000000000000 and 400000000000000;
+//!
+//! 2. A rock thrown from some integer position with an integer velocity hits every hailstone. Find the sum of the
+//!    coordinates of its initial position.
+//!
+//! # Solution
+//!
+//! Part 1 intersects the paths of every pair of hailstones, solving a 2x2 system with Cramer's rule. Coordinates are
+//! around 10^14, too close to the limits of `f64` to trust crossings near the edges of the test area, so a quick
+//! floating point test only discards the pairs clearly crossing elsewhere, and the remaining ones are checked exactly
+//! with `math::Rational`.
+//!
+//! For part 2, the rock `P + t * V` hits hailstone `p_i + t * v_i` at some time, so `P - p_i` and `V - v_i` are
+//! parallel and `(P - p_i) × (V - v_i) = 0`. Expanding, the only non-linear term `P × V` is the same for every
+//! hailstone, so subtracting the equations of two hailstones leaves 3 linear equations in the 6 unknowns. Two pairs
+//! give a square system, solved exactly over `i128` by `math::solve_linear_system_integer`.
+
+use crate::{
+    answer::Answer,
+    combinator::{complete, integer, pair, preceded, space, tag},
+    error::AocError,
+    iter_ext::IterExt,
+    math::{self, Rational},
+};
+
+/// Bounds of the test area of part 1, for both x and y.
+const TEST_AREA: (i64, i64) = (200000000000000, 400000000000000);
+
+/// Margin of the floating point pre-filter of part 1, relative to the size of the test area.
+const TOLERANCE: f64 = 1e-6;
+
+pub fn run(input: &str) -> Result<[Answer; 2], AocError> {
+    Ok([solve_part1(input)?.into(), solve_part2(input)?.into()])
+}
+
+/// Returns the number of pairs of hailstones whose paths cross in the future inside the test area.
+///
+/// # Errors
+///
+/// Fails if the input is malformed, or the exact crossings overflow.
+pub fn solve_part1(input: &str) -> Result<usize, AocError> {
+    crossings_within(&parse_input(input)?, TEST_AREA)
+}
+
+/// Returns the sum of the coordinates of the initial position of the rock hitting every hailstone.
+///
+/// # Errors
+///
+/// Fails if the input is malformed, there is no such rock or the sum overflows.
+pub fn solve_part2(input: &str) -> Result<i64, AocError> {
+    let rock = throw_rock(&parse_input(input)?).ok_or_else(|| AocError::invalid("no rock hits every hailstone"))?;
+    rock.pos.into_iter().checked_sum().ok_or_else(AocError::overflow)
+}
+
+fn parse_input(input: &str) -> Result<Vec<Hailstone>, AocError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| Hailstone::parse(line).map_err(|e| e.at_line(i + 1).in_day(24)))
+        .collect()
+}
+
+//----------
+// Helper methods
+//----------
+
+/// Returns the number of pairs of hailstones whose paths cross in the future within `[min, max]` in x and y.
+///
+/// Fails if the exact crossing of a pair overflows.
+fn crossings_within(hailstones: &[Hailstone], (min, max): (i64, i64)) -> Result<usize, AocError> {
+    // the pre-filter accepts crossings slightly outside the area, or slightly in the past
+    let margin = (max - min) as f64 * TOLERANCE;
+    let maybe_inside = |v: f64| (min as f64 - margin..=max as f64 + margin).contains(&v);
+    let (min, max) = (Rational::from(min), Rational::from(max));
+    let inside = |v: Rational| -> Result<bool, AocError> {
+        min.checked_cmp(&v)
+            .zip(v.checked_cmp(&max))
+            .map(|(low, high)| low.is_le() && high.is_le())
+            .ok_or_else(AocError::overflow)
+    };
+
+    hailstones
+        .iter()
+        .enumerate()
+        .flat_map(|(i, a)| hailstones[i + 1..].iter().map(move |b| (a, b)))
+        .filter(|(a, b)| {
+            a.crossing_xy(b, -margin)
+                .is_some_and(|(x, y)| maybe_inside(x) && maybe_inside(y))
+        })
+        .try_fold(0, |count, (a, b)| {
+            let hit = match a.crossing_xy_exact(b)? {
+                Some((x, y)) => inside(x)? && inside(y)?,
+                None => false,
+            };
+            Ok(count + hit as usize)
+        })
+}
+
+/// Returns the rock that hits every hailstone, or `None` if there is no such rock with integer position and velocity.
+///
+/// The rock is computed from three consecutive hailstones, moving on to the next ones when their system is singular.
+fn throw_rock(hailstones: &[Hailstone]) -> Option<Hailstone> {
+    hailstones
+        .windows(3)
+        .filter_map(|w| rock_hitting(&w[0], &w[1], &w[2]))
+        .find(|rock| hailstones.iter().all(|h| rock.hits(h)))
+}
+
+/// Returns the rock with integer position and velocity that hits `h0`, `h1` and `h2`, if the system has a unique
+/// integer solution that fits in `i64` and no coefficient overflows.
+fn rock_hitting(h0: &Hailstone, h1: &Hailstone, h2: &Hailstone) -> Option<Hailstone> {
+    // positions relative to the first hailstone keep the coefficients small
+    let relative = |h: &Hailstone| -> ([i128; 3], [i128; 3]) {
+        (
+            [0, 1, 2].map(|k| i128::from(h.pos[k]) - i128::from(h0.pos[k])),
+            h.vel.map(i128::from),
+        )
+    };
+
+    let (p0, v0) = relative(h0);
+    let mut a = Vec::with_capacity(6);
+    let mut b = Vec::with_capacity(6);
+    for (pj, vj) in [relative(h1), relative(h2)] {
+        // P × (vj - v0) + (pj - p0) × V = pj × vj - p0 × v0
+        let w = [0, 1, 2].map(|k| vj[k] - v0[k]);
+        let d = [0, 1, 2].map(|k| pj[k] - p0[k]);
+        let rhs = sub(cross(pj, vj)?, cross(p0, v0)?)?;
+
+        a.push(vec![0, w[2], -w[1], 0, -d[2], d[1]]);
+        a.push(vec![-w[2], 0, w[0], d[2], 0, -d[0]]);
+        a.push(vec![w[1], -w[0], 0, -d[1], d[0], 0]);
+        b.extend(rhs);
+    }
+
+    let x = math::solve_linear_system_integer(&a, &b)?;
+    let unknown = |k: usize| i64::try_from(x[k]).ok();
+    let position = |k: usize| i64::try_from(x[k].checked_add(h0.pos[k].into())?).ok();
+    Some(Hailstone {
+        pos: [position(0)?, position(1)?, position(2)?],
+        vel: [unknown(3)?, unknown(4)?, unknown(5)?],
+    })
+}
+
+/// Returns the cross product `a × b`, or `None` if it overflows.
+fn cross(a: [i128; 3], b: [i128; 3]) -> Option<[i128; 3]> {
+    let det = |i: usize, j: usize| a[i].checked_mul(b[j])?.checked_sub(a[j].checked_mul(b[i])?);
+    Some([det(1, 2)?, det(2, 0)?, det(0, 1)?])
+}
+
+/// Returns the difference `a - b`, or `None` if it overflows.
+fn sub(a: [i128; 3], b: [i128; 3]) -> Option<[i128; 3]> {
+    Some([
+        a[0].checked_sub(b[0])?,
+        a[1].checked_sub(b[1])?,
+        a[2].checked_sub(b[2])?,
+    ])
+}
+
+//----------
+// Structs
+//----------
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Hailstone {
+    pos: [i64; 3],
+    vel: [i64; 3],
+}
+
+//----------
+// Implementations
+//----------
+
+impl Hailstone {
+    /// Parses a hailstone like "19, 13, 30 @ -2, 1, -2".
+    fn parse(line: &str) -> Result<Hailstone, AocError> {
+        let next = || preceded(pair(tag(","), space), integer::<i64>);
+        let triple = || pair(integer::<i64>, pair(next(), next()));
+        let separator = pair(space, pair(tag("@"), space));
+
+        let ((px, (py, pz)), (vx, (vy, vz))) = complete(line, pair(triple(), preceded(separator, triple())))?;
+        Ok(Hailstone {
+            pos: [px, py, pz],
+            vel: [vx, vy, vz],
+        })
+    }
+
+    /// Returns approximately where the paths of `self` and `other` cross, ignoring the z axis, or `None` if they do not
+    /// or cross before `min_time`.
+    fn crossing_xy(&self, other: &Hailstone, min_time: f64) -> Option<(f64, f64)> {
+        let [px, py, _] = self.pos.map(|v| v as f64);
+        let [vx, vy, _] = self.vel.map(|v| v as f64);
+        let [qx, qy, _] = other.pos.map(|v| v as f64);
+        let [wx, wy, _] = other.vel.map(|v| v as f64);
+
+        // p + t * v = q + s * w
+        let det = vx * wy - vy * wx;
+        if det == 0.0 {
+            return None;
+        }
+        let (dx, dy) = (qx - px, qy - py);
+        let t = (dx * wy - dy * wx) / det;
+        let s = (dx * vy - dy * vx) / det;
+
+        (t >= min_time && s >= min_time).then_some((px + t * vx, py + t * vy))
+    }
+
+    /// Returns exactly where the paths of `self` and `other` cross in the future, ignoring the z axis, or `None` if
+    /// they do not.
+    ///
+    /// Fails if the computation overflows, which takes velocities around 10^15.
+    fn crossing_xy_exact(&self, other: &Hailstone) -> Result<Option<(Rational, Rational)>, AocError> {
+        let [px, py, _] = self.pos.map(i128::from);
+        let [vx, vy, _] = self.vel.map(i128::from);
+        let [qx, qy, _] = other.pos.map(i128::from);
+        let [wx, wy, _] = other.vel.map(i128::from);
+        let cross = |a: i128, b: i128, c: i128, d: i128| {
+            a.checked_mul(b)
+                .zip(c.checked_mul(d))
+                .and_then(|(ab, cd)| ab.checked_sub(cd))
+                .ok_or_else(AocError::overflow)
+        };
+
+        let det = cross(vx, wy, vy, wx)?;
+        if det == 0 {
+            return Ok(None);
+        }
+        let (dx, dy) = (qx - px, qy - py);
+        let t = Rational::new(cross(dx, wy, dy, wx)?, det);
+        let s = Rational::new(cross(dx, vy, dy, vx)?, det);
+
+        // the denominators are positive, so the signs of the times are the ones of their numerators
+        if t.numer() < 0 || s.numer() < 0 {
+            return Ok(None);
+        }
+        let at = |p: i128, v: i128| {
+            t.checked_mul(v.into())
+                .and_then(|tv| Rational::from(p).checked_add(tv))
+                .ok_or_else(AocError::overflow)
+        };
+        Ok(Some((at(px, vx)?, at(py, vy)?)))
+    }
+
+    /// Checks whether `self` and `other` are at the same position at the same non-negative time.
+    fn hits(&self, other: &Hailstone) -> bool {
+        let mut time = None;
+        for k in 0..3 {
+            // widened, since the differences of two `i64` may not fit in one
+            let dp = i128::from(other.pos[k]) - i128::from(self.pos[k]);
+            let dv = i128::from(self.vel[k]) - i128::from(other.vel[k]);
+            match (dv, dp) {
+                (0, 0) => continue,
+                (0, _) => return false,
+                _ if dp % dv != 0 || dp / dv < 0 => return false,
+                _ if *time.get_or_insert(dp / dv) != dp / dv => return false,
+                _ => {}
+            }
+        }
+        true
+    }
+}
+
+//----------
+// Tests
+//----------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aoc_test;
+
+    const SAMPLE: &str = "19, 13, 30 @ -2,  1, -2
+18, 19, 22 @ -1, -1, -2
+20, 25, 34 @ -2, -2, -4
+12, 31, 28 @ -1, -2, -1
+20, 19, 15 @  1, -5, -3";
+
+    aoc_test! {
+        sample: SAMPLE,
+        pt2_sample: solve_part2 => 47,
+    }
+
+    #[test]
+    fn test_crossings() {
+        let hailstones = parse_input(SAMPLE).unwrap();
+        assert_eq!(crossings_within(&hailstones, (7, 27)).unwrap(), 2);
+
+        let (x, y) = hailstones[0].crossing_xy(&hailstones[1], 0.0).unwrap();
+        assert!((x - 14.333).abs() < 1e-3 && (y - 15.333).abs() < 1e-3);
+        let exact = hailstones[0].crossing_xy_exact(&hailstones[1]).unwrap();
+        assert_eq!(exact, Some((Rational::new(43, 3), Rational::new(46, 3))));
+        // parallel paths
+        assert_eq!(hailstones[1].crossing_xy(&hailstones[2], 0.0), None);
+        assert_eq!(hailstones[1].crossing_xy_exact(&hailstones[2]).unwrap(), None);
+        // crossing in the past of the first hailstone
+        assert_eq!(hailstones[0].crossing_xy(&hailstones[4], 0.0), None);
+        assert_eq!(hailstones[0].crossing_xy_exact(&hailstones[4]).unwrap(), None);
+
+        // crossings exactly on the edge of the test area, at the magnitudes of the inputs, are inside
+        let (min, max) = TEST_AREA;
+        let a = Hailstone {
+            pos: [min - 3 * 97, max - 5, 0],
+            vel: [97, 1, 0],
+        };
+        let b = Hailstone {
+            pos: [min, max + 5 * 89, 0],
+            vel: [0, -89, 0],
+        };
+        assert_eq!(a.crossing_xy_exact(&b).unwrap(), Some((min.into(), (max - 2).into())));
+        assert_eq!(crossings_within(&[a, b], TEST_AREA).unwrap(), 1);
+        assert_eq!(crossings_within(&[a, b], (min + 1, max)).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_throw_rock() {
+        let rock = throw_rock(&parse_input(SAMPLE).unwrap()).unwrap();
+        assert_eq!(
+            rock,
+            Hailstone {
+                pos: [24, 13, 10],
+                vel: [-3, 1, 2]
+            }
+        );
+
+        // magnitudes of the puzzle inputs: the rock hits each hailstone after about 10^11 or 10^12 nanoseconds
+        let rock = Hailstone {
+            pos: [287430900705823, 451620998712421, 260730677041648],
+            vel: [-43, -273, 71],
+        };
+        let hailstones: Vec<_> = [
+            (123456789012, [-12, 340, -85]),
+            (987654321098, [113, -27, 44]),
+            (456789012345, [-208, 181, -3]),
+            (654321098765, [37, -390, 122]),
+        ]
+        .into_iter()
+        .map(|(t, vel)| Hailstone {
+            pos: [0, 1, 2].map(|k| rock.pos[k] + t * (rock.vel[k] - vel[k])),
+            vel,
+        })
+        .collect();
+        assert_eq!(throw_rock(&hailstones), Some(rock));
+    }
+
+    #[test]
+    fn test_crossing_overflow() {
+        // velocities around 10^15 overflow the exact crossing, which used to wrap around in release builds
+        let input = "300000000000000, 300000000000000, 0 @ 1000000000000007, 1, 0
+300000000000000, 300000000001000, 0 @ 1000000000000003, -999999999999991, 0";
+        let err = solve_part1(input).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid input: the numbers are too large, the answer overflows"
+        );
+    }
+
+    #[test]
+    fn test_overflow() {
+        let input = "-9223372036854775808, -9223372036854775808, -9223372036854775808 @ 1, 2, 3
+9223372036854775807, 9223372036854775807, 9223372036854775807 @ -1, -2, -3
+0, 0, 0 @ 9223372036854775807, -9223372036854775808, 1";
+        assert!(solve_part1(input).is_ok());
+        assert!(solve_part2(input).is_err());
+
+        let far = Hailstone {
+            pos: [i64::MIN, 0, 0],
+            vel: [1, 0, 0],
+        };
+        let near = Hailstone {
+            pos: [i64::MAX - 1, 0, 0],
+            vel: [-1, 0, 0],
+        };
+        assert!(far.hits(&near));
+
+        // the rock exists, but the sum of its coordinates does not fit in `i64`
+        let rock = Hailstone {
+            pos: [i64::MAX - 100, i64::MAX - 100, 0],
+            vel: [-1, 0, 0],
+        };
+        let input: String = [[2, 3, 5], [-7, 11, 1], [13, -2, 4]]
+            .iter()
+            .enumerate()
+            .map(|(t, vel)| {
+                let t = t as i64 + 1;
+                let pos = [0, 1, 2].map(|k| rock.pos[k] + t * (rock.vel[k] - vel[k]));
+                format!(
+                    "{}, {}, {} @ {}, {}, {}\n",
+                    pos[0], pos[1], pos[2], vel[0], vel[1], vel[2]
+                )
+            })
+            .collect();
+        assert_eq!(throw_rock(&parse_input(&input).unwrap()), Some(rock));
+        assert_eq!(
+            solve_part2(&input).unwrap_err().to_string(),
+            "Invalid input: the numbers are too large, the answer overflows"
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let err = Hailstone::parse("19, 13, 30 -2, 1, -2").unwrap_err();
+        assert_eq!(err.to_string(), "Line 1, column 12: Unexpected `-`, expected `@`.");
+    }
+}
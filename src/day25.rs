@@ -0,0 +1,274 @@
+//! Day 25: Snowverload
+//!
+//! Link: https://adventofcode.com/2023/day/25
+//!
+//! # Problem
+//!
+//! Each line lists a component and the components connected to it, like `jqt: rhn xhk nvd`. Connections work both
+//! ways. Disconnecting exactly three wires splits the components into two separate groups.
+//!
+//! 1. Find the product of the sizes of the two groups.
+//!
+//! # Solution
+//!
+//! The three wires are a minimum cut of the graph. By the max-flow min-cut theorem, with every wire carrying one unit
+//! of flow, the maximum flow between two components in different groups is 3, while between components of the same
+//! group it is at least 4. So the flow is computed from a fixed source to every other component, with Edmonds-Karp
+//! stopping after the fourth augmenting path, until a sink with a flow of 3 is found. The components still reachable
+//! from the source in the residual graph are then its group.
+//!
+//! [`run_with`] can use Karger's randomized algorithm instead, selected with `aoc2023 25 --strategy karger`: edges are
+//! contracted in a random order until two groups are left. A contraction only finds the minimum cut with some
+//! probability, so the cut is verified to have exactly three wires, retrying with another order otherwise.
+
+use crate::{
+    answer::Answer,
+    combinator::{complete, pair, separated_list, tag, terminated, word},
+    dsu::Dsu,
+    error::AocError,
+    graph::Graph,
+    rng::Rng,
+};
+use std::{
+    collections::{HashMap, VecDeque},
+    str::FromStr,
+};
+
+/// Number of wires to disconnect.
+const CUT_SIZE: usize = 3;
+
+/// Contractions tried by Karger's algorithm before giving up.
+const MAX_TRIALS: usize = 10_000;
+
+pub fn run(input: &str) -> Result<[Answer; 2], AocError> {
+    run_with(input, MinCut::Flow)
+}
+
+/// Solves the puzzle, finding the cut with the given algorithm.
+///
+/// # Errors
+///
+/// Fails if the input is malformed or no three wires split the components.
+pub fn run_with(input: &str, min_cut: MinCut) -> Result<[Answer; 2], AocError> {
+    Ok([
+        groups_product(input, min_cut)?.into(),
+        "Push the big red button!".into(),
+    ])
+}
+
+/// Returns the product of the sizes of the two groups left after disconnecting three wires.
+///
+/// # Errors
+///
+/// Fails if the input is malformed or no three wires split the components.
+pub fn solve_part1(input: &str) -> Result<usize, AocError> {
+    groups_product(input, MinCut::Flow)
+}
+
+/// Returns the product of the sizes of the two groups, finding the cut with `min_cut`.
+fn groups_product(input: &str, min_cut: MinCut) -> Result<usize, AocError> {
+    let graph = parse_input(input)?;
+    let group = match min_cut {
+        MinCut::Flow => min_cut_group(&graph, CUT_SIZE),
+        MinCut::Karger => karger_group(&graph, CUT_SIZE, &mut Rng::from_entropy()),
+    };
+    let group = group.ok_or_else(|| AocError::invalid("no three wires split the components in two groups"))?;
+    Ok(group * (graph.len() - group))
+}
+
+fn parse_input(input: &str) -> Result<Graph, AocError> {
+    parse_graph(input).map_err(|e| e.in_day(25))
+}
+
+/// Parses the connections into an undirected graph, with edges in both directions.
+fn parse_graph(input: &str) -> Result<Graph, AocError> {
+    let parser = pair(terminated(word, tag(": ")), separated_list(word, tag(" ")));
+
+    let mut graph = Graph::new();
+    for (i, line) in input.lines().enumerate() {
+        let (from, to) = complete(line, &parser).map_err(|e| e.at_line(i + 1))?;
+        for to in to {
+            let (from, to) = graph.add_edge(from, to);
+            graph.add_edge_ids(to, from);
+        }
+    }
+
+    Ok(graph)
+}
+
+//----------
+// Structs
+//----------
+
+/// Algorithm finding the wires to disconnect.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MinCut {
+    /// Maximum flows between a fixed source and every other component, deterministic.
+    Flow,
+    /// Karger's randomized contractions, verified and retried.
+    Karger,
+}
+
+impl FromStr for MinCut {
+    type Err = AocError;
+
+    /// Parses the name of an algorithm, `flow` or `karger`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "flow" => Ok(MinCut::Flow),
+            "karger" => Ok(MinCut::Karger),
+            _ => Err(AocError::invalid(format!(
+                "unknown min cut algorithm `{s}`, expected `flow` or `karger`"
+            ))),
+        }
+    }
+}
+
+//----------
+// Helper methods
+//----------
+
+/// Returns the size of one of the two groups left by a cut of exactly `cut_size` edges, or `None` if the minimum cut
+/// of the graph has a different size.
+fn min_cut_group(graph: &Graph, cut_size: usize) -> Option<usize> {
+    let source = graph.nodes().next()?;
+
+    for sink in graph.nodes().skip(1) {
+        // flow carried by each directed edge, the flow in the opposite direction is its negation
+        let mut flow = HashMap::new();
+        let mut n_paths = 0;
+
+        let group = loop {
+            let parents = residual_bfs(graph, &flow, source);
+            if parents[sink as usize].is_none() || n_paths > cut_size {
+                break parents.iter().filter(|p| p.is_some()).count();
+            }
+
+            // push one unit of flow along the path
+            let mut node = sink;
+            while node != source {
+                let parent = parents[node as usize].unwrap();
+                *flow.entry((parent, node)).or_insert(0) += 1;
+                *flow.entry((node, parent)).or_insert(0) -= 1;
+                node = parent;
+            }
+            n_paths += 1;
+        };
+
+        if n_paths == cut_size {
+            return Some(group);
+        }
+        if n_paths < cut_size {
+            return None;
+        }
+    }
+
+    None
+}
+
+/// Returns the parent of every node in a breadth-first search from `source` over the edges with spare capacity, or
+/// `None` for the nodes not reached. The parent of `source` is itself.
+fn residual_bfs(graph: &Graph, flow: &HashMap<(u32, u32), i32>, source: u32) -> Vec<Option<u32>> {
+    let mut parents = vec![None; graph.len()];
+    parents[source as usize] = Some(source);
+    let mut queue = VecDeque::from([source]);
+
+    while let Some(node) = queue.pop_front() {
+        for &next in graph.neighbors(node) {
+            let spare = flow.get(&(node, next)).copied().unwrap_or(0) < 1;
+            if spare && parents[next as usize].is_none() {
+                parents[next as usize] = Some(node);
+                queue.push_back(next);
+            }
+        }
+    }
+
+    parents
+}
+
+/// Returns the size of one of the two groups left by a cut of exactly `cut_size` edges, found with Karger's
+/// algorithm, or `None` if no contraction out of `MAX_TRIALS` finds one.
+fn karger_group(graph: &Graph, cut_size: usize, rng: &mut Rng) -> Option<usize> {
+    // every wire once, as the graph has edges in both directions
+    let mut edges: Vec<_> = graph.edges().filter(|&(from, to)| from < to).collect();
+
+    for _ in 0..MAX_TRIALS {
+        rng.shuffle(&mut edges);
+
+        let mut groups = Dsu::new(graph.len());
+        for &(from, to) in &edges {
+            if groups.n_sets() == 2 {
+                break;
+            }
+            groups.union(from as usize, to as usize);
+        }
+
+        let cut = edges
+            .iter()
+            .filter(|&&(from, to)| !groups.same(from as usize, to as usize))
+            .count();
+        if cut == cut_size {
+            return Some(groups.size_of(0));
+        }
+    }
+
+    None
+}
+
+//----------
+// Tests
+//----------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aoc_test;
+
+    const SAMPLE: &str = "jqt: rhn xhk nvd
+rsh: frs pzl lsr
+xhk: hfx
+cmg: qnr nvd lhk bvb
+rhn: xhk bvb hfx
+bvb: xhk hfx
+pzl: lsr hfx nvd
+qnr: nvd
+ntq: jqt hfx bvb xhk
+nvd: lhk
+lsr: lhk
+rzs: qnr cmg lsr rsh
+frs: qnr lhk lsr";
+
+    aoc_test! {
+        sample: SAMPLE,
+        pt1_sample: solve_part1 => 54,
+    }
+
+    #[test]
+    fn test_min_cut() {
+        let graph = parse_input(SAMPLE).unwrap();
+        assert_eq!(graph.len(), 15);
+        assert_eq!(graph.n_edges(), 2 * 33);
+
+        // the groups have 9 and 6 components
+        let group = min_cut_group(&graph, CUT_SIZE).unwrap();
+        assert!(group == 9 || group == 6);
+        // the graph is too well connected for smaller cuts
+        assert_eq!(min_cut_group(&graph, 2), None);
+    }
+
+    #[test]
+    fn test_karger() {
+        let graph = parse_input(SAMPLE).unwrap();
+        for seed in 1..=10 {
+            let group = karger_group(&graph, CUT_SIZE, &mut Rng::new(seed)).unwrap();
+            assert!(group == 9 || group == 6, "seed {seed}");
+        }
+        assert_eq!(karger_group(&graph, 2, &mut Rng::new(1)), None);
+
+        assert_eq!(
+            run_with(SAMPLE, "karger".parse().unwrap()).unwrap(),
+            run(SAMPLE).unwrap()
+        );
+        assert!("random".parse::<MinCut>().is_err());
+    }
+}
@@ -0,0 +1,157 @@
+//! Disjoint set union (union-find) over the elements `0..n`.
+//!
+//! Uses path compression and union by rank, so any sequence of operations runs in nearly constant amortized time per
+//! operation.
+
+//----------
+// Structs
+//----------
+
+/// Partition of the elements `0..n` into disjoint sets.
+#[derive(Clone, Debug)]
+pub struct Dsu {
+    parent: Vec<usize>,
+    rank: Vec<u8>,
+    size: Vec<usize>,
+    n_sets: usize,
+}
+
+//----------
+// Implementations
+//----------
+
+impl Dsu {
+    /// Creates `n` singleton sets, one for each element in `0..n`.
+    pub fn new(n: usize) -> Dsu {
+        Dsu {
+            parent: (0..n).collect(),
+            rank: vec![0; n],
+            size: vec![1; n],
+            n_sets: n,
+        }
+    }
+
+    /// Returns the total number of elements.
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    /// Checks whether there are no elements.
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+
+    /// Returns the number of disjoint sets.
+    pub fn n_sets(&self) -> usize {
+        self.n_sets
+    }
+
+    /// Returns the representative element of the set containing `x`.
+    pub fn find(&mut self, x: usize) -> usize {
+        let mut root = x;
+        while self.parent[root] != root {
+            root = self.parent[root];
+        }
+
+        // path compression: point every element on the way directly to the root
+        let mut x = x;
+        while self.parent[x] != root {
+            (x, self.parent[x]) = (self.parent[x], root);
+        }
+
+        root
+    }
+
+    /// Joins the sets containing `a` and `b`. Returns `false` if they were already in the same set.
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (mut a, mut b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+
+        // attach the shallower tree below the deeper one
+        if self.rank[a] < self.rank[b] {
+            (a, b) = (b, a);
+        }
+        if self.rank[a] == self.rank[b] {
+            self.rank[a] += 1;
+        }
+
+        self.parent[b] = a;
+        self.size[a] += self.size[b];
+        self.n_sets -= 1;
+        true
+    }
+
+    /// Checks whether `a` and `b` belong to the same set.
+    pub fn same(&mut self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+
+    /// Returns the number of elements in the set containing `x`.
+    pub fn size_of(&mut self, x: usize) -> usize {
+        let root = self.find(x);
+        self.size[root]
+    }
+
+    /// Returns an iterator over the sets, each one given as the `Vec` of its elements in increasing order.
+    ///
+    /// Sets are yielded in increasing order of their smallest element.
+    pub fn components(&mut self) -> impl Iterator<Item = Vec<usize>> {
+        let mut index = vec![usize::MAX; self.len()];
+        let mut components: Vec<Vec<usize>> = Vec::with_capacity(self.n_sets);
+
+        for x in 0..self.len() {
+            let root = self.find(x);
+            if index[root] == usize::MAX {
+                index[root] = components.len();
+                components.push(Vec::with_capacity(self.size[root]));
+            }
+            components[index[root]].push(x);
+        }
+
+        components.into_iter()
+    }
+}
+
+//----------
+// Tests
+//----------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tests() {
+        let mut dsu = Dsu::new(6);
+        assert_eq!(dsu.n_sets(), 6);
+
+        assert!(dsu.union(0, 1));
+        assert!(dsu.union(2, 3));
+        assert!(dsu.union(1, 3));
+        assert!(!dsu.union(0, 2));
+
+        assert!(dsu.same(0, 3));
+        assert!(!dsu.same(0, 4));
+        assert_eq!(dsu.size_of(2), 4);
+        assert_eq!(dsu.size_of(5), 1);
+        assert_eq!(dsu.n_sets(), 3);
+
+        let components: Vec<_> = dsu.components().collect();
+        assert_eq!(components, [vec![0, 1, 2, 3], vec![4], vec![5]]);
+    }
+
+    #[test]
+    fn test_long_chain() {
+        let n = 100_000;
+        let mut dsu = Dsu::new(n);
+        (1..n).for_each(|i| {
+            dsu.union(i - 1, i);
+        });
+
+        assert_eq!(dsu.n_sets(), 1);
+        assert_eq!(dsu.size_of(n / 2), n);
+        assert!(dsu.same(0, n - 1));
+    }
+}
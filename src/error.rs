@@ -0,0 +1,198 @@
+//! Error type shared by the input parsers of all days.
+//!
+//! Line and column numbers start at 1. A parser that only sees a single line (like most `FromStr` implementations)
+//! reports its errors at line 1, and the functions parsing a whole input move them to the right line with
+//! [`AocError::at_line`] and tag them with the day using [`AocError::in_day`].
+
+use std::{fmt::Display, io, num::ParseIntError, path::PathBuf};
+use thiserror::Error;
+
+/// Errors raised while loading or parsing a puzzle input.
+#[derive(Debug, Error)]
+pub enum AocError {
+    /// The input does not follow the expected format.
+    #[error("{}: {message}", location(*day, *line, *column))]
+    ParseError {
+        day: Option<u8>,
+        line: usize,
+        column: Option<usize>,
+        message: String,
+    },
+
+    /// An input file could not be read.
+    #[error("Not able to read `{}`: {source}", path.display())]
+    IoError { path: PathBuf, source: io::Error },
+
+    /// The input is well formed, but does not describe a valid puzzle.
+    #[error("Invalid input: {0}")]
+    InvalidInput(String),
+}
+
+impl AocError {
+    /// Creates a parse error at line 1, without a known column.
+    pub fn parse(message: impl Display) -> AocError {
+        AocError::ParseError {
+            day: None,
+            line: 1,
+            column: None,
+            message: message.to_string(),
+        }
+    }
+
+    /// Creates a parse error pointing to `token`, which must be a slice of the line `s` for the column to be known.
+    pub fn parse_at(s: &str, token: &str, message: impl Display) -> AocError {
+        AocError::ParseError {
+            day: None,
+            line: 1,
+            column: column_of(s, token),
+            message: message.to_string(),
+        }
+    }
+
+    /// Creates a parse error pointing to the byte `offset` of `source`, which may span several lines.
+    pub fn parse_at_offset(source: &str, offset: usize, message: impl Display) -> AocError {
+        AocError::parse(message).at_offset(source, offset)
+    }
+
+    /// Creates an error for a file that could not be read.
+    pub fn io(path: impl Into<PathBuf>, source: io::Error) -> AocError {
+        AocError::IoError {
+            path: path.into(),
+            source,
+        }
+    }
+
+    /// Creates an error for an input that is well formed but not valid.
+    pub fn invalid(description: impl Display) -> AocError {
+        AocError::InvalidInput(description.to_string())
+    }
+
+    /// Creates an error for an input whose numbers are so large that the answer, or a step towards it, overflows.
+    pub fn overflow() -> AocError {
+        AocError::invalid("the numbers are too large, the answer overflows")
+    }
+
+    /// Sets the day of a parse error.
+    pub fn in_day(mut self, day: u8) -> AocError {
+        if let AocError::ParseError { day: d, .. } = &mut self {
+            *d = Some(day);
+        }
+        self
+    }
+
+    /// Moves a parse error found when parsing a block starting at `line` of a larger input, so it points to the same
+    /// place in the larger input.
+    pub fn at_line(mut self, line: usize) -> AocError {
+        if let AocError::ParseError { line: l, .. } = &mut self {
+            *l += line - 1;
+        }
+        self
+    }
+
+    /// Moves a parse error found when parsing `inner`, a slice of the line `outer`, so its column is relative to
+    /// `outer`. Errors without a column point to the start of `inner`.
+    pub fn within(self, outer: &str, inner: &str) -> AocError {
+        match column_of(outer, inner) {
+            Some(start) => self.at_offset(outer, start - 1),
+            None => self,
+        }
+    }
+
+    /// Moves a parse error found when parsing a slice of `source` starting at byte `offset`, so it points to the same
+    /// place in `source`. Errors without a column point to the start of the slice.
+    pub fn at_offset(mut self, source: &str, offset: usize) -> AocError {
+        if let AocError::ParseError { line, column, .. } = &mut self {
+            let before = &source[..offset];
+            let start_line = before.matches('\n').count() + 1;
+            let start_column = offset - before.rfind('\n').map_or(0, |i| i + 1) + 1;
+
+            if *line == 1 {
+                *column = Some(column.map_or(start_column, |c| start_column + c - 1));
+            }
+            *line += start_line - 1;
+        }
+        self
+    }
+}
+
+impl From<ParseIntError> for AocError {
+    fn from(e: ParseIntError) -> Self {
+        AocError::parse(e)
+    }
+}
+
+/// Returns the column where `token` starts, if it is a slice of `s`.
+fn column_of(s: &str, token: &str) -> Option<usize> {
+    let offset = (token.as_ptr() as usize).checked_sub(s.as_ptr() as usize)?;
+    (offset + token.len() <= s.len()).then_some(offset + 1)
+}
+
+/// Formats the position of a parse error, for example "Day 04, line 3, column 7".
+fn location(day: Option<u8>, line: usize, column: Option<usize>) -> String {
+    let line = match day {
+        Some(day) => format!("Day {day:02}, line {line}"),
+        None => format!("Line {line}"),
+    };
+    match column {
+        Some(column) => format!("{line}, column {column}"),
+        None => line,
+    }
+}
+
+//----------
+// Tests
+//----------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        let line = "Card 1: 41 x8 | 83";
+        let err = AocError::parse_at(line, &line[11..13], "Not a number.");
+        assert_eq!(err.to_string(), "Line 1, column 12: Not a number.");
+        assert_eq!(
+            err.at_line(5).in_day(4).to_string(),
+            "Day 04, line 5, column 12: Not a number."
+        );
+
+        let err = AocError::parse("Missing separator.").at_line(3);
+        assert_eq!(err.to_string(), "Line 3: Missing separator.");
+
+        // tokens that are not part of the line have no column
+        assert_eq!(
+            AocError::parse_at(line, &String::from("x8"), "Oops.").to_string(),
+            "Line 1: Oops."
+        );
+
+        let err = AocError::io("inputs/day99", io::Error::from(io::ErrorKind::NotFound));
+        assert!(err.to_string().starts_with("Not able to read `inputs/day99`"));
+        assert_eq!(
+            AocError::invalid("no start tile").in_day(10).to_string(),
+            "Invalid input: no start tile"
+        );
+    }
+
+    #[test]
+    fn test_within() {
+        let line = "Game 1: 3 blue, 4 rad";
+        let cube = &line[16..];
+        let err = AocError::parse_at(cube, &cube[2..], "Unknown color.").within(line, cube);
+        assert_eq!(err.to_string(), "Line 1, column 19: Unknown color.");
+
+        let err = AocError::parse("Unknown color.").within(line, cube);
+        assert_eq!(err.to_string(), "Line 1, column 17: Unknown color.");
+    }
+
+    #[test]
+    fn test_at_offset() {
+        let source = "seeds: 1 2\n\nmap:\n1 x 3";
+        let err = AocError::parse_at_offset(source, 19, "Not a number.");
+        assert_eq!(err.to_string(), "Line 4, column 3: Not a number.");
+
+        // errors past the first line of the slice keep their own column
+        let err = AocError::parse("Missing map.").at_line(2).at_offset(source, 7);
+        assert_eq!(err.to_string(), "Line 2: Missing map.");
+    }
+}
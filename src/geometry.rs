@@ -0,0 +1,205 @@
+//! Geometry over lattice points: polygon areas, point counting and point-in-polygon tests.
+//!
+//! Points are `(x, y)` pairs of integers, and polygons are given by their vertices in order (either clockwise or
+//! counterclockwise), without repeating the first vertex at the end.
+
+/// A point on the integer lattice.
+pub type Point = (i64, i64);
+
+/// Returns twice the area of a polygon, computed exactly with the shoelace formula.
+pub fn double_polygon_area(points: &[Point]) -> i64 {
+    let n = points.len();
+    (0..n)
+        .map(|i| {
+            let (x1, y1) = points[i];
+            let (x2, y2) = points[(i + 1) % n];
+            x1 * y2 - x2 * y1
+        })
+        .sum::<i64>()
+        .abs()
+}
+
+/// Returns the area of a polygon using the shoelace formula.
+///
+/// The result is exact whenever the area is an integer, which is always the case for rectilinear polygons (all edges
+/// horizontal or vertical); otherwise it is rounded down.
+pub fn polygon_area(points: &[Point]) -> i64 {
+    double_polygon_area(points) / 2
+}
+
+/// Returns the area of a polygon like [`polygon_area`], or `None` if it does not fit in an `i64`. The shoelace formula
+/// is computed with `i128`s, so any vertices are accepted.
+pub fn checked_polygon_area(points: &[Point]) -> Option<i64> {
+    let n = points.len();
+    let double = (0..n).try_fold(0i128, |sum, i| {
+        let (x1, y1) = points[i];
+        let (x2, y2) = points[(i + 1) % n];
+        sum.checked_add(x1 as i128 * y2 as i128 - x2 as i128 * y1 as i128)
+    })?;
+    i64::try_from(double.abs() / 2).ok()
+}
+
+/// Returns the number of lattice points lying on the edges of a polygon.
+pub fn boundary_points(points: &[Point]) -> i64 {
+    let n = points.len();
+    (0..n)
+        .map(|i| {
+            let (x1, y1) = points[i];
+            let (x2, y2) = points[(i + 1) % n];
+            crate::math::gcd(x1.abs_diff(x2), y1.abs_diff(y2)) as i64
+        })
+        .sum()
+}
+
+/// Returns the number of lattice points lying on the edges of a polygon like [`boundary_points`], or `None` if it does
+/// not fit in an `i64`.
+pub fn checked_boundary_points(points: &[Point]) -> Option<i64> {
+    let n = points.len();
+    (0..n).try_fold(0i64, |sum, i| {
+        let (x1, y1) = points[i];
+        let (x2, y2) = points[(i + 1) % n];
+        sum.checked_add(crate::math::gcd(x1.abs_diff(x2), y1.abs_diff(y2)).try_into().ok()?)
+    })
+}
+
+/// Returns the number of lattice points strictly inside a polygon, given its `area` and the number of lattice points on
+/// its `boundary`.
+///
+/// Uses Pick's theorem: `area = interior + boundary / 2 - 1`.
+pub fn interior_points(area: i64, boundary: i64) -> i64 {
+    area - boundary / 2 + 1
+}
+
+/// Checks whether `point` lies strictly inside a polygon, using the even-odd (ray casting) rule.
+///
+/// A horizontal ray is cast from `point` towards increasing `x` and the crossed edges are counted: the point is inside
+/// if the count is odd. Points on the boundary are not considered inside. All computations use integers, so the result
+/// is exact.
+pub fn point_in_polygon(point: Point, polygon: &[Point]) -> bool {
+    let (px, py) = point;
+    let n = polygon.len();
+    let mut inside = false;
+
+    for i in 0..n {
+        let (ax, ay) = polygon[i];
+        let (bx, by) = polygon[(i + 1) % n];
+
+        if is_on_segment(point, (ax, ay), (bx, by)) {
+            return false;
+        }
+
+        // half-open rule on `y`, so a ray through a vertex counts exactly one of the edges sharing it
+        if (ay > py) != (by > py) {
+            // the crossing is to the right of the point if px < ax + (py - ay) * (bx - ax) / (by - ay), which is
+            // rewritten without division, flipping the comparison when the denominator is negative
+            let lhs = (px - ax) * (by - ay);
+            let rhs = (py - ay) * (bx - ax);
+            if (by > ay && lhs < rhs) || (by < ay && lhs > rhs) {
+                inside = !inside;
+            }
+        }
+    }
+
+    inside
+}
+
+/// Returns the lattice points strictly inside a polygon, sorted by `y` and then by `x`.
+///
+/// Every point of the polygon's bounding box is classified with [`point_in_polygon`].
+pub fn points_inside(polygon: &[Point]) -> Vec<Point> {
+    let (min_x, max_x) = (polygon.iter().map(|p| p.0).min(), polygon.iter().map(|p| p.0).max());
+    let (min_y, max_y) = (polygon.iter().map(|p| p.1).min(), polygon.iter().map(|p| p.1).max());
+    let (Some(min_x), Some(max_x), Some(min_y), Some(max_y)) = (min_x, max_x, min_y, max_y) else {
+        return Vec::new();
+    };
+
+    (min_y..=max_y)
+        .flat_map(|y| (min_x..=max_x).map(move |x| (x, y)))
+        .filter(|&p| point_in_polygon(p, polygon))
+        .collect()
+}
+
+/// Checks whether `p` lies on the segment from `a` to `b`, endpoints included.
+fn is_on_segment(p: Point, a: Point, b: Point) -> bool {
+    let cross = (b.0 - a.0) * (p.1 - a.1) - (b.1 - a.1) * (p.0 - a.0);
+    cross == 0 && p.0 >= a.0.min(b.0) && p.0 <= a.0.max(b.0) && p.1 >= a.1.min(b.1) && p.1 <= a.1.max(b.1)
+}
+
+//----------
+// Tests
+//----------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_area() {
+        let square = [(0, 0), (4, 0), (4, 4), (0, 4)];
+        assert_eq!(polygon_area(&square), 16);
+
+        // orientation does not matter
+        let reversed: Vec<Point> = square.iter().rev().copied().collect();
+        assert_eq!(polygon_area(&reversed), 16);
+
+        // triangle with half-integer area
+        let triangle = [(0, 0), (3, 0), (0, 3)];
+        assert_eq!(double_polygon_area(&triangle), 9);
+        assert_eq!(polygon_area(&triangle), 4);
+
+        // L shaped polygon, with a collinear vertex
+        let l_shape = [(0, 0), (2, 0), (4, 0), (4, 2), (2, 2), (2, 4), (0, 4)];
+        assert_eq!(polygon_area(&l_shape), 12);
+        assert_eq!(checked_polygon_area(&l_shape), Some(12));
+
+        // only the checked area notices overflows
+        let huge = [(0, 0), (i64::MAX, 0), (i64::MAX, i64::MAX), (0, i64::MAX)];
+        assert_eq!(checked_polygon_area(&huge), None);
+        assert_eq!(checked_polygon_area(&[(0, 0), (i64::MAX, 0), (0, 2)]), Some(i64::MAX));
+    }
+
+    #[test]
+    fn test_points() {
+        let square = [(0, 0), (4, 0), (4, 4), (0, 4)];
+        let boundary = boundary_points(&square);
+        assert_eq!(boundary, 16);
+        assert_eq!(checked_boundary_points(&square), Some(16));
+        assert_eq!(checked_boundary_points(&[(i64::MIN, 0), (i64::MAX, 0)]), None);
+        assert_eq!(interior_points(polygon_area(&square), boundary), 9);
+
+        let triangle = [(0, 0), (4, 0), (0, 4)];
+        let boundary = boundary_points(&triangle);
+        assert_eq!(boundary, 12);
+        assert_eq!(interior_points(polygon_area(&triangle), boundary), 3);
+    }
+
+    #[test]
+    fn test_point_in_polygon() {
+        // U shaped polygon, the notch between x = 2 and x = 4 above y = 2 is outside
+        let u_shape = [(0, 0), (6, 0), (6, 6), (4, 6), (4, 2), (2, 2), (2, 6), (0, 6)];
+
+        assert!(point_in_polygon((1, 1), &u_shape));
+        assert!(point_in_polygon((1, 5), &u_shape));
+        assert!(point_in_polygon((5, 5), &u_shape));
+        assert!(!point_in_polygon((3, 4), &u_shape));
+        assert!(!point_in_polygon((7, 1), &u_shape));
+        assert!(!point_in_polygon((-1, 2), &u_shape));
+
+        // rays passing through vertices
+        assert!(point_in_polygon((1, 2), &u_shape));
+        assert!(!point_in_polygon((-1, 6), &u_shape));
+
+        // boundary points are not inside
+        assert!(!point_in_polygon((0, 3), &u_shape));
+        assert!(!point_in_polygon((3, 2), &u_shape));
+        assert!(!point_in_polygon((6, 6), &u_shape));
+
+        // the number of inside lattice points agrees with Pick's theorem
+        let inside = points_inside(&u_shape);
+        assert_eq!(inside[0], (1, 1));
+        assert_eq!(
+            inside.len() as i64,
+            interior_points(polygon_area(&u_shape), boundary_points(&u_shape))
+        );
+    }
+}
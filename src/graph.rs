@@ -0,0 +1,505 @@
+//! Generic graph traversals, and an adjacency list graph with labelled nodes.
+//!
+//! Graphs are given implicitly: a start node and a closure returning the neighbours of a node. This way the same
+//! traversal serves grids, mazes and explicit adjacency lists. For the weighted searches, the closure returns the
+//! neighbours along with the cost of the edge leading to them.
+//!
+//! Puzzles describing a graph by the names of its nodes can build a [`Graph`], which maps each name to a compact `u32`
+//! id, so the traversals work on ids instead of strings.
+
+use crate::{
+    arena::Interner,
+    error::AocError,
+    priority_queue::{BucketQueue, PriorityQueue},
+};
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    hash::Hash,
+    str::FromStr,
+};
+
+/// Queue used by Dijkstra's algorithm: a binary heap, see [`dijkstra`], or buckets of small integer costs, see
+/// [`dijkstra_buckets`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Queue {
+    Heap,
+    Buckets,
+}
+
+//----------
+// Breadth-first search
+//----------
+
+/// Iterator over the nodes reachable from a start node, in breadth-first order.
+///
+/// Created by [`bfs`].
+pub struct Bfs<N, F> {
+    queue: VecDeque<(N, usize)>,
+    visited: HashSet<N>,
+    neighbors: F,
+}
+
+/// Returns an iterator visiting every node reachable from `start` in breadth-first order.
+///
+/// Each node is yielded once, along with its distance (number of edges) from `start`. Because of the visiting order,
+/// the distance is the length of the shortest path.
+pub fn bfs<N, F, I>(start: N, neighbors: F) -> Bfs<N, F>
+where
+    N: Clone + Eq + Hash,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = N>,
+{
+    Bfs {
+        queue: VecDeque::from([(start.clone(), 0)]),
+        visited: HashSet::from([start]),
+        neighbors,
+    }
+}
+
+impl<N, F, I> Iterator for Bfs<N, F>
+where
+    N: Clone + Eq + Hash,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = N>,
+{
+    type Item = (N, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (node, dist) = self.queue.pop_front()?;
+
+        for next in (self.neighbors)(&node) {
+            if self.visited.insert(next.clone()) {
+                self.queue.push_back((next, dist + 1));
+            }
+        }
+
+        Some((node, dist))
+    }
+}
+
+//----------
+// Depth-first search
+//----------
+
+/// Iterator over the nodes reachable from a start node, in depth-first order.
+///
+/// Created by [`dfs`].
+pub struct Dfs<N, F> {
+    stack: Vec<(N, usize)>,
+    visited: HashSet<N>,
+    neighbors: F,
+}
+
+/// Returns an iterator visiting every node reachable from `start` in depth-first order.
+///
+/// Each node is yielded once, along with its depth in the search tree. Unlike [`bfs`], the depth is not necessarily the
+/// shortest distance from `start`.
+pub fn dfs<N, F, I>(start: N, neighbors: F) -> Dfs<N, F>
+where
+    N: Clone + Eq + Hash,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = N>,
+{
+    Dfs {
+        stack: vec![(start, 0)],
+        visited: HashSet::new(),
+        neighbors,
+    }
+}
+
+impl<N, F, I> Iterator for Dfs<N, F>
+where
+    N: Clone + Eq + Hash,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = N>,
+{
+    type Item = (N, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // nodes may be pushed more than once before being visited, skip the stale copies
+        while let Some((node, depth)) = self.stack.pop() {
+            if !self.visited.insert(node.clone()) {
+                continue;
+            }
+
+            for next in (self.neighbors)(&node) {
+                if !self.visited.contains(&next) {
+                    self.stack.push((next, depth + 1));
+                }
+            }
+
+            return Some((node, depth));
+        }
+
+        None
+    }
+}
+
+//----------
+// Shortest paths
+//----------
+
+/// Returns the cost of the cheapest path from `start` to any node satisfying `is_goal`, or `None` if no goal node is
+/// reachable.
+///
+/// Uses Dijkstra's algorithm, so edge costs must not be negative.
+pub fn dijkstra<N, F, I, G>(start: N, neighbors: F, is_goal: G) -> Option<u64>
+where
+    N: Clone + Eq + Hash,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = (N, u64)>,
+    G: FnMut(&N) -> bool,
+{
+    search(start, neighbors, is_goal, |_| 0, false).map(|(cost, _)| cost)
+}
+
+/// Same as [`dijkstra`], but also returns the nodes of the cheapest path, from `start` to the goal.
+pub fn dijkstra_path<N, F, I, G>(start: N, neighbors: F, is_goal: G) -> Option<(u64, Vec<N>)>
+where
+    N: Clone + Eq + Hash,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = (N, u64)>,
+    G: FnMut(&N) -> bool,
+{
+    search(start, neighbors, is_goal, |_| 0, true)
+}
+
+/// Same as [`dijkstra`], but with a [`BucketQueue`], for graphs whose edge costs are at most `max_cost`. Faster than
+/// the binary heap when `max_cost` is small.
+///
+/// # Panics
+///
+/// Panics if an edge costs more than `max_cost`.
+pub fn dijkstra_buckets<N, F, I, G>(start: N, mut neighbors: F, mut is_goal: G, max_cost: u64) -> Option<u64>
+where
+    N: Clone + Eq + Hash,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = (N, u64)>,
+    G: FnMut(&N) -> bool,
+{
+    let mut queue = BucketQueue::new(max_cost);
+    queue.push(start, 0);
+
+    while let Some((node, cost)) = queue.pop() {
+        if is_goal(&node) {
+            return Some(cost);
+        }
+        for (next, edge_cost) in neighbors(&node) {
+            queue.push(next, cost + edge_cost);
+        }
+    }
+
+    None
+}
+
+impl FromStr for Queue {
+    type Err = AocError;
+
+    /// Parses the name of a queue, `heap` or `buckets`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "heap" => Ok(Queue::Heap),
+            "buckets" => Ok(Queue::Buckets),
+            _ => Err(AocError::invalid(format!(
+                "unknown queue `{s}`, expected `heap` or `buckets`"
+            ))),
+        }
+    }
+}
+
+/// Returns the cost of the cheapest path from `start` to any node satisfying `is_goal` using the A* algorithm.
+///
+/// The `heuristic` estimates the remaining cost from a node to the goal. It must never overestimate it (admissible),
+/// otherwise the result may not be the cheapest path. A heuristic that always returns 0 turns A* into Dijkstra.
+pub fn astar<N, F, I, G, H>(start: N, neighbors: F, is_goal: G, heuristic: H) -> Option<u64>
+where
+    N: Clone + Eq + Hash,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = (N, u64)>,
+    G: FnMut(&N) -> bool,
+    H: FnMut(&N) -> u64,
+{
+    search(start, neighbors, is_goal, heuristic, false).map(|(cost, _)| cost)
+}
+
+/// Same as [`astar`], but also returns the nodes of the cheapest path, from `start` to the goal.
+pub fn astar_path<N, F, I, G, H>(start: N, neighbors: F, is_goal: G, heuristic: H) -> Option<(u64, Vec<N>)>
+where
+    N: Clone + Eq + Hash,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = (N, u64)>,
+    G: FnMut(&N) -> bool,
+    H: FnMut(&N) -> u64,
+{
+    search(start, neighbors, is_goal, heuristic, true)
+}
+
+/// A* search shared by the public functions. The path is only reconstructed, and the parents only tracked, when
+/// `with_path` is set; otherwise an empty path is returned.
+fn search<N, F, I, G, H>(
+    start: N,
+    mut neighbors: F,
+    mut is_goal: G,
+    mut heuristic: H,
+    with_path: bool,
+) -> Option<(u64, Vec<N>)>
+where
+    N: Clone + Eq + Hash,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = (N, u64)>,
+    G: FnMut(&N) -> bool,
+    H: FnMut(&N) -> u64,
+{
+    // priorities are (estimate, cost): as the heuristic of a node never changes, comparing the estimates of the same
+    // node is the same as comparing its costs
+    let mut queue = PriorityQueue::new();
+    let mut parents = HashMap::new();
+    queue.push(start.clone(), (heuristic(&start), 0));
+
+    while let Some((node, (_, cost))) = queue.pop() {
+        if is_goal(&node) {
+            let mut path = Vec::new();
+            if with_path {
+                let mut current = Some(node);
+                while let Some(n) = current {
+                    current = parents.get(&n).cloned();
+                    path.push(n);
+                }
+                path.reverse();
+            }
+            return Some((cost, path));
+        }
+
+        for (next, edge_cost) in neighbors(&node) {
+            let next_cost = cost + edge_cost;
+            let priority = (next_cost + heuristic(&next), next_cost);
+            if queue.push(next.clone(), priority) && with_path {
+                parents.insert(next, node.clone());
+            }
+        }
+    }
+
+    None
+}
+
+//----------
+// Adjacency lists
+//----------
+
+/// Directed graph whose nodes are identified by string labels, interned into `u32` ids.
+///
+/// Ids are assigned in order of first appearance, starting at 0, and the edges of every node are kept in insertion
+/// order, so puzzles where the order of the edges matters (like "left" and "right") can rely on it. Labels are stored
+/// in an `Interner`, so adding a node does not allocate a `String`.
+#[derive(Clone, Debug, Default)]
+pub struct Graph {
+    labels: Interner,
+    edges: Vec<Vec<u32>>,
+}
+
+impl Graph {
+    /// Creates an empty graph.
+    pub fn new() -> Graph {
+        Graph::default()
+    }
+
+    /// Returns the id of the node labelled `label`, adding the node if it does not exist.
+    pub fn intern(&mut self, label: &str) -> u32 {
+        let id = self.labels.intern(label);
+        if id as usize == self.edges.len() {
+            self.edges.push(Vec::new());
+        }
+        id
+    }
+
+    /// Returns the id of the node labelled `label`, if it exists.
+    pub fn id(&self, label: &str) -> Option<u32> {
+        self.labels.id(label)
+    }
+
+    /// Returns the label of the node `id`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no node `id`.
+    pub fn label(&self, id: u32) -> &str {
+        self.labels.get(id)
+    }
+
+    /// Adds an edge between the nodes labelled `from` and `to`, adding the nodes if needed. Returns their ids.
+    pub fn add_edge(&mut self, from: &str, to: &str) -> (u32, u32) {
+        let (from, to) = (self.intern(from), self.intern(to));
+        self.add_edge_ids(from, to);
+        (from, to)
+    }
+
+    /// Adds an edge between the nodes `from` and `to`, which must exist.
+    pub fn add_edge_ids(&mut self, from: u32, to: u32) {
+        assert!((to as usize) < self.labels.len(), "unknown node {to}");
+        self.edges[from as usize].push(to);
+    }
+
+    /// Returns the number of nodes.
+    pub fn len(&self) -> usize {
+        self.labels.len()
+    }
+
+    /// Checks whether the graph has no nodes.
+    pub fn is_empty(&self) -> bool {
+        self.labels.is_empty()
+    }
+
+    /// Returns the number of edges.
+    pub fn n_edges(&self) -> usize {
+        self.edges.iter().map(Vec::len).sum()
+    }
+
+    /// Returns the nodes reached by the edges leaving `id`, in insertion order.
+    pub fn neighbors(&self, id: u32) -> &[u32] {
+        &self.edges[id as usize]
+    }
+
+    /// Returns an iterator over the ids of all nodes.
+    pub fn nodes(&self) -> impl Iterator<Item = u32> {
+        0..self.labels.len() as u32
+    }
+
+    /// Returns an iterator over all edges, as `(from, to)` pairs.
+    pub fn edges(&self) -> impl Iterator<Item = (u32, u32)> + '_ {
+        self.nodes()
+            .flat_map(move |from| self.neighbors(from).iter().map(move |&to| (from, to)))
+    }
+
+    /// Returns the graph with the same nodes (and ids) and every edge reversed.
+    pub fn reversed(&self) -> Graph {
+        let mut reversed = Graph {
+            labels: self.labels.clone(),
+            edges: vec![Vec::new(); self.labels.len()],
+        };
+        for (from, to) in self.edges() {
+            reversed.edges[to as usize].push(from);
+        }
+        reversed
+    }
+}
+
+//----------
+// Tests
+//----------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Directed graph: 0 -> 1 -> 2 -> 3, 0 -> 2, 4 is unreachable.
+    fn neighbors(n: &u32) -> Vec<u32> {
+        match n {
+            0 => vec![1, 2],
+            1 => vec![2],
+            2 => vec![3],
+            4 => vec![0],
+            _ => vec![],
+        }
+    }
+
+    #[test]
+    fn test_bfs() {
+        let visited: Vec<_> = bfs(0, neighbors).collect();
+        assert_eq!(visited, [(0, 0), (1, 1), (2, 1), (3, 2)]);
+    }
+
+    #[test]
+    fn test_bfs_grid() {
+        // steps within a 3x3 open grid
+        let grid_neighbors = |&(r, c): &(i32, i32)| {
+            [(r - 1, c), (r + 1, c), (r, c - 1), (r, c + 1)]
+                .into_iter()
+                .filter(|&(r, c)| (0..3).contains(&r) && (0..3).contains(&c))
+        };
+
+        let dists: Vec<_> = bfs((0, 0), grid_neighbors).collect();
+        assert_eq!(dists.len(), 9);
+        assert_eq!(dists.last(), Some(&((2, 2), 4)));
+    }
+
+    #[test]
+    fn test_dfs() {
+        let visited: Vec<_> = dfs(0, neighbors).collect();
+        assert_eq!(visited, [(0, 0), (2, 1), (3, 2), (1, 1)]);
+
+        let mut nodes: Vec<_> = dfs(4, neighbors).map(|(n, _)| n).collect();
+        nodes.sort();
+        assert_eq!(nodes, [0, 1, 2, 3, 4]);
+    }
+
+    /// Weighted directed graph: the direct edge 0 -> 3 is more expensive than going through 1 and 2.
+    fn weighted(n: &u32) -> Vec<(u32, u64)> {
+        match n {
+            0 => vec![(1, 1), (3, 10), (2, 5)],
+            1 => vec![(2, 1)],
+            2 => vec![(3, 1)],
+            _ => vec![],
+        }
+    }
+
+    #[test]
+    fn test_dijkstra() {
+        assert_eq!(dijkstra(0, weighted, |&n| n == 3), Some(3));
+        assert_eq!(dijkstra(0, weighted, |&n| n == 0), Some(0));
+        assert_eq!(dijkstra(3, weighted, |&n| n == 0), None);
+        assert_eq!(dijkstra_path(0, weighted, |&n| n == 3), Some((3, vec![0, 1, 2, 3])));
+
+        assert_eq!(dijkstra_buckets(0, weighted, |&n| n == 3, 10), Some(3));
+        assert_eq!(dijkstra_buckets(0, weighted, |&n| n == 0, 10), Some(0));
+        assert_eq!(dijkstra_buckets(3, weighted, |&n| n == 0, 10), None);
+    }
+
+    #[test]
+    fn test_astar() {
+        // 10x10 open grid with unit costs, Manhattan distance is admissible
+        let grid_neighbors = |&(r, c): &(i32, i32)| {
+            [(r - 1, c), (r + 1, c), (r, c - 1), (r, c + 1)]
+                .into_iter()
+                .filter(|&(r, c)| (0..10).contains(&r) && (0..10).contains(&c))
+                .map(|p| (p, 1))
+        };
+        let manhattan = |&(r, c): &(i32, i32)| (9 - r + 9 - c) as u64;
+
+        assert_eq!(astar((0, 0), grid_neighbors, |&p| p == (9, 9), manhattan), Some(18));
+
+        let (cost, path) = astar_path((0, 0), grid_neighbors, |&p| p == (9, 9), manhattan).unwrap();
+        assert_eq!(cost, 18);
+        assert_eq!(path.len(), 19);
+        assert_eq!((path[0], path[18]), ((0, 0), (9, 9)));
+    }
+
+    #[test]
+    fn test_graph() {
+        let mut graph = Graph::new();
+        for line in ["broadcaster -> a, b", "a -> b", "b -> con", "con -> output"] {
+            let (from, to) = line.split_once(" -> ").unwrap();
+            for to in to.split(", ") {
+                graph.add_edge(from, to);
+            }
+        }
+
+        assert_eq!((graph.len(), graph.n_edges()), (5, 5));
+        let [broadcaster, a, b, con, output] =
+            ["broadcaster", "a", "b", "con", "output"].map(|label| graph.id(label).unwrap());
+        assert_eq!(broadcaster, 0);
+        assert_eq!(graph.label(con), "con");
+        assert_eq!(graph.neighbors(broadcaster), [a, b]);
+        assert!(graph.neighbors(output).is_empty());
+        assert_eq!(graph.id("missing"), None);
+
+        let reversed = graph.reversed();
+        assert_eq!(reversed.neighbors(b), [broadcaster, a]);
+        assert_eq!(reversed.id("output"), Some(output));
+
+        // ids plug into the traversals
+        let reachable: Vec<u32> = bfs(output, |&n| reversed.neighbors(n).to_vec())
+            .map(|(n, _)| n)
+            .collect();
+        assert_eq!(reachable, [output, con, b, broadcaster, a]);
+    }
+}
@@ -0,0 +1,545 @@
+//! Two-dimensional grid shared by the puzzles whose input is a map of characters.
+//!
+//! The grid is stored row-major in a single `Vec`, and cells are addressed by `Position`s (row and column indexes).
+
+use crate::error::AocError;
+use std::{
+    collections::HashSet,
+    ops::{Index, IndexMut},
+    str::FromStr,
+};
+
+/// ANSI escape sequence starting the highlight of a cell (bold red).
+const HIGHLIGHT: &str = "\x1b[1;31m";
+
+/// ANSI escape sequence ending the highlight of a cell.
+const RESET: &str = "\x1b[0m";
+
+//----------
+// Structs
+//----------
+
+/// Dense two-dimensional grid of `T`s.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    vec: Vec<T>,
+    n_rows: usize,
+    n_cols: usize,
+}
+
+/// Two-dimensional position within a grid.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Position {
+    pub row: usize,
+    pub col: usize,
+}
+
+/// Cardinal directions. North points to lower row indexes and West to lower column indexes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+/// A position and a heading, for walking through mazes and tracing beams.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Cursor {
+    pub pos: Position,
+    pub dir: Direction,
+}
+
+//----------
+// Implementations
+//----------
+
+impl Position {
+    /// Creates a new `Position` from row and column indexes within a grid.
+    pub fn new(row: usize, col: usize) -> Position {
+        Position { row, col }
+    }
+
+    /// Returns the adjacent position in direction `dir`, or `None` if it would have a negative index.
+    pub fn step(self, dir: Direction) -> Option<Position> {
+        let (row, col) = (self.row, self.col);
+        Some(match dir {
+            Direction::North => Position::new(row.checked_sub(1)?, col),
+            Direction::South => Position::new(row + 1, col),
+            Direction::East => Position::new(row, col + 1),
+            Direction::West => Position::new(row, col.checked_sub(1)?),
+        })
+    }
+}
+
+impl Direction {
+    /// All directions, clockwise from North.
+    pub const ALL: [Direction; 4] = [Direction::North, Direction::East, Direction::South, Direction::West];
+
+    /// Returns the direction after a 90 degrees turn to the left.
+    pub fn turn_left(self) -> Direction {
+        Direction::ALL[(self as usize + 3) % 4]
+    }
+
+    /// Returns the direction after a 90 degrees turn to the right.
+    pub fn turn_right(self) -> Direction {
+        Direction::ALL[(self as usize + 1) % 4]
+    }
+
+    /// Returns the opposite direction.
+    pub fn reverse(self) -> Direction {
+        Direction::ALL[(self as usize + 2) % 4]
+    }
+}
+
+impl Cursor {
+    /// Creates a cursor at `pos` heading to `dir`.
+    pub fn new(pos: Position, dir: Direction) -> Cursor {
+        Cursor { pos, dir }
+    }
+
+    /// Returns the cursor moved one cell forward, or `None` if it would leave the grid through the top or the left.
+    /// Leaving through the bottom or the right must be checked with [`Grid::contains`].
+    pub fn step(self) -> Option<Cursor> {
+        Some(Cursor::new(self.pos.step(self.dir)?, self.dir))
+    }
+
+    /// Returns the cursor rotated 90 degrees to the left, at the same position.
+    pub fn turn_left(self) -> Cursor {
+        Cursor::new(self.pos, self.dir.turn_left())
+    }
+
+    /// Returns the cursor rotated 90 degrees to the right, at the same position.
+    pub fn turn_right(self) -> Cursor {
+        Cursor::new(self.pos, self.dir.turn_right())
+    }
+
+    /// Returns the cursor facing the opposite direction, at the same position.
+    pub fn reverse(self) -> Cursor {
+        Cursor::new(self.pos, self.dir.reverse())
+    }
+}
+
+impl<T> Grid<T> {
+    /// Creates a grid from a row-major `Vec` of cells.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length of `vec` is not `n_rows * n_cols`.
+    pub fn new(vec: Vec<T>, n_rows: usize, n_cols: usize) -> Grid<T> {
+        assert_eq!(
+            vec.len(),
+            n_rows * n_cols,
+            "grid dimensions do not match the number of cells"
+        );
+        Grid { vec, n_rows, n_cols }
+    }
+
+    /// Parses a grid from a string, converting each byte of a line into a cell with `f`. Lines may end in `\n` or
+    /// `\r\n`, and trailing empty lines are ignored.
+    ///
+    /// Returns an error if the lines have different lengths.
+    pub fn from_str_with(s: &str, mut f: impl FnMut(u8) -> T) -> Result<Grid<T>, AocError> {
+        let s = s.trim_end_matches(['\r', '\n']);
+        let n_cols = s.lines().next().map_or(0, str::len);
+        let mut vec = Vec::with_capacity(s.len());
+        let mut n_rows = 0;
+
+        for line in s.lines() {
+            if line.len() != n_cols {
+                let message = format!("Found {} columns, expected {}.", line.len(), n_cols);
+                return Err(AocError::parse(message).at_line(n_rows + 1));
+            }
+            vec.extend(line.bytes().map(&mut f));
+            n_rows += 1;
+        }
+
+        Ok(Grid { vec, n_rows, n_cols })
+    }
+
+    /// Returns the number of rows.
+    pub fn n_rows(&self) -> usize {
+        self.n_rows
+    }
+
+    /// Returns the number of columns.
+    pub fn n_cols(&self) -> usize {
+        self.n_cols
+    }
+
+    /// Returns a reference to the cell at `pos`, or `None` if it is out of bounds.
+    pub fn get(&self, pos: Position) -> Option<&T> {
+        self.contains(pos).then(|| &self.vec[pos.row * self.n_cols + pos.col])
+    }
+
+    /// Returns a mutable reference to the cell at `pos`, or `None` if it is out of bounds.
+    pub fn get_mut(&mut self, pos: Position) -> Option<&mut T> {
+        if self.contains(pos) {
+            Some(&mut self.vec[pos.row * self.n_cols + pos.col])
+        } else {
+            None
+        }
+    }
+
+    /// Checks whether `pos` lies inside the grid.
+    pub fn contains(&self, pos: Position) -> bool {
+        pos.row < self.n_rows && pos.col < self.n_cols
+    }
+
+    /// Returns the cells of a row as a slice.
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.vec[row * self.n_cols..(row + 1) * self.n_cols]
+    }
+
+    /// Returns an iterator over the rows of the grid.
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // `max(1)` keeps `chunks` from panicking on an empty grid, which has no cells to iterate anyway.
+        self.vec.chunks(self.n_cols.max(1))
+    }
+
+    /// Returns the in-bounds positions orthogonally adjacent to `pos` (up, down, left and right).
+    pub fn neighbors4(&self, pos: Position) -> impl Iterator<Item = Position> {
+        const OFFSETS: [(isize, isize); 4] = [(-1, 0), (0, -1), (0, 1), (1, 0)];
+        self.offset_positions(pos, &OFFSETS)
+    }
+
+    /// Returns the in-bounds positions adjacent to `pos`, including diagonals.
+    pub fn neighbors8(&self, pos: Position) -> impl Iterator<Item = Position> {
+        const OFFSETS: [(isize, isize); 8] = [(-1, -1), (-1, 0), (-1, 1), (0, -1), (0, 1), (1, -1), (1, 0), (1, 1)];
+        self.offset_positions(pos, &OFFSETS)
+    }
+
+    /// Applies each offset to `pos`, keeping the resulting positions that lie inside the grid.
+    fn offset_positions(&self, pos: Position, offsets: &'static [(isize, isize)]) -> impl Iterator<Item = Position> {
+        let (n_rows, n_cols) = (self.n_rows, self.n_cols);
+        offsets.iter().filter_map(move |&(dr, dc)| {
+            let row = pos.row.checked_add_signed(dr).filter(|&r| r < n_rows)?;
+            let col = pos.col.checked_add_signed(dc).filter(|&c| c < n_cols)?;
+            Some(Position::new(row, col))
+        })
+    }
+
+    /// Returns an iterator over all cells along with their positions, in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = (Position, &T)> {
+        let n_cols = self.n_cols;
+        self.vec
+            .iter()
+            .enumerate()
+            .map(move |(idx, cell)| (Position::new(idx / n_cols, idx % n_cols), cell))
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    /// Creates a grid of the given dimensions with every cell set to `value`.
+    pub fn filled(value: T, n_rows: usize, n_cols: usize) -> Grid<T> {
+        Grid {
+            vec: vec![value; n_rows * n_cols],
+            n_rows,
+            n_cols,
+        }
+    }
+
+    /// Returns a copy of the grid surrounded by a one cell wide border of `neutral` values.
+    ///
+    /// Padding allows looking at the neighbours of any original cell without dealing with boundary conditions. Note
+    /// that a cell at `Position { row, col }` in the original grid is at `Position { row + 1, col + 1 }` in the padded
+    /// one.
+    pub fn with_padding(&self, neutral: T) -> Grid<T> {
+        let n_rows = self.n_rows + 2;
+        let n_cols = self.n_cols + 2;
+
+        let mut vec = Vec::with_capacity(n_rows * n_cols);
+        vec.extend(std::iter::repeat_n(neutral.clone(), n_cols));
+        for row in self.rows() {
+            vec.push(neutral.clone());
+            vec.extend_from_slice(row);
+            vec.push(neutral.clone());
+        }
+        vec.extend(std::iter::repeat_n(neutral, n_cols));
+
+        Grid { vec, n_rows, n_cols }
+    }
+}
+
+//----------
+// Flood fill
+//----------
+
+impl<T> Grid<T> {
+    /// Returns the positions of the region connected to `start` through orthogonal steps over cells accepted by
+    /// `passable`, including `start` itself. The region is empty if `start` is not passable or out of bounds.
+    ///
+    /// The fill is iterative, so large regions do not overflow the stack.
+    pub fn flood_fill(&self, start: Position, mut passable: impl FnMut(&T) -> bool) -> Vec<Position> {
+        if !self.get(start).is_some_and(&mut passable) {
+            return Vec::new();
+        }
+
+        let mut visited = Grid::filled(false, self.n_rows, self.n_cols);
+        visited[start] = true;
+        let mut region = vec![start];
+        let mut stack = vec![start];
+
+        while let Some(pos) = stack.pop() {
+            for next in self.neighbors4(pos) {
+                if !visited[next] && passable(&self[next]) {
+                    visited[next] = true;
+                    region.push(next);
+                    stack.push(next);
+                }
+            }
+        }
+
+        region
+    }
+}
+
+//----------
+// Transformations
+//----------
+
+impl<T: Clone> Grid<T> {
+    /// Returns the transposed grid, where rows become columns.
+    pub fn transpose(&self) -> Grid<T> {
+        self.remap(self.n_cols, self.n_rows, |row, col| (col, row))
+    }
+
+    /// Returns the grid rotated 90 degrees clockwise.
+    pub fn rotate_cw(&self) -> Grid<T> {
+        self.remap(self.n_cols, self.n_rows, |row, col| (self.n_rows - 1 - col, row))
+    }
+
+    /// Returns the grid rotated 90 degrees counterclockwise.
+    pub fn rotate_ccw(&self) -> Grid<T> {
+        self.remap(self.n_cols, self.n_rows, |row, col| (col, self.n_cols - 1 - row))
+    }
+
+    /// Builds a new `n_rows` x `n_cols` grid, where the cell at `(row, col)` is copied from the position of `self`
+    /// returned by `source(row, col)`. Allocates the new cells once.
+    fn remap(&self, n_rows: usize, n_cols: usize, source: impl Fn(usize, usize) -> (usize, usize)) -> Grid<T> {
+        let mut vec = Vec::with_capacity(self.vec.len());
+        for row in 0..n_rows {
+            for col in 0..n_cols {
+                let (r, c) = source(row, col);
+                vec.push(self.vec[r * self.n_cols + c].clone());
+            }
+        }
+
+        Grid { vec, n_rows, n_cols }
+    }
+}
+
+impl<T> Grid<T> {
+    /// Mirrors the grid in place, left to right.
+    pub fn flip_horizontal(&mut self) {
+        self.vec.chunks_mut(self.n_cols.max(1)).for_each(|row| row.reverse());
+    }
+
+    /// Mirrors the grid in place, top to bottom.
+    pub fn flip_vertical(&mut self) {
+        for row in 0..self.n_rows / 2 {
+            let (top, bottom) = self.vec.split_at_mut((self.n_rows - 1 - row) * self.n_cols);
+            top[row * self.n_cols..(row + 1) * self.n_cols].swap_with_slice(&mut bottom[..self.n_cols]);
+        }
+    }
+}
+
+//----------
+// Rendering
+//----------
+
+impl<T> Grid<T> {
+    /// Renders the grid as text, one line per row, converting each cell into a character with `glyph`.
+    pub fn render(&self, glyph: impl FnMut(&T) -> char) -> String {
+        self.render_highlighted(glyph, &HashSet::new())
+    }
+
+    /// Same as [`Grid::render`], but the cells at the `highlighted` positions are wrapped in ANSI escape codes, so they
+    /// stand out when printed to a terminal.
+    pub fn render_highlighted(&self, mut glyph: impl FnMut(&T) -> char, highlighted: &HashSet<Position>) -> String {
+        let mut s = String::with_capacity(self.vec.len() + self.n_rows);
+        for (pos, cell) in self.iter() {
+            if pos.col == 0 && pos.row > 0 {
+                s.push('\n');
+            }
+            if highlighted.contains(&pos) {
+                s.push_str(HIGHLIGHT);
+                s.push(glyph(cell));
+                s.push_str(RESET);
+            } else {
+                s.push(glyph(cell));
+            }
+        }
+        s
+    }
+}
+
+//----------
+// Traits
+//----------
+
+impl<T> Index<Position> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Position) -> &Self::Output {
+        self.get(pos).expect("position out of grid bounds")
+    }
+}
+
+impl<T> IndexMut<Position> for Grid<T> {
+    fn index_mut(&mut self, pos: Position) -> &mut Self::Output {
+        self.get_mut(pos).expect("position out of grid bounds")
+    }
+}
+
+impl FromStr for Grid<u8> {
+    type Err = AocError;
+
+    /// Parses a grid of raw ascii bytes, one row per line.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Grid::from_str_with(s, |b| b)
+    }
+}
+
+//----------
+// Tests
+//----------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let grid: Grid<u8> = "abc\ndef".parse().unwrap();
+        assert_eq!((grid.n_rows(), grid.n_cols()), (2, 3));
+        assert_eq!(grid[Position::new(1, 0)], b'd');
+        assert_eq!(grid.row(0), b"abc");
+        assert_eq!(grid.get(Position::new(2, 0)), None);
+
+        // line endings do not count as columns, nor trailing empty lines as rows
+        let crlf: Grid<u8> = "abc\r\ndef\r\n\r\n".parse().unwrap();
+        assert_eq!((crlf.n_rows(), crlf.n_cols()), (2, 3));
+        assert_eq!(crlf.row(1), b"def");
+
+        let err = "abc\nde".parse::<Grid<u8>>().unwrap_err();
+        assert_eq!(err.to_string(), "Line 2: Found 2 columns, expected 3.");
+    }
+
+    #[test]
+    fn test_with_padding() {
+        let grid: Grid<u8> = "ab\ncd\nef".parse().unwrap();
+        let padded = grid.with_padding(b'.');
+
+        assert_eq!((padded.n_rows(), padded.n_cols()), (5, 4));
+        assert_eq!(padded.row(0), b"....");
+        assert_eq!(padded.row(1), b".ab.");
+        assert_eq!(padded.row(3), b".ef.");
+        assert_eq!(padded.row(4), b"....");
+        assert_eq!(padded[Position::new(2, 2)], grid[Position::new(1, 1)]);
+    }
+
+    #[test]
+    fn test_transformations() {
+        // non-square grid:
+        // abc
+        // def
+        let grid: Grid<u8> = "abc\ndef".parse().unwrap();
+
+        let transposed = grid.transpose();
+        assert_eq!((transposed.n_rows(), transposed.n_cols()), (3, 2));
+        assert_eq!(transposed.rows().collect::<Vec<_>>(), [b"ad", b"be", b"cf"]);
+        assert_eq!(transposed.transpose(), grid);
+
+        let cw = grid.rotate_cw();
+        assert_eq!((cw.n_rows(), cw.n_cols()), (3, 2));
+        assert_eq!(cw.rows().collect::<Vec<_>>(), [b"da", b"eb", b"fc"]);
+
+        let ccw = grid.rotate_ccw();
+        assert_eq!(ccw.rows().collect::<Vec<_>>(), [b"cf", b"be", b"ad"]);
+        assert_eq!(ccw.rotate_cw(), grid);
+        assert_eq!(grid.rotate_cw().rotate_cw().rotate_cw().rotate_cw(), grid);
+
+        let mut flipped = grid.clone();
+        flipped.flip_horizontal();
+        assert_eq!(flipped.rows().collect::<Vec<_>>(), [b"cba", b"fed"]);
+
+        let mut flipped: Grid<u8> = "ab\ncd\nef".parse().unwrap();
+        flipped.flip_vertical();
+        assert_eq!(flipped.rows().collect::<Vec<_>>(), [b"ef", b"cd", b"ab"]);
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let grid = Grid::filled(0, 3, 4);
+
+        let corner: Vec<_> = grid.neighbors4(Position::new(0, 0)).collect();
+        assert_eq!(corner, [Position::new(0, 1), Position::new(1, 0)]);
+        assert_eq!(grid.neighbors8(Position::new(0, 0)).count(), 3);
+
+        assert_eq!(grid.neighbors4(Position::new(1, 1)).count(), 4);
+        assert_eq!(grid.neighbors8(Position::new(1, 1)).count(), 8);
+
+        let edge: Vec<_> = grid.neighbors8(Position::new(2, 3)).collect();
+        assert_eq!(edge, [Position::new(1, 2), Position::new(1, 3), Position::new(2, 2)]);
+    }
+
+    #[test]
+    fn test_cursor() {
+        use Direction::*;
+
+        assert_eq!(North.turn_left(), West);
+        assert_eq!(West.turn_right(), North);
+        assert_eq!(East.reverse(), West);
+
+        let cursor = Cursor::new(Position::new(0, 1), East);
+        let cursor = cursor.step().unwrap().turn_right().step().unwrap();
+        assert_eq!(cursor, Cursor::new(Position::new(1, 2), South));
+
+        assert_eq!(cursor.reverse().step().unwrap().step(), None);
+        assert_eq!(Cursor::new(Position::new(3, 0), West).step(), None);
+    }
+
+    #[test]
+    fn test_flood_fill() {
+        let grid: Grid<u8> = "..#..
+..#..
+###..
+....."
+            .parse()
+            .unwrap();
+
+        let mut region = grid.flood_fill(Position::new(0, 0), |&c| c == b'.');
+        region.sort();
+        assert_eq!(
+            region,
+            [
+                Position::new(0, 0),
+                Position::new(0, 1),
+                Position::new(1, 0),
+                Position::new(1, 1)
+            ]
+        );
+
+        assert_eq!(grid.flood_fill(Position::new(0, 4), |&c| c == b'.').len(), 11);
+        assert!(grid.flood_fill(Position::new(0, 2), |&c| c == b'.').is_empty());
+        assert!(grid.flood_fill(Position::new(9, 9), |&c| c == b'.').is_empty());
+
+        // large regions do not overflow the stack
+        let big = Grid::filled(b'.', 1000, 1000);
+        assert_eq!(big.flood_fill(Position::new(500, 500), |&c| c == b'.').len(), 1_000_000);
+    }
+
+    #[test]
+    fn test_render() {
+        let grid: Grid<u8> = "#..\n.#.".parse().unwrap();
+        let glyph = |&b: &u8| if b == b'#' { '█' } else { ' ' };
+        assert_eq!(grid.render(glyph), "█  \n █ ");
+
+        let highlighted = HashSet::from([Position::new(1, 1), Position::new(0, 2)]);
+        assert_eq!(
+            grid.render_highlighted(|&b| b as char, &highlighted),
+            "#.\x1b[1;31m.\x1b[0m\n.\x1b[1;31m#\x1b[0m."
+        );
+    }
+}
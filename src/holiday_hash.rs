@@ -0,0 +1,160 @@
+//! The Holiday ASCII String Helper algorithm (HASH) and the map of boxes built on it (HASHMAP), from day 15.
+//!
+//! HASH turns a string into a value in `0..256`: for every byte, add its value, multiply by 17 and keep the remainder
+//! of the division by 256. `BucketMap` stores labelled values in 256 boxes chosen by the hash of their labels, keeping
+//! the insertion order inside each box.
+
+//----------
+// Helper methods
+//----------
+
+/// Number of boxes of a `BucketMap`, one for every possible hash.
+pub const N_BUCKETS: usize = 256;
+
+/// Returns the HASH of `s`.
+pub fn hash(s: &[u8]) -> u8 {
+    s.iter().fold(0u8, |acc, &b| acc.wrapping_add(b).wrapping_mul(17))
+}
+
+//----------
+// Structs
+//----------
+
+/// Map from labels to values, where each label is placed in the box given by its hash and the boxes keep their labels
+/// in insertion order.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BucketMap<V> {
+    buckets: Vec<Vec<(String, V)>>,
+}
+
+//----------
+// Implementations
+//----------
+
+impl<V> BucketMap<V> {
+    /// Creates a map with 256 empty boxes.
+    pub fn new() -> BucketMap<V> {
+        BucketMap {
+            buckets: (0..N_BUCKETS).map(|_| Vec::new()).collect(),
+        }
+    }
+
+    /// Sets the value of `label`. An existing label keeps its place in its box, a new one goes to the back of the box.
+    /// Returns the previous value, if any.
+    pub fn insert(&mut self, label: &str, value: V) -> Option<V> {
+        let bucket = &mut self.buckets[hash(label.as_bytes()) as usize];
+        match bucket.iter_mut().find(|(l, _)| l == label) {
+            Some((_, v)) => Some(std::mem::replace(v, value)),
+            None => {
+                bucket.push((label.to_owned(), value));
+                None
+            }
+        }
+    }
+
+    /// Removes `label` from its box, moving the labels behind it forward. Returns its value, if it was present.
+    pub fn remove(&mut self, label: &str) -> Option<V> {
+        let bucket = &mut self.buckets[hash(label.as_bytes()) as usize];
+        let idx = bucket.iter().position(|(l, _)| l == label)?;
+        Some(bucket.remove(idx).1)
+    }
+
+    /// Returns the value of `label`, if present.
+    pub fn get(&self, label: &str) -> Option<&V> {
+        self.buckets[hash(label.as_bytes()) as usize]
+            .iter()
+            .find_map(|(l, v)| (l == label).then_some(v))
+    }
+
+    /// Returns the number of labels in the map.
+    pub fn len(&self) -> usize {
+        self.buckets.iter().map(Vec::len).sum()
+    }
+
+    /// Checks whether all boxes are empty.
+    pub fn is_empty(&self) -> bool {
+        self.buckets.iter().all(Vec::is_empty)
+    }
+
+    /// Returns the labels and values in box `idx`, in insertion order.
+    pub fn bucket(&self, idx: u8) -> &[(String, V)] {
+        &self.buckets[idx as usize]
+    }
+
+    /// Returns an iterator over the boxes, from box 0 to box 255.
+    pub fn buckets(&self) -> impl Iterator<Item = &[(String, V)]> {
+        self.buckets.iter().map(Vec::as_slice)
+    }
+
+    /// Returns an iterator over the entries, as `(box, slot, label, value)`, ordered by box and then by slot.
+    pub fn iter(&self) -> impl Iterator<Item = (usize, usize, &str, &V)> {
+        self.buckets.iter().enumerate().flat_map(|(b, bucket)| {
+            bucket
+                .iter()
+                .enumerate()
+                .map(move |(slot, (label, value))| (b, slot, label.as_str(), value))
+        })
+    }
+}
+
+impl<V> Default for BucketMap<V> {
+    fn default() -> Self {
+        BucketMap::new()
+    }
+}
+
+//----------
+// Tests
+//----------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7";
+
+    #[test]
+    fn test_hash() {
+        assert_eq!(hash(b"HASH"), 52);
+        assert_eq!(hash(b""), 0);
+        assert_eq!(hash(b"rn"), 0);
+        assert_eq!(hash(b"qp"), 1);
+
+        let sum: u32 = EXAMPLE.split(',').map(|step| hash(step.as_bytes()) as u32).sum();
+        assert_eq!(sum, 1320);
+    }
+
+    #[test]
+    fn test_bucket_map() {
+        let mut map = BucketMap::new();
+        for step in EXAMPLE.split(',') {
+            match step.split_once('=') {
+                Some((label, focal)) => {
+                    map.insert(label, focal.parse::<usize>().unwrap());
+                }
+                None => {
+                    map.remove(step.trim_end_matches('-'));
+                }
+            }
+        }
+
+        assert_eq!(map.len(), 5);
+        assert_eq!(map.bucket(0), [("rn".to_owned(), 1), ("cm".to_owned(), 2)]);
+        assert_eq!(map.get("ot"), Some(&7));
+        assert_eq!(map.get("pc"), Some(&6));
+        assert_eq!(map.get("qp"), None);
+
+        // "ot" replaced its value in place, so it stays in front of "ab"
+        let box3: Vec<&str> = map.bucket(3).iter().map(|(label, _)| label.as_str()).collect();
+        assert_eq!(box3, ["ot", "ab", "pc"]);
+
+        let power: usize = map
+            .iter()
+            .map(|(b, slot, _, &focal)| (b + 1) * (slot + 1) * focal)
+            .sum();
+        assert_eq!(power, 145);
+
+        assert_eq!(map.insert("rn", 9), Some(1));
+        assert!(BucketMap::<u8>::default().is_empty());
+    }
+}
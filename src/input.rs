@@ -0,0 +1,161 @@
+//! Loading of the puzzle inputs.
+//!
+//! Inputs are read from the directory set in the `AOC_INPUT_DIR` environment variable, or from `inputs` when it is not
+//! set, and are named after their day, like `day01`.
+//!
+//! Days that only need one line at a time can stream their input with [`lines`] instead of loading it whole, keeping
+//! their memory use constant however large the input is.
+
+use crate::error::AocError;
+use std::{
+    env,
+    fs::{self, File},
+    io::{BufRead, BufReader},
+    path::{Path, PathBuf},
+};
+
+/// Environment variable overriding the input directory.
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+/// Default input directory, relative to the working directory.
+pub const DEFAULT_INPUT_DIR: &str = "inputs";
+
+/// Returns the directory the inputs are loaded from.
+pub fn input_dir() -> PathBuf {
+    env::var_os(INPUT_DIR_VAR).map_or_else(|| PathBuf::from(DEFAULT_INPUT_DIR), PathBuf::from)
+}
+
+/// Loads the input of `day`, with normalized line endings and without trailing newlines.
+///
+/// Returns an error naming the file if it can not be read.
+pub fn load(day: u8) -> Result<String, AocError> {
+    load_from(&input_dir(), day)
+}
+
+/// Loads the input of `day` from the directory `dir`. See [`load`].
+pub fn load_from(dir: &Path, day: u8) -> Result<String, AocError> {
+    let path = dir.join(format!("day{day:02}"));
+    let input = fs::read_to_string(&path).map_err(|e| AocError::io(path, e))?;
+    Ok(normalize(&input))
+}
+
+/// Returns an iterator over the lines of the input of `day`, read one at a time. The lines are the same as the ones of
+/// the input returned by [`load`].
+///
+/// Returns an error naming the file if it can not be opened.
+pub fn lines(day: u8) -> Result<Lines<BufReader<File>>, AocError> {
+    lines_from(&input_dir(), day)
+}
+
+/// Returns an iterator over the lines of the input of `day` from the directory `dir`. See [`lines`].
+pub fn lines_from(dir: &Path, day: u8) -> Result<Lines<BufReader<File>>, AocError> {
+    let path = dir.join(format!("day{day:02}"));
+    let file = File::open(&path).map_err(|e| AocError::io(&path, e))?;
+    Ok(Lines::new(BufReader::new(file), path))
+}
+
+/// Converts `\r\n` line endings into `\n` and removes trailing newlines.
+pub fn normalize(input: &str) -> String {
+    input.replace("\r\n", "\n").trim_end_matches('\n').to_owned()
+}
+
+//----------
+// Structs
+//----------
+
+/// Iterator over the lines of a reader, without their line endings. Like [`normalize`], trailing empty lines are
+/// dropped, so empty lines are held back until a non-empty one follows them.
+///
+/// Created by [`lines`].
+pub struct Lines<R> {
+    reader: R,
+    /// Path of the file read, for the errors.
+    path: PathBuf,
+    /// Empty lines read and not returned yet.
+    n_empty: usize,
+    /// Non-empty line read after the pending empty lines.
+    next_line: Option<String>,
+}
+
+//----------
+// Implementations
+//----------
+
+impl<R: BufRead> Lines<R> {
+    /// Creates an iterator over the lines of `reader`, naming `path` in the errors.
+    pub fn new(reader: R, path: impl Into<PathBuf>) -> Lines<R> {
+        Lines {
+            reader,
+            path: path.into(),
+            n_empty: 0,
+            next_line: None,
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for Lines<R> {
+    type Item = Result<String, AocError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.next_line.is_some() {
+            if self.n_empty > 0 {
+                self.n_empty -= 1;
+                return Some(Ok(String::new()));
+            }
+            return self.next_line.take().map(Ok);
+        }
+
+        loop {
+            let mut line = String::new();
+            match self.reader.read_line(&mut line) {
+                Ok(0) => return None,
+                Ok(_) => (),
+                Err(e) => return Some(Err(AocError::io(&self.path, e))),
+            }
+
+            let len = line.trim_end_matches('\n').trim_end_matches('\r').len();
+            line.truncate(len);
+            if line.is_empty() {
+                self.n_empty += 1;
+            } else {
+                self.next_line = Some(line);
+                return self.next();
+            }
+        }
+    }
+}
+
+//----------
+// Tests
+//----------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(normalize("1\r\n2\n3\r\n\r\n"), "1\n2\n3");
+        assert_eq!(normalize("a\n\nb\n"), "a\n\nb");
+        assert_eq!(normalize(""), "");
+    }
+
+    #[test]
+    fn test_load_missing() {
+        let err = load_from(Path::new("no_such_dir"), 7).unwrap_err();
+        assert!(matches!(err, AocError::IoError { .. }));
+        assert!(err.to_string().starts_with("Not able to read `no_such_dir/day07`"));
+    }
+
+    #[test]
+    fn test_lines() {
+        for input in ["1\r\n2\n3\r\n\r\n", "a\n\nb\n", "a\n\n\nb", "\n\na", "", "\n\n"] {
+            let lines: Vec<_> = Lines::new(input.as_bytes(), "test").map(Result::unwrap).collect();
+            let expected: Vec<_> = normalize(input).lines().map(str::to_owned).collect();
+            assert_eq!(lines, expected, "{input:?}");
+        }
+
+        let err = lines_from(Path::new("no_such_dir"), 7).err().unwrap();
+        assert!(err.to_string().starts_with("Not able to read `no_such_dir/day07`"));
+    }
+}
@@ -0,0 +1,199 @@
+//! Half-open integer intervals and the usual operations needed to split and merge ranges of values.
+//!
+//! An `Interval` represents all integers `x` such that `start <= x < end`. Intervals with `start >= end` are empty.
+
+use std::fmt;
+
+//----------
+// Structs
+//----------
+
+/// Half-open interval of integers, `[start, end)`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Interval {
+    pub start: i64,
+    pub end: i64,
+}
+
+//----------
+// Implementations
+//----------
+
+impl Interval {
+    /// Creates the interval `[start, end)`.
+    pub fn new(start: i64, end: i64) -> Interval {
+        Interval { start, end }
+    }
+
+    /// Creates the interval starting at `start` and containing `len` values. See `checked_with_len` when the end may
+    /// overflow.
+    pub fn with_len(start: i64, len: i64) -> Interval {
+        Interval {
+            start,
+            end: start + len,
+        }
+    }
+
+    /// Creates the interval starting at `start` and containing `len` values, or `None` if its end overflows.
+    pub fn checked_with_len(start: i64, len: i64) -> Option<Interval> {
+        Some(Interval {
+            start,
+            end: start.checked_add(len)?,
+        })
+    }
+
+    /// Checks whether the interval contains no values.
+    pub fn is_empty(&self) -> bool {
+        self.start >= self.end
+    }
+
+    /// Returns the number of values within the interval.
+    pub fn len(&self) -> i64 {
+        (self.end - self.start).max(0)
+    }
+
+    /// Checks whether `val` lies within the interval.
+    pub fn contains(&self, val: i64) -> bool {
+        self.start <= val && val < self.end
+    }
+
+    /// Returns the values present in both `self` and `other`, or `None` if they do not overlap.
+    pub fn intersection(&self, other: &Interval) -> Option<Interval> {
+        Interval::new(self.start.max(other.start), self.end.min(other.end)).non_empty()
+    }
+
+    /// Returns the values of `self` that are not in `other`.
+    ///
+    /// The result is split in the part lying before `other` and the part lying after it, any of which may be `None`.
+    pub fn difference(&self, other: &Interval) -> (Option<Interval>, Option<Interval>) {
+        if other.is_empty() {
+            return (self.non_empty(), None);
+        }
+
+        (self.split_at(other.start).0, self.split_at(other.end).1)
+    }
+
+    /// Returns the interval shifted by `delta`.
+    pub fn offset(&self, delta: i64) -> Interval {
+        Interval::new(self.start + delta, self.end + delta)
+    }
+
+    /// Splits the interval at `val`, returning the values lower than `val` and the remaining ones.
+    pub fn split_at(&self, val: i64) -> (Option<Interval>, Option<Interval>) {
+        let low = Interval::new(self.start, self.end.min(val));
+        let high = Interval::new(self.start.max(val), self.end);
+        (low.non_empty(), high.non_empty())
+    }
+
+    /// Returns `Some(self)` if the interval is not empty.
+    fn non_empty(self) -> Option<Interval> {
+        (!self.is_empty()).then_some(self)
+    }
+}
+
+//----------
+// Helper methods
+//----------
+
+/// Merges a set of intervals, returning the sorted, disjoint intervals that cover the same values.
+///
+/// Overlapping and adjacent intervals are joined together, and empty intervals are discarded.
+pub fn merge(mut intervals: Vec<Interval>) -> Vec<Interval> {
+    intervals.retain(|i| !i.is_empty());
+    intervals.sort_unstable();
+
+    let mut merged: Vec<Interval> = Vec::with_capacity(intervals.len());
+    for i in intervals {
+        match merged.last_mut() {
+            Some(last) if i.start <= last.end => last.end = last.end.max(i.end),
+            _ => merged.push(i),
+        }
+    }
+
+    merged
+}
+
+//----------
+// Traits
+//----------
+
+impl fmt::Display for Interval {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "[{}, {})", self.start, self.end)
+    }
+}
+
+//----------
+// Tests
+//----------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn i(start: i64, end: i64) -> Interval {
+        Interval::new(start, end)
+    }
+
+    #[test]
+    fn test_basics() {
+        assert_eq!(Interval::with_len(98, 2), i(98, 100));
+        assert_eq!(Interval::checked_with_len(98, 2), Some(i(98, 100)));
+        assert_eq!(Interval::checked_with_len(i64::MAX, 5), None);
+        assert_eq!(Interval::checked_with_len(i64::MIN, -1), None);
+        assert_eq!(i(98, 100).len(), 2);
+        assert_eq!(i(5, 5).len(), 0);
+        assert_eq!(i(5, 2).len(), 0);
+        assert!(i(5, 5).is_empty());
+        assert!(i(0, 10).contains(0));
+        assert!(!i(0, 10).contains(10));
+        assert_eq!(i(-3, 4).offset(-2), i(-5, 2));
+    }
+
+    #[test]
+    fn test_intersection() {
+        assert_eq!(i(0, 10).intersection(&i(5, 15)), Some(i(5, 10)));
+        assert_eq!(i(5, 15).intersection(&i(0, 10)), Some(i(5, 10)));
+        assert_eq!(i(0, 10).intersection(&i(2, 3)), Some(i(2, 3)));
+        assert_eq!(i(0, 10).intersection(&i(10, 15)), None);
+        assert_eq!(i(0, 10).intersection(&i(20, 25)), None);
+    }
+
+    #[test]
+    fn test_difference() {
+        // other covers the middle
+        assert_eq!(i(0, 10).difference(&i(3, 5)), (Some(i(0, 3)), Some(i(5, 10))));
+        // other covers the start or the end
+        assert_eq!(i(0, 10).difference(&i(-5, 5)), (None, Some(i(5, 10))));
+        assert_eq!(i(0, 10).difference(&i(5, 15)), (Some(i(0, 5)), None));
+        // other covers everything
+        assert_eq!(i(0, 10).difference(&i(0, 10)), (None, None));
+        assert_eq!(i(0, 10).difference(&i(-1, 11)), (None, None));
+        // disjoint
+        assert_eq!(i(0, 10).difference(&i(20, 30)), (Some(i(0, 10)), None));
+        assert_eq!(i(20, 30).difference(&i(0, 10)), (None, Some(i(20, 30))));
+        // empty operands
+        assert_eq!(i(0, 10).difference(&i(5, 5)), (Some(i(0, 10)), None));
+        assert_eq!(i(5, 5).difference(&i(0, 10)), (None, None));
+    }
+
+    #[test]
+    fn test_split_at() {
+        assert_eq!(i(0, 10).split_at(4), (Some(i(0, 4)), Some(i(4, 10))));
+        assert_eq!(i(0, 10).split_at(0), (None, Some(i(0, 10))));
+        assert_eq!(i(0, 10).split_at(-5), (None, Some(i(0, 10))));
+        assert_eq!(i(0, 10).split_at(10), (Some(i(0, 10)), None));
+        assert_eq!(i(0, 10).split_at(15), (Some(i(0, 10)), None));
+    }
+
+    #[test]
+    fn test_merge() {
+        assert_eq!(merge(vec![]), vec![]);
+        assert_eq!(
+            merge(vec![i(10, 12), i(0, 3), i(2, 5), i(5, 7), i(20, 20), i(11, 15)]),
+            vec![i(0, 7), i(10, 15)]
+        );
+        assert_eq!(merge(vec![i(0, 10), i(2, 3), i(4, 5)]), vec![i(0, 10)]);
+        assert_eq!(merge(vec![i(0, 1), i(2, 3)]), vec![i(0, 1), i(2, 3)]);
+    }
+}
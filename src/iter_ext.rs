@@ -0,0 +1,183 @@
+//! Extension methods for iterators that are missing from (stable) std.
+//!
+//! Also sums and products returning `None` on overflow, instead of panicking in debug builds and wrapping in release
+//! ones.
+
+//----------
+// Trait
+//----------
+
+/// Extra adapters available on every iterator.
+pub trait IterExt: Iterator + Sized {
+    /// Returns an iterator over each pair of consecutive elements.
+    ///
+    /// For example, `[1, 2, 4]` yields `(1, 2)` and `(2, 4)`.
+    fn pairwise(self) -> Pairwise<Self>
+    where
+        Self::Item: Clone,
+    {
+        Pairwise { iter: self, prev: None }
+    }
+
+    /// Calls `f` on each window of `N` consecutive elements and yields the results. A stable counterpart of the
+    /// nightly `Iterator::map_windows`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `N` is zero.
+    fn windows_map<const N: usize, F, R>(self, f: F) -> WindowsMap<Self, F, N>
+    where
+        F: FnMut(&[Self::Item; N]) -> R,
+    {
+        assert!(N > 0, "window size must be greater than zero");
+        WindowsMap {
+            iter: self,
+            window: Vec::with_capacity(N),
+            f,
+        }
+    }
+
+    /// Sums the elements, returning `None` if the sum overflows.
+    fn checked_sum(mut self) -> Option<Self::Item>
+    where
+        Self::Item: CheckedInt,
+    {
+        self.try_fold(CheckedInt::ZERO, CheckedInt::checked_add)
+    }
+
+    /// Multiplies the elements, returning `None` if the product overflows.
+    fn checked_product(mut self) -> Option<Self::Item>
+    where
+        Self::Item: CheckedInt,
+    {
+        self.try_fold(CheckedInt::ONE, CheckedInt::checked_mul)
+    }
+}
+
+impl<I: Iterator> IterExt for I {}
+
+/// Integers with arithmetic checked for overflow, for [`IterExt::checked_sum`] and [`IterExt::checked_product`].
+pub trait CheckedInt: Sized {
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn checked_add(self, other: Self) -> Option<Self>;
+
+    fn checked_mul(self, other: Self) -> Option<Self>;
+}
+
+/// Implements `CheckedInt` with the inherent checked methods of the integer types.
+macro_rules! checked_int {
+    ($($t:ty),*) => {
+        $(impl CheckedInt for $t {
+            const ZERO: $t = 0;
+            const ONE: $t = 1;
+
+            fn checked_add(self, other: $t) -> Option<$t> {
+                <$t>::checked_add(self, other)
+            }
+
+            fn checked_mul(self, other: $t) -> Option<$t> {
+                <$t>::checked_mul(self, other)
+            }
+        })*
+    };
+}
+
+checked_int!(u32, u64, usize, i64);
+
+//----------
+// Adapters
+//----------
+
+/// Iterator over pairs of consecutive elements. Created by [`IterExt::pairwise`].
+pub struct Pairwise<I: Iterator> {
+    iter: I,
+    prev: Option<I::Item>,
+}
+
+impl<I> Iterator for Pairwise<I>
+where
+    I: Iterator,
+    I::Item: Clone,
+{
+    type Item = (I::Item, I::Item);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let prev = match self.prev.take() {
+            Some(prev) => prev,
+            None => self.iter.next()?,
+        };
+        let next = self.iter.next()?;
+        self.prev = Some(next.clone());
+
+        Some((prev, next))
+    }
+}
+
+/// Iterator mapping windows of consecutive elements. Created by [`IterExt::windows_map`].
+pub struct WindowsMap<I: Iterator, F, const N: usize> {
+    iter: I,
+    window: Vec<I::Item>,
+    f: F,
+}
+
+impl<I, F, R, const N: usize> Iterator for WindowsMap<I, F, N>
+where
+    I: Iterator,
+    F: FnMut(&[I::Item; N]) -> R,
+{
+    type Item = R;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.window.len() == N {
+            self.window.remove(0);
+        }
+        while self.window.len() < N {
+            self.window.push(self.iter.next()?);
+        }
+
+        let window: &[I::Item; N] = self.window.as_slice().try_into().unwrap();
+        Some((self.f)(window))
+    }
+}
+
+//----------
+// Tests
+//----------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pairwise() {
+        let pairs: Vec<_> = [1, 2, 4, 7].iter().pairwise().map(|(a, b)| b - a).collect();
+        assert_eq!(pairs, [1, 2, 3]);
+
+        assert_eq!([1].iter().pairwise().count(), 0);
+        assert_eq!(std::iter::empty::<u8>().pairwise().count(), 0);
+    }
+
+    #[test]
+    fn test_windows_map() {
+        let sums: Vec<i32> = (1..=5).windows_map(|[a, b, c]| a + b + c).collect();
+        assert_eq!(sums, [6, 9, 12]);
+
+        let singles: Vec<i32> = (1..=3).windows_map(|[a]| *a).collect();
+        assert_eq!(singles, [1, 2, 3]);
+
+        assert_eq!((1..=2).windows_map(|w: &[i32; 3]| w[0]).count(), 0);
+    }
+
+    #[test]
+    fn test_checked() {
+        assert_eq!([1u32, 2, 3].into_iter().checked_sum(), Some(6));
+        assert_eq!([u64::MAX, 1].into_iter().checked_sum(), None);
+        assert_eq!(std::iter::empty::<i64>().checked_sum(), Some(0));
+
+        assert_eq!([2usize, 3, 4].into_iter().checked_product(), Some(24));
+        assert_eq!([1 << 32, 1u64 << 32].into_iter().checked_product(), None);
+        assert_eq!(std::iter::empty::<u32>().checked_product(), Some(1));
+    }
+}
@@ -1,4 +1,47 @@
-#![feature(iter_map_windows)]
+#![cfg_attr(feature = "simd", feature(portable_simd))]
+#![forbid(unsafe_code)]
+
+/// Declares [`runner`] dispatching day numbers to the `run` functions of their modules. A new day only needs its
+/// module and an entry here.
+macro_rules! days {
+    ($($day:literal => $module:ident),* $(,)?) => {
+        /// Returns the `run` function of `day`, or `None` if there is no such day.
+        pub fn runner(day: u8) -> Option<fn(&str) -> Result<[answer::Answer; 2], error::AocError>> {
+            match day {
+                $($day => Some($module::run),)*
+                _ => None,
+            }
+        }
+    };
+}
+
+days! {
+    1 => day01,
+    2 => day02,
+    3 => day03,
+    4 => day04,
+    5 => day05,
+    6 => day06,
+    7 => day07,
+    8 => day08,
+    9 => day09,
+    10 => day10,
+    11 => day11,
+    12 => day12,
+    13 => day13,
+    14 => day14,
+    15 => day15,
+    16 => day16,
+    17 => day17,
+    18 => day18,
+    19 => day19,
+    20 => day20,
+    21 => day21,
+    22 => day22,
+    23 => day23,
+    24 => day24,
+    25 => day25,
+}
 
 pub mod day01;
 pub mod day02;
@@ -10,3 +53,46 @@ pub mod day07;
 pub mod day08;
 pub mod day09;
 pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day16;
+pub mod day17;
+pub mod day18;
+pub mod day19;
+pub mod day20;
+pub mod day21;
+pub mod day22;
+pub mod day23;
+pub mod day24;
+pub mod day25;
+
+pub mod answer;
+pub mod arena;
+pub mod automaton;
+pub mod bitset;
+pub mod combinator;
+pub mod counter;
+pub mod cycle;
+pub mod dsu;
+pub mod error;
+pub mod geometry;
+pub mod graph;
+pub mod grid;
+pub mod holiday_hash;
+pub mod input;
+pub mod interval;
+pub mod iter_ext;
+pub mod math;
+pub mod parse;
+pub mod priority_queue;
+pub mod range_map;
+pub mod rng;
+pub mod scan;
+pub mod search;
+pub mod sparse_grid;
+pub mod synthetic;
+mod testing;
+pub mod token;
@@ -1,24 +1,325 @@
 use aoc2023::*;
-use std::env;
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    env, fs,
+    io::{self, Read},
+    sync::atomic::{AtomicUsize, Ordering},
+    time::{Duration, Instant},
+};
 
+/// Allocator counting the number of allocations and the bytes requested, reported by the benchmark mode.
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static ALLOCATED_BYTES: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        ALLOCATED_BYTES.fetch_add(layout.size(), Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// Options doing something else than solving the day with its default algorithms, which need the whole input.
+const DAY_OPTIONS: [&str; 7] = [
+    "--dot",
+    "--validate",
+    "--render",
+    "--rankings",
+    "--loads",
+    "--strategy",
+    "--limits",
+];
+
+/// Usage: `aoc2023 <day> [--stdin] [--json] [--bench] [--threads <n>] [--dot] [--validate] [--strategy <name>]
+/// [--limits <cubes>] [--render] [--rankings] [--loads <file>]`. The input is read from the inputs directory, or from
+/// the standard input with `--stdin`. `--json` prints the answers as a JSON object. The benchmark mode reports the
+/// running time and the allocations of the day, excluding the loading of the input, except for days 01 and 04 which
+/// stream their input file one line at a time. `--threads` sets the number of threads of the days solved in parallel,
+/// all cores by default. `--dot` prints the graph of the input in the GraphViz DOT language instead of solving the day,
+/// only for days 08 and 20. `--validate` reports the overlaps and gaps between the ranges of the almanac maps instead,
+/// only for day 05. `--strategy <name>` selects the algorithm of the days offering several, see [`run_with_strategy`].
+/// `--limits` sets the cubes in the bag of day 02, like `red=12,green=13,blue=14`. Instead of solving the day,
+/// `--render` prints the grid of days 03 and 10 with its highlighted cells, `--rankings` prints the hands of day 07 in
+/// ranked order for both parts, and `--loads` writes the loads of day 14 over the spin cycles to a CSV file.
+///
+/// `aoc2023 gen-input <day> <size> [--seed <n>]` prints a synthetic input of the day instead, see the `synthetic`
+/// module for the meaning of the size of each day.
 fn main() {
     let input = env::args().nth(1);
     if input.is_none() {
         println!("No input argument.");
         return;
     }
+    if input.as_deref() == Some("gen-input") {
+        gen_input(&env::args().skip(2).collect::<Vec<_>>());
+        return;
+    }
+    let options: Vec<String> = env::args().skip(2).collect();
+
+    if let Some(i) = options.iter().position(|arg| arg == "--threads") {
+        let Some(n_threads) = options.get(i + 1).and_then(|n| n.parse().ok()) else {
+            println!("Invalid number of threads.");
+            return;
+        };
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(n_threads)
+            .build_global()
+            .unwrap();
+    }
+
+    let day = input.unwrap().parse().ok();
+    let Some(run) = day.and_then(runner) else {
+        println!("Invalid input argument.");
+        return;
+    };
+
+    let stdin = options.iter().any(|arg| arg == "--stdin");
+    if !stdin && !options.iter().any(|arg| DAY_OPTIONS.contains(&arg.as_str())) {
+        let since = allocated();
+        let start = Instant::now();
+        if let Some(answers) = run_streamed(day.unwrap()) {
+            report(day.unwrap(), answers, start.elapsed(), since, &options);
+            return;
+        }
+    }
+
+    let input = if stdin {
+        let mut buf = String::new();
+        io::stdin()
+            .read_to_string(&mut buf)
+            .map(|_| input::normalize(&buf))
+            .map_err(|e| error::AocError::io("<stdin>", e))
+    } else {
+        input::load(day.unwrap())
+    };
+    let input = match input {
+        Ok(input) => input,
+        Err(e) => {
+            println!("{e}");
+            return;
+        }
+    };
+
+    if options.iter().any(|arg| arg == "--dot") {
+        let dot = match day {
+            Some(8) => day08::network_dot(&input),
+            Some(20) => day20::network_dot(&input),
+            _ => {
+                println!("No graph to export for this day.");
+                return;
+            }
+        };
+        match dot {
+            Ok(dot) => print!("{dot}"),
+            Err(e) => println!("{e}"),
+        }
+        return;
+    }
+
+    if options.iter().any(|arg| arg == "--validate") {
+        match day {
+            Some(5) => match day05::validate_almanac(&input) {
+                Ok(issues) => {
+                    issues.iter().for_each(|issue| println!("{issue}"));
+                    println!("{} issues found.", issues.len());
+                }
+                Err(e) => println!("{e}"),
+            },
+            _ => println!("No validation for this day."),
+        }
+        return;
+    }
+
+    if options.iter().any(|arg| arg == "--render") {
+        let render = match day {
+            Some(3) => day03::render(&input),
+            Some(10) => day10::render(&input),
+            _ => {
+                println!("No grid to render for this day.");
+                return;
+            }
+        };
+        match render {
+            Ok(render) => println!("{render}"),
+            Err(e) => println!("{e}"),
+        }
+        return;
+    }
+
+    if options.iter().any(|arg| arg == "--rankings") {
+        if day != Some(7) {
+            println!("No rankings for this day.");
+            return;
+        }
+        let rankings = [
+            ("Part 1", day07::rankings(&input, &day07::Standard)),
+            ("Part 2", day07::rankings(&input, &day07::Wildcard(day07::Card::J))),
+        ];
+        for (title, rankings) in rankings {
+            match rankings {
+                Ok(rankings) => {
+                    println!("{title} rankings:");
+                    rankings.iter().for_each(|hand| println!("{hand}"));
+                }
+                Err(e) => {
+                    println!("{e}");
+                    return;
+                }
+            }
+        }
+        return;
+    }
+
+    if let Some(path) = option_value(&options, "--loads") {
+        let written = match day {
+            Some(14) => day14::loads_over_cycles(&input)
+                .and_then(|csv| fs::write(path, csv).map_err(|e| error::AocError::io(path, e))),
+            _ => {
+                println!("No loads to export for this day.");
+                return;
+            }
+        };
+        match written {
+            Ok(()) => println!("Loads written to {path}."),
+            Err(e) => println!("{e}"),
+        }
+        return;
+    }
+
+    let since = allocated();
+    let strategy = option_value(&options, "--strategy");
+    let limits = option_value(&options, "--limits");
+    if limits.is_some() && day != Some(2) {
+        println!("No limits to set for this day.");
+        return;
+    }
+    let start = Instant::now();
+    let answers = match (strategy, limits) {
+        (Some(strategy), _) => run_with_strategy(day.unwrap(), &input, strategy),
+        (None, Some(limits)) => Some(
+            day02::parse_limits(limits)
+                .map_err(|e| e.in_day(2))
+                .and_then(|limits| day02::run_with(&input, &limits)),
+        ),
+        (None, None) => Some(run(&input)),
+    };
+    let elapsed = start.elapsed();
+    let Some(answers) = answers else {
+        println!("No strategy to select for this day.");
+        return;
+    };
+    report(day.unwrap(), answers, elapsed, since, &options);
+}
+
+/// Prints the answers of `day`, or its error, as text or as a JSON object with `--json`. With `--bench`, also prints
+/// the running time and the allocations made since `since`, as returned by [`allocated`].
+fn report(
+    day: u8,
+    answers: Result<[answer::Answer; 2], error::AocError>,
+    elapsed: Duration,
+    since: (usize, usize),
+    options: &[String],
+) {
+    let [part1, part2] = match answers {
+        Ok(answers) => answers,
+        Err(e) => {
+            println!("{e}");
+            return;
+        }
+    };
+
+    if options.iter().any(|arg| arg == "--json") {
+        let (part1, part2) = (part1.to_json(), part2.to_json());
+        println!(r#"{{"day": {day}, "part1": {part1}, "part2": {part2}}}"#);
+    } else {
+        println!("Part 1: {part1}");
+        println!("Part 2: {part2}");
+    }
+
+    if options.iter().any(|arg| arg == "--bench") {
+        let (allocations, bytes) = allocated();
+        let (allocations, bytes) = (allocations - since.0, bytes - since.1);
+        println!("Elapsed: {elapsed:?}, allocations: {allocations} ({bytes} bytes)");
+    }
+}
+
+/// Returns the number of allocations and the bytes allocated so far.
+fn allocated() -> (usize, usize) {
+    (
+        ALLOCATIONS.load(Ordering::Relaxed),
+        ALLOCATED_BYTES.load(Ordering::Relaxed),
+    )
+}
+
+/// Solves `day` streaming its input file one line at a time, instead of loading it whole, or returns `None` if the day
+/// needs the whole input. Only days 01 and 04 are streamed.
+fn run_streamed(day: u8) -> Option<Result<[answer::Answer; 2], error::AocError>> {
+    match day {
+        1 => Some(input::lines(1).and_then(day01::run_lines)),
+        4 => Some(input::lines(4).and_then(day04::run_lines)),
+        _ => None,
+    }
+}
+
+/// Returns the argument following the option `name`, empty if it is the last argument, or `None` without the option.
+fn option_value<'a>(options: &'a [String], name: &str) -> Option<&'a str> {
+    let i = options.iter().position(|arg| arg == name)?;
+    Some(options.get(i + 1).map_or("", String::as_str))
+}
+
+/// Solves `day` with the algorithm named `strategy`, or returns `None` if the day offers a single one:
+///
+/// - Day 03: the search of the gears, `indexed` (the default) or `naive`;
+/// - Day 05: the search of the lowest location of part 2, `intervals` (the default), `brute-force` or `reverse`;
+/// - Day 06: the count of the ways to beat a record, `binary-search` (the default) or `closed-form`;
+/// - Day 17: the queue of Dijkstra's algorithm, `buckets` (the default) or `heap`;
+/// - Day 25: the minimum cut algorithm, `flow` (the default) or `karger`.
+fn run_with_strategy(day: u8, input: &str, strategy: &str) -> Option<Result<[answer::Answer; 2], error::AocError>> {
+    match day {
+        3 => Some(strategy.parse().and_then(|search| day03::run_with(input, search))),
+        5 => Some(strategy.parse().and_then(|strategy| day05::run_with(input, strategy))),
+        6 => Some(strategy.parse().and_then(|method| day06::run_with(input, method))),
+        17 => Some(strategy.parse().and_then(|queue| day17::run_with(input, queue))),
+        25 => Some(strategy.parse().and_then(|min_cut| day25::run_with(input, min_cut))),
+        _ => None,
+    }
+}
+
+/// Prints a synthetic input, given the arguments of the `gen-input` subcommand: the day, the size and optionally
+/// `--seed <n>`.
+fn gen_input(args: &[String]) {
+    /// Seed of the inputs generated without `--seed`, so they are the same on every run.
+    const DEFAULT_SEED: u64 = 2023;
+
+    let (Some(day), Some(size)) = (
+        args.first().and_then(|day| day.parse().ok()),
+        args.get(1).and_then(|size| size.parse().ok()),
+    ) else {
+        println!("Usage: aoc2023 gen-input <day> <size> [--seed <n>]");
+        return;
+    };
+    let seed = match args.iter().position(|arg| arg == "--seed") {
+        Some(i) => match args.get(i + 1).and_then(|n| n.parse().ok()).filter(|&n| n != 0) {
+            Some(seed) => seed,
+            None => {
+                println!("Invalid seed, it must be a positive integer.");
+                return;
+            }
+        },
+        None => DEFAULT_SEED,
+    };
 
-    match input.unwrap().parse().unwrap() {
-        1 => day01::run(),
-        2 => day02::run(),
-        3 => day03::run(),
-        4 => day04::run(),
-        5 => day05::run(),
-        6 => day06::run(),
-        7 => day07::run(),
-        8 => day08::run(),
-        9 => day09::run(),
-        10 => day10::run(),
-        _ => println!("Invalid input argument."),
+    match synthetic::generate(day, size, &mut rng::Rng::new(seed)) {
+        Some(input) => print!("{input}"),
+        None => println!("Invalid day."),
     }
 }
@@ -0,0 +1,555 @@
+//! Number theory helpers: greatest common divisor, least common multiple, modular arithmetic and the Chinese
+//! remainder theorem, which also aligns periodic events. Also exact solving of linear systems over the integers, and a
+//! `Rational` type for exact geometry.
+
+use std::{
+    cmp::Ordering,
+    fmt,
+    ops::{Add, Div, Mul, Neg, Sub},
+};
+
+/// Returns the greatest common divisor of `a` and `b`. `gcd(0, 0)` is 0.
+pub fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// Returns the least common multiple of `a` and `b`, or `None` if it overflows. The result is 0 if any of them is 0.
+pub fn lcm(a: u64, b: u64) -> Option<u64> {
+    if a == 0 || b == 0 {
+        Some(0)
+    } else {
+        (a / gcd(a, b)).checked_mul(b)
+    }
+}
+
+/// Returns the greatest common divisor of all values, or 0 for an empty slice.
+pub fn gcd_of(values: &[u64]) -> u64 {
+    values.iter().fold(0, |acc, &v| gcd(acc, v))
+}
+
+/// Returns the least common multiple of all values, 1 for an empty slice, or `None` if it overflows.
+pub fn lcm_of(values: &[u64]) -> Option<u64> {
+    values.iter().try_fold(1, |acc, &v| lcm(acc, v))
+}
+
+/// Extended Euclidean algorithm.
+///
+/// Returns `(g, x, y)` such that `g = gcd(a, b)` and `a * x + b * y = g`. `g` is never negative.
+pub fn extended_gcd(a: i64, b: i64) -> (i64, i64, i64) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+
+    while r != 0 {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_x, x) = (x, old_x - q * x);
+        (old_y, y) = (y, old_y - q * y);
+    }
+
+    if old_r < 0 {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/// Returns the modular inverse of `a` modulo `m`, in the range `[0, m)`, or `None` if `a` and `m` are not coprime.
+///
+/// `m` must be positive.
+pub fn mod_inverse(a: i64, m: i64) -> Option<i64> {
+    let (g, x, _) = extended_gcd(a.rem_euclid(m), m);
+    (g == 1).then(|| x.rem_euclid(m))
+}
+
+/// Returns `base^exp mod m`, in the range `[0, m)`. `m` must be positive.
+pub fn mod_pow(base: i64, mut exp: u64, m: i64) -> i64 {
+    let m = m as i128;
+    let mut base = (base as i128).rem_euclid(m);
+    let mut result = 1 % m;
+
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = result * base % m;
+        }
+        base = base * base % m;
+        exp >>= 1;
+    }
+
+    result as i64
+}
+
+/// Solves a system of congruences `x ≡ residue (mod modulus)` with the Chinese remainder theorem.
+///
+/// Moduli do not need to be pairwise coprime. Returns `(x, m)`, where `m` is the least common multiple of the moduli
+/// and `x`, in the range `[0, m)`, is the smallest non-negative solution; every solution is `x + k * m`. Returns
+/// `None` if the congruences are incompatible, a modulus is not positive, or the least common multiple of the moduli
+/// overflows an `i64`.
+pub fn crt(congruences: &[(i64, i64)]) -> Option<(i64, i64)> {
+    congruences.iter().try_fold((0, 1), |(x, m), &(residue, modulus)| {
+        if modulus <= 0 {
+            return None;
+        }
+
+        // x + m * k ≡ residue (mod modulus)  =>  m * k ≡ residue - x (mod modulus)
+        let (g, inv, _) = extended_gcd(m, modulus);
+        let diff = residue.checked_sub(x)?;
+        if diff % g != 0 {
+            return None;
+        }
+
+        let step = modulus / g;
+        let k = ((diff / g) as i128 * inv as i128).rem_euclid(step as i128) as i64;
+        let lcm = m.checked_mul(step)?;
+        Some(((x as i128 + m as i128 * k as i128).rem_euclid(lcm as i128) as i64, lcm))
+    })
+}
+
+/// Returns the first step at which periodic events coincide.
+///
+/// Each event is given as `(first_hit, period)`: it happens at steps `first_hit`, `first_hit + period`,
+/// `first_hit + 2 * period`, and so on. Unlike a plain least common multiple, the events do not need to start at step
+/// 0, and the result is never before the first hit of any event. Returns `None` if the events never coincide, a period
+/// is not positive, or the step overflows an `i64`. First hits must be non-negative.
+pub fn first_coincidence(events: &[(i64, i64)]) -> Option<i64> {
+    let (step, lcm) = crt(events)?;
+
+    // the smallest solution of the congruences may come before the first hit of some event, skip whole cycles until
+    // every event has started
+    let last_first_hit = events.iter().map(|&(first_hit, _)| first_hit).max().unwrap_or(0);
+    if step < last_first_hit {
+        let gap = last_first_hit - step;
+        let cycles = gap / lcm + (gap % lcm != 0) as i64;
+        cycles.checked_mul(lcm)?.checked_add(step)
+    } else {
+        Some(step)
+    }
+}
+
+//----------
+// Linear systems
+//----------
+
+/// Solves the square linear system `a * x = b` exactly, without floating point.
+///
+/// Uses fraction-free Gauss-Jordan elimination over `i128`, dividing every row by the gcd of its entries to keep the
+/// values small. Returns each unknown as a reduced fraction `(numerator, denominator)` with a positive denominator, or
+/// `None` if the system has no unique solution (singular matrix) or an intermediate value overflows.
+///
+/// # Panics
+///
+/// Panics if `a` is not a square matrix with the same number of rows as `b`.
+pub fn solve_linear_system(a: &[Vec<i128>], b: &[i128]) -> Option<Vec<(i128, i128)>> {
+    let n = b.len();
+    assert!(
+        a.len() == n && a.iter().all(|row| row.len() == n),
+        "expected a square system"
+    );
+
+    // augmented matrix [a | b]
+    let mut m: Vec<Vec<i128>> = a
+        .iter()
+        .zip(b)
+        .map(|(row, &rhs)| row.iter().copied().chain([rhs]).collect())
+        .collect();
+    m.iter_mut().for_each(|row| normalize_row(row));
+
+    for col in 0..n {
+        // the smallest pivot keeps the multipliers of the other rows small
+        let pivot = (col..n)
+            .filter(|&row| m[row][col] != 0)
+            .min_by_key(|&row| m[row][col].unsigned_abs())?;
+        m.swap(col, pivot);
+        let pivot_row = m[col].clone();
+
+        for (row, entries) in m.iter_mut().enumerate() {
+            if row == col || entries[col] == 0 {
+                continue;
+            }
+            // row = row * p - pivot_row * f, which zeroes `entries[col]` while keeping integer entries
+            let g = gcd_i128(pivot_row[col], entries[col]);
+            let (p, f) = (pivot_row[col] / g, entries[col] / g);
+            for (entry, &pivot_entry) in entries.iter_mut().zip(&pivot_row) {
+                *entry = entry.checked_mul(p)?.checked_sub(pivot_entry.checked_mul(f)?)?;
+            }
+            normalize_row(entries);
+        }
+    }
+
+    Some(
+        (0..n)
+            .map(|i| {
+                let (num, den) = (m[i][n], m[i][i]);
+                let g = gcd_i128(num, den) * den.signum();
+                (num / g, den / g)
+            })
+            .collect(),
+    )
+}
+
+/// Same as [`solve_linear_system`], but returns `None` as well when any unknown is not an integer.
+pub fn solve_linear_system_integer(a: &[Vec<i128>], b: &[i128]) -> Option<Vec<i128>> {
+    solve_linear_system(a, b)?
+        .into_iter()
+        .map(|(num, den)| (den == 1).then_some(num))
+        .collect()
+}
+
+/// Divides all entries of a row by their greatest common divisor.
+fn normalize_row(row: &mut [i128]) {
+    let g = row.iter().fold(0, |acc, &v| gcd_i128(acc, v));
+    if g > 1 {
+        row.iter_mut().for_each(|v| *v /= g);
+    }
+}
+
+//----------
+// Rationals
+//----------
+
+/// Exact fraction of two `i128`s, always reduced and with a positive denominator.
+///
+/// Arithmetic panics on overflow in debug builds, like the integer types, so the operands must stay well below
+/// `i128::MAX` (about 1.7 * 10^38) once multiplied together. The `checked_` methods return `None` instead.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Rational {
+    num: i128,
+    den: i128,
+}
+
+impl Rational {
+    /// Creates the fraction `num / den`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `den` is 0.
+    pub fn new(num: i128, den: i128) -> Rational {
+        assert!(den != 0, "zero denominator");
+        let g = gcd_i128(num, den) * den.signum();
+        Rational {
+            num: num / g,
+            den: den / g,
+        }
+    }
+
+    /// Returns the numerator, with the sign of the fraction.
+    pub fn numer(&self) -> i128 {
+        self.num
+    }
+
+    /// Returns the denominator, always positive.
+    pub fn denom(&self) -> i128 {
+        self.den
+    }
+
+    /// Checks whether the fraction is an integer.
+    pub fn is_integer(&self) -> bool {
+        self.den == 1
+    }
+
+    /// Returns the nearest `f64`.
+    pub fn to_f64(&self) -> f64 {
+        self.num as f64 / self.den as f64
+    }
+
+    /// Returns `self + other`, or `None` if an intermediate value overflows.
+    pub fn checked_add(self, other: Rational) -> Option<Rational> {
+        let g = gcd_i128(self.den, other.den);
+        let num = self
+            .num
+            .checked_mul(other.den / g)?
+            .checked_add(other.num.checked_mul(self.den / g)?)?;
+        Some(Rational::new(num, (self.den / g).checked_mul(other.den)?))
+    }
+
+    /// Returns `self * other`, or `None` if an intermediate value overflows.
+    pub fn checked_mul(self, other: Rational) -> Option<Rational> {
+        let (g1, g2) = (
+            gcd_i128(self.num, other.den).max(1),
+            gcd_i128(other.num, self.den).max(1),
+        );
+        Some(Rational::new(
+            (self.num / g1).checked_mul(other.num / g2)?,
+            (self.den / g2).checked_mul(other.den / g1)?,
+        ))
+    }
+
+    /// Compares `self` and `other`, or returns `None` if the cross products overflow.
+    pub fn checked_cmp(&self, other: &Rational) -> Option<Ordering> {
+        Some(self.num.checked_mul(other.den)?.cmp(&other.num.checked_mul(self.den)?))
+    }
+}
+
+impl From<i64> for Rational {
+    fn from(n: i64) -> Rational {
+        Rational { num: n as i128, den: 1 }
+    }
+}
+
+impl From<i128> for Rational {
+    fn from(n: i128) -> Rational {
+        Rational { num: n, den: 1 }
+    }
+}
+
+impl Add for Rational {
+    type Output = Rational;
+
+    fn add(self, other: Rational) -> Rational {
+        // dividing by the gcd of the denominators first keeps the intermediate values small
+        let g = gcd_i128(self.den, other.den);
+        Rational::new(
+            self.num * (other.den / g) + other.num * (self.den / g),
+            self.den / g * other.den,
+        )
+    }
+}
+
+impl Sub for Rational {
+    type Output = Rational;
+
+    fn sub(self, other: Rational) -> Rational {
+        self + -other
+    }
+}
+
+impl Mul for Rational {
+    type Output = Rational;
+
+    fn mul(self, other: Rational) -> Rational {
+        // cross reduction keeps the intermediate values small
+        let (g1, g2) = (
+            gcd_i128(self.num, other.den).max(1),
+            gcd_i128(other.num, self.den).max(1),
+        );
+        Rational::new((self.num / g1) * (other.num / g2), (self.den / g2) * (other.den / g1))
+    }
+}
+
+impl Div for Rational {
+    type Output = Rational;
+
+    /// # Panics
+    ///
+    /// Panics if `other` is 0.
+    fn div(self, other: Rational) -> Rational {
+        assert!(other.num != 0, "division by zero");
+        self * Rational::new(other.den, other.num)
+    }
+}
+
+impl Neg for Rational {
+    type Output = Rational;
+
+    fn neg(self) -> Rational {
+        Rational {
+            num: -self.num,
+            den: self.den,
+        }
+    }
+}
+
+impl Ord for Rational {
+    fn cmp(&self, other: &Rational) -> Ordering {
+        // denominators are positive, so cross multiplying keeps the order
+        (self.num * other.den).cmp(&(other.num * self.den))
+    }
+}
+
+impl PartialOrd for Rational {
+    fn partial_cmp(&self, other: &Rational) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for Rational {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.den == 1 {
+            write!(f, "{}", self.num)
+        } else {
+            write!(f, "{}/{}", self.num, self.den)
+        }
+    }
+}
+
+/// Greatest common divisor of two signed values, always non-negative.
+fn gcd_i128(a: i128, b: i128) -> i128 {
+    let (mut a, mut b) = (a.unsigned_abs(), b.unsigned_abs());
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a as i128
+}
+
+//----------
+// Tests
+//----------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gcd_lcm() {
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(gcd(17, 5), 1);
+        assert_eq!(gcd(0, 7), 7);
+        assert_eq!(gcd(0, 0), 0);
+
+        assert_eq!(lcm(4, 6), Some(12));
+        assert_eq!(lcm(0, 6), Some(0));
+        assert_eq!(lcm(u64::MAX, 2), None);
+
+        assert_eq!(gcd_of(&[12, 18, 30]), 6);
+        assert_eq!(lcm_of(&[2, 3, 4]), Some(12));
+        assert_eq!(lcm_of(&[]), Some(1));
+
+        // the primes up to 59 multiply beyond a u64
+        let primes = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59];
+        assert_eq!(lcm_of(&primes[..15]), Some(614_889_782_588_491_410));
+        assert_eq!(lcm_of(&primes), None);
+    }
+
+    #[test]
+    fn test_extended_gcd() {
+        for (a, b) in [(240, 46), (46, 240), (-240, 46), (7, 0), (0, -7), (13, 13)] {
+            let (g, x, y) = extended_gcd(a, b);
+            assert_eq!(g, gcd(a.unsigned_abs(), b.unsigned_abs()) as i64);
+            assert_eq!(a * x + b * y, g);
+        }
+    }
+
+    #[test]
+    fn test_mod_inverse() {
+        assert_eq!(mod_inverse(3, 11), Some(4));
+        assert_eq!(mod_inverse(-3, 11), Some(7));
+        assert_eq!(mod_inverse(10, 17), Some(12));
+        assert_eq!(mod_inverse(6, 9), None);
+    }
+
+    #[test]
+    fn test_mod_pow() {
+        assert_eq!(mod_pow(2, 10, 1000), 24);
+        assert_eq!(mod_pow(-2, 3, 5), 2);
+        assert_eq!(mod_pow(5, 0, 1), 0);
+    }
+
+    #[test]
+    fn test_crt() {
+        // coprime moduli
+        assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        // non-coprime moduli
+        assert_eq!(crt(&[(2, 4), (4, 6)]), Some((10, 12)));
+        assert_eq!(crt(&[(1, 4), (2, 6)]), None);
+        // negative residues and trivial systems
+        assert_eq!(crt(&[(-1, 5)]), Some((4, 5)));
+        assert_eq!(crt(&[]), Some((0, 1)));
+        // moduli that are not positive
+        assert_eq!(crt(&[(2, 3), (0, 0)]), None);
+        assert_eq!(crt(&[(1, -4)]), None);
+        // intermediate products of large moduli do not overflow
+        let (p, q) = (1_000_000_007, 998_244_353);
+        let (x, m) = crt(&[(0, p), (1, q)]).unwrap();
+        assert_eq!((x % p, x % q, m), (0, 1, p * q));
+        // but their least common multiple does
+        assert_eq!(crt(&[(0, p), (1, q), (2, 1_000_000_009)]), None);
+    }
+
+    #[test]
+    fn test_first_coincidence() {
+        // events starting at step 0 align at the least common multiple of the periods
+        assert_eq!(first_coincidence(&[(0, 4), (0, 6)]), Some(0));
+        assert_eq!(first_coincidence(&[(4, 4), (6, 6)]), Some(12));
+
+        // offsets that a plain lcm ignores
+        assert_eq!(first_coincidence(&[(2, 3), (3, 5)]), Some(8));
+
+        // the solution of the congruences comes before the first hit of an event
+        assert_eq!(first_coincidence(&[(1, 2), (13, 3)]), Some(13));
+
+        assert_eq!(first_coincidence(&[(1, 2), (2, 4)]), None);
+        assert_eq!(first_coincidence(&[(1, 2), (3, 0)]), None);
+        // skipping to the first hit overflows
+        assert_eq!(first_coincidence(&[(0, i64::MAX - 1), (i64::MAX, 1)]), None);
+        assert_eq!(first_coincidence(&[]), Some(0));
+    }
+
+    #[test]
+    fn test_linear_system() {
+        // 2x + y - z = 8, -3x - y + 2z = -11, -2x + y + 2z = -3  =>  (2, 3, -1)
+        let a = vec![vec![2, 1, -1], vec![-3, -1, 2], vec![-2, 1, 2]];
+        let b = [8, -11, -3];
+        assert_eq!(solve_linear_system_integer(&a, &b), Some(vec![2, 3, -1]));
+
+        // needs a row swap, fractional solution: y = 1/2, x = 3/4
+        let a = vec![vec![0, 2], vec![4, 0]];
+        let b = [1, 3];
+        assert_eq!(solve_linear_system(&a, &b), Some(vec![(3, 4), (1, 2)]));
+        assert_eq!(solve_linear_system_integer(&a, &b), None);
+
+        // singular
+        let a = vec![vec![1, 2], vec![2, 4]];
+        assert_eq!(solve_linear_system(&a, &[3, 6]), None);
+
+        // system with the magnitudes of hailstone puzzles: positions around 10^14 and velocities in the hundreds. Each
+        // hailstone is hit by a rock at position `rock` moving with velocity `vel`, and each pair of hailstones gives
+        // one linear equation on the rock's (x, y, vx, vy).
+        let (rock, vel): ([i128; 2], [i128; 2]) = ([242_369_545_669_096, 339_680_097_675_927], [-95, 131]);
+        let hail: Vec<([i128; 2], [i128; 2])> = [(1, 2, 3), (-40, 55, 7), (88, -13, 11), (17, 230, 5), (-200, -9, 2)]
+            .iter()
+            .map(|&(vx, vy, t)| {
+                let t = t * 100_000_000_000;
+                ([rock[0] + t * (vel[0] - vx), rock[1] + t * (vel[1] - vy)], [vx, vy])
+            })
+            .collect();
+        let (a, b): (Vec<Vec<i128>>, Vec<i128>) = hail
+            .windows(2)
+            .map(|w| {
+                let (([xi, yi], [vxi, vyi]), ([xj, yj], [vxj, vyj])) = (w[0], w[1]);
+                (
+                    vec![vyj - vyi, vxi - vxj, yi - yj, xj - xi],
+                    (xj * vyj - yj * vxj) - (xi * vyi - yi * vxi),
+                )
+            })
+            .unzip();
+        assert_eq!(
+            solve_linear_system_integer(&a, &b),
+            Some(vec![rock[0], rock[1], vel[0], vel[1]])
+        );
+    }
+
+    #[test]
+    fn test_rational() {
+        let half = Rational::new(2, 4);
+        assert_eq!((half.numer(), half.denom()), (1, 2));
+        assert_eq!(Rational::new(3, -6), -half);
+        assert_eq!(Rational::new(0, -5), Rational::from(0i64));
+
+        let third = Rational::new(1, 3);
+        assert_eq!(half + third, Rational::new(5, 6));
+        assert_eq!(half - third, Rational::new(1, 6));
+        assert_eq!(half * third, Rational::new(1, 6));
+        assert_eq!(half / third, Rational::new(3, 2));
+        assert!((half * Rational::from(2i64)).is_integer());
+
+        assert!(third < half && -half < third);
+        assert_eq!(Rational::new(7, 2).to_string(), "7/2");
+        assert_eq!(Rational::new(-4, 2).to_string(), "-2");
+        assert_eq!(Rational::new(-3, 4).to_f64(), -0.75);
+
+        // values with the magnitudes of hailstone puzzles
+        let big = Rational::new(400_000_000_000_000 * 1_000_003, 1_000_003);
+        assert_eq!(big, Rational::from(400_000_000_000_000i64));
+
+        assert_eq!(half.checked_add(third), Some(Rational::new(5, 6)));
+        assert_eq!(half.checked_mul(third), Some(Rational::new(1, 6)));
+        assert_eq!(third.checked_cmp(&half), Some(Ordering::Less));
+        let huge = Rational::new(i128::MAX, 3);
+        assert_eq!(huge.checked_add(huge), None);
+        assert_eq!(huge.checked_mul(Rational::from(2i64)), None);
+        assert_eq!(huge.checked_cmp(&Rational::new(i128::MAX, 7)), None);
+    }
+}
@@ -0,0 +1,132 @@
+//! Helpers for the input parsing patterns that repeat across days.
+//!
+//! Errors are reported as `AocError`s, like the `FromStr` implementations of the days, and include the line (and
+//! column, when it makes sense) where parsing failed. Line and column numbers start at 1.
+
+use crate::{error::AocError, grid::Grid};
+use std::{fmt::Display, iter, str::FromStr};
+
+/// Parses every token of a whitespace separated sequence as `T`.
+///
+/// For example, `numbers::<u32>(" 7 15 30")` returns `[7, 15, 30]`. Returns an error with the column of the first token
+/// that fails to parse.
+pub fn numbers<T>(line: &str) -> Result<Vec<T>, AocError>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    line.split_ascii_whitespace()
+        .map(|token| token.parse().map_err(|e| AocError::parse_at(line, token, e)))
+        .collect()
+}
+
+/// Returns an iterator over the blocks of an input, which are groups of lines separated by empty lines.
+///
+/// Lines may end in `\n` or `\r\n`. The trailing line ending of each block is removed, and empty blocks are skipped.
+pub fn blocks(input: &str) -> impl Iterator<Item = &str> {
+    let mut rest = input;
+    iter::from_fn(move || loop {
+        if rest.is_empty() {
+            return None;
+        }
+
+        // the block ends at the first empty line, or at the end of the input
+        let (mut end, mut next) = (0, rest.len());
+        for line in rest.split_inclusive('\n') {
+            if line.trim_end_matches(['\r', '\n']).is_empty() {
+                next = end + line.len();
+                break;
+            }
+            end += line.len();
+        }
+
+        let block = rest[..end].trim_end_matches(['\r', '\n']);
+        rest = &rest[next..];
+        if !block.is_empty() {
+            return Some(block);
+        }
+    })
+}
+
+/// Parses each non-empty line of the input as `T`.
+///
+/// Returns an error with the offending line number if any line fails to parse.
+pub fn lines_as<T>(input: &str) -> Result<Vec<T>, AocError>
+where
+    T: FromStr,
+    T::Err: Into<AocError>,
+{
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.is_empty())
+        .map(|(i, line)| line.parse().map_err(|e: T::Err| e.into().at_line(i + 1)))
+        .collect()
+}
+
+/// Parses a rectangular block of decimal digits into a grid of their values.
+///
+/// Returns an error with the line and column of the first character that is not a digit.
+pub fn digit_grid(input: &str) -> Result<Grid<u8>, AocError> {
+    for (i, line) in input.lines().enumerate() {
+        if let Some(j) = line.bytes().position(|b| !b.is_ascii_digit()) {
+            return Err(AocError::parse_at(line, &line[j..], "expected a digit.").at_line(i + 1));
+        }
+    }
+
+    Grid::from_str_with(input, |b| b - b'0')
+}
+
+//----------
+// Tests
+//----------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::Position;
+
+    #[test]
+    fn test_numbers() {
+        assert_eq!(numbers::<u32>("      7  15   30").unwrap(), [7, 15, 30]);
+        assert_eq!(numbers::<i64>("0 -3 6").unwrap(), [0, -3, 6]);
+        assert_eq!(numbers::<u8>("").unwrap(), []);
+
+        let err = numbers::<u32>("7 15x 30").unwrap_err();
+        assert_eq!(err.to_string(), "Line 1, column 3: invalid digit found in string");
+        let err = numbers::<u8>("7 256").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Line 1, column 3: number too large to fit in target type"
+        );
+    }
+
+    #[test]
+    fn test_blocks() {
+        let input = "seeds: 1 2\n\na map:\n1 2 3\n\n\nb map:\n4 5 6\n";
+        assert_eq!(
+            blocks(input).collect::<Vec<_>>(),
+            ["seeds: 1 2", "a map:\n1 2 3", "b map:\n4 5 6"]
+        );
+
+        let crlf = "\r\nseeds: 1 2\r\n\r\na map:\r\n1 2 3\r\n\r\n";
+        assert_eq!(blocks(crlf).collect::<Vec<_>>(), ["seeds: 1 2", "a map:\r\n1 2 3"]);
+    }
+
+    #[test]
+    fn test_lines_as() {
+        assert_eq!(lines_as::<u32>("1\n2\n\n3\n").unwrap(), [1, 2, 3]);
+
+        let err = lines_as::<u32>("1\n2\nx").unwrap_err();
+        assert!(err.to_string().starts_with("Line 3:"));
+    }
+
+    #[test]
+    fn test_digit_grid() {
+        let grid = digit_grid("123\n456").unwrap();
+        assert_eq!(grid[Position::new(1, 2)], 6);
+
+        let err = digit_grid("123\n4.6").unwrap_err();
+        assert_eq!(err.to_string(), "Line 2, column 2: expected a digit.");
+    }
+}
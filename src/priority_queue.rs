@@ -0,0 +1,247 @@
+//! Min-priority queue for shortest path searches.
+//!
+//! `BinaryHeap` has no decrease-key operation, so searches push a node again whenever they find a better priority for
+//! it and skip the outdated ("stale") entries when they are popped. `PriorityQueue` keeps the best priority of every
+//! node to do this bookkeeping, and also ignores pushes that would not improve it.
+//!
+//! When priorities are integers and every push is at most a small step above the last popped priority, like in
+//! Dijkstra's algorithm over small edge costs, `BucketQueue` (Dial's algorithm) replaces the heap with a ring of
+//! buckets, one per priority, making both operations constant time.
+
+use std::{
+    cmp::Ordering,
+    collections::{BinaryHeap, HashMap},
+    hash::Hash,
+};
+
+//----------
+// Structs
+//----------
+
+/// Priority queue popping the node with the lowest priority first, where every node is popped at most once per
+/// improvement of its priority.
+pub struct PriorityQueue<N, P> {
+    heap: BinaryHeap<Entry<N, P>>,
+    best: HashMap<N, P>,
+}
+
+/// Priority queue over `u64` priorities, keeping a bucket of nodes for each of the `max_step + 1` priorities above the
+/// last popped one, reused as a ring. Pushes must not be below the last popped priority nor more than `max_step` above.
+pub struct BucketQueue<N> {
+    buckets: Vec<Vec<N>>,
+    current: u64,
+    len: usize,
+    best: HashMap<N, u64>,
+}
+
+/// Entry of the heap. Ordered by `priority` only, reversed so `BinaryHeap` pops the lowest one first.
+struct Entry<N, P> {
+    priority: P,
+    node: N,
+}
+
+//----------
+// Implementations
+//----------
+
+impl<N, P> PriorityQueue<N, P>
+where
+    N: Clone + Eq + Hash,
+    P: Copy + Ord,
+{
+    /// Creates an empty queue.
+    pub fn new() -> PriorityQueue<N, P> {
+        PriorityQueue {
+            heap: BinaryHeap::new(),
+            best: HashMap::new(),
+        }
+    }
+
+    /// Pushes `node` with `priority` if it is lower than the best priority pushed for `node` so far. Returns whether
+    /// the node was pushed.
+    pub fn push(&mut self, node: N, priority: P) -> bool {
+        if self.best.get(&node).is_some_and(|&best| best <= priority) {
+            return false;
+        }
+
+        self.best.insert(node.clone(), priority);
+        self.heap.push(Entry { priority, node });
+        true
+    }
+
+    /// Removes and returns the node with the lowest priority, along with its priority. Stale entries, for nodes pushed
+    /// again with a lower priority, are skipped.
+    pub fn pop(&mut self) -> Option<(N, P)> {
+        while let Some(Entry { priority, node }) = self.heap.pop() {
+            if self.best.get(&node).is_some_and(|&best| best == priority) {
+                return Some((node, priority));
+            }
+        }
+        None
+    }
+
+    /// Returns the best priority pushed for `node`, if any.
+    pub fn best(&self, node: &N) -> Option<P> {
+        self.best.get(node).copied()
+    }
+
+    /// Consumes the queue, returning the best priority pushed for every node.
+    pub fn into_best(self) -> HashMap<N, P> {
+        self.best
+    }
+}
+
+impl<N, P> Default for PriorityQueue<N, P>
+where
+    N: Clone + Eq + Hash,
+    P: Copy + Ord,
+{
+    fn default() -> Self {
+        PriorityQueue::new()
+    }
+}
+
+impl<N, P: Ord> PartialEq for Entry<N, P> {
+    fn eq(&self, other: &Self) -> bool {
+        self.priority == other.priority
+    }
+}
+
+impl<N, P: Ord> Eq for Entry<N, P> {}
+
+impl<N, P: Ord> Ord for Entry<N, P> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.priority.cmp(&self.priority)
+    }
+}
+
+impl<N, P: Ord> PartialOrd for Entry<N, P> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<N> BucketQueue<N>
+where
+    N: Clone + Eq + Hash,
+{
+    /// Creates an empty queue for pushes at most `max_step` above the last popped priority.
+    pub fn new(max_step: u64) -> BucketQueue<N> {
+        BucketQueue {
+            buckets: vec![Vec::new(); max_step as usize + 1],
+            current: 0,
+            len: 0,
+            best: HashMap::new(),
+        }
+    }
+
+    /// Pushes `node` with `priority` if it is lower than the best priority pushed for `node` so far. Returns whether
+    /// the node was pushed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `priority` is below the last popped priority, or more than `max_step` above it.
+    pub fn push(&mut self, node: N, priority: u64) -> bool {
+        let n_buckets = self.buckets.len() as u64;
+        assert!(
+            (self.current..self.current + n_buckets).contains(&priority),
+            "priority {priority} out of the range of the queue"
+        );
+        if self.best.get(&node).is_some_and(|&best| best <= priority) {
+            return false;
+        }
+
+        self.best.insert(node.clone(), priority);
+        self.buckets[(priority % n_buckets) as usize].push(node);
+        self.len += 1;
+        true
+    }
+
+    /// Removes and returns a node with the lowest priority, along with its priority. Stale entries, for nodes pushed
+    /// again with a lower priority, are skipped.
+    pub fn pop(&mut self) -> Option<(N, u64)> {
+        let n_buckets = self.buckets.len() as u64;
+        while self.len > 0 {
+            let Some(node) = self.buckets[(self.current % n_buckets) as usize].pop() else {
+                self.current += 1;
+                continue;
+            };
+
+            self.len -= 1;
+            if self.best.get(&node).is_some_and(|&best| best == self.current) {
+                return Some((node, self.current));
+            }
+        }
+        None
+    }
+
+    /// Returns the best priority pushed for `node`, if any.
+    pub fn best(&self, node: &N) -> Option<u64> {
+        self.best.get(node).copied()
+    }
+}
+
+//----------
+// Tests
+//----------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tests() {
+        let mut queue = PriorityQueue::new();
+        assert!(queue.push('a', 5));
+        assert!(queue.push('b', 3));
+        assert!(queue.push('c', 4));
+
+        // only improvements are pushed
+        assert!(!queue.push('a', 6));
+        assert!(!queue.push('a', 5));
+        assert!(queue.push('a', 1));
+        assert_eq!(queue.best(&'a'), Some(1));
+
+        // the stale entry ('a', 5) is skipped
+        let popped: Vec<_> = std::iter::from_fn(|| queue.pop()).collect();
+        assert_eq!(popped, [('a', 1), ('b', 3), ('c', 4)]);
+        assert_eq!(queue.into_best().len(), 3);
+    }
+
+    #[test]
+    fn test_tuple_priorities() {
+        // ties on the first element are broken by the second one
+        let mut queue = PriorityQueue::default();
+        queue.push("x", (2, 9));
+        queue.push("y", (2, 1));
+        queue.push("z", (1, 5));
+        assert_eq!(queue.pop(), Some(("z", (1, 5))));
+        assert_eq!(queue.pop(), Some(("y", (2, 1))));
+        assert_eq!(queue.pop(), Some(("x", (2, 9))));
+        assert_eq!(queue.pop(), None);
+    }
+
+    #[test]
+    fn test_bucket_queue() {
+        let mut queue = BucketQueue::new(3);
+        assert!(queue.push('a', 3));
+        assert!(queue.push('b', 1));
+        assert!(!queue.push('a', 3));
+        assert!(queue.push('a', 2));
+        assert_eq!(queue.best(&'a'), Some(2));
+        assert_eq!(queue.pop(), Some(('b', 1)));
+
+        // the ring wraps around: priority 4 reuses the bucket of priority 0
+        assert!(queue.push('c', 4));
+        assert_eq!(queue.pop(), Some(('a', 2)));
+        assert_eq!(queue.pop(), Some(('c', 4)));
+        assert_eq!(queue.pop(), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "out of the range")]
+    fn test_bucket_queue_range() {
+        let mut queue = BucketQueue::new(3);
+        queue.push('a', 4);
+    }
+}
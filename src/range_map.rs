@@ -0,0 +1,277 @@
+//! Piecewise translation of integers, where ranges of values are shifted by a constant offset.
+//!
+//! Values outside every range are mapped to themselves. This is the shape of the almanac maps of day 05 ("destination
+//! start, source start, length"), and ranges of values can be mapped as a whole, splitting them at the boundaries of
+//! the entries, instead of value by value.
+
+use crate::interval::Interval;
+
+//----------
+// Structs
+//----------
+
+/// Entry of a `RangeMap`: every value in `source` is mapped to `value + offset`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct MapEntry {
+    pub source: Interval,
+    pub offset: i64,
+}
+
+/// Mapping of integers made of non-overlapping ranges, each shifted by its own offset.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RangeMap {
+    /// Entries sorted by the start of their source ranges, which do not overlap.
+    entries: Vec<MapEntry>,
+}
+
+//----------
+// Implementations
+//----------
+
+impl RangeMap {
+    /// Creates the identity map.
+    pub fn new() -> RangeMap {
+        RangeMap { entries: Vec::new() }
+    }
+
+    /// Maps `source` onto the range starting at `destination_start`. Empty ranges are ignored.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `source` overlaps the range of another entry, or the destination range does not fit in an `i64`.
+    pub fn insert(&mut self, source: Interval, destination_start: i64) {
+        if source.is_empty() {
+            return;
+        }
+
+        let idx = self.entries.partition_point(|e| e.source.start < source.start);
+        let overlaps_prev = idx > 0 && self.entries[idx - 1].source.end > source.start;
+        let overlaps_next = idx < self.entries.len() && self.entries[idx].source.start < source.end;
+        assert!(!overlaps_prev && !overlaps_next, "overlapping range map entries");
+
+        let offset = destination_start
+            .checked_sub(source.start)
+            .filter(|offset| source.end.checked_add(*offset).is_some())
+            .expect("range map destination overflows");
+        self.entries.insert(idx, MapEntry { source, offset });
+    }
+
+    /// Returns the entries, sorted by the start of their source ranges.
+    pub fn entries(&self) -> &[MapEntry] {
+        &self.entries
+    }
+
+    /// Maps a single value.
+    pub fn lookup(&self, value: i64) -> i64 {
+        let idx = self.entries.partition_point(|e| e.source.end <= value);
+        match self.entries.get(idx) {
+            Some(entry) if entry.source.contains(value) => value + entry.offset,
+            _ => value,
+        }
+    }
+
+    /// Returns every value mapped to `value`, in increasing order. There may be none, or several, as the ranges of the
+    /// entries can be mapped onto each other or onto values outside every range.
+    pub fn preimages(&self, value: i64) -> Vec<i64> {
+        let mut preimages: Vec<_> = self
+            .entries
+            .iter()
+            .filter_map(|e| value.checked_sub(e.offset).filter(|&source| e.source.contains(source)))
+            .collect();
+
+        // values outside every range map to themselves
+        let idx = self.entries.partition_point(|e| e.source.end <= value);
+        if self.entries.get(idx).is_none_or(|e| !e.source.contains(value)) {
+            preimages.push(value);
+        }
+
+        preimages.sort_unstable();
+        preimages
+    }
+
+    /// Maps all values of `interval`, returning the resulting intervals in the order of the source values they come
+    /// from. The intervals are not merged, and may overlap.
+    pub fn lookup_range(&self, interval: Interval) -> Vec<Interval> {
+        self.pieces(interval)
+            .into_iter()
+            .map(|(piece, offset)| piece.offset(offset))
+            .collect()
+    }
+
+    /// Returns the map equivalent to applying `self` and then `other`.
+    pub fn compose(&self, other: &RangeMap) -> RangeMap {
+        // outside the ranges of both maps every value is mapped to itself
+        let starts = self.entries.iter().chain(&other.entries).map(|e| e.source.start);
+        let ends = self.entries.iter().chain(&other.entries).map(|e| e.source.end);
+        let (Some(start), Some(end)) = (starts.min(), ends.max()) else {
+            return RangeMap::new();
+        };
+
+        let mut entries: Vec<MapEntry> = Vec::new();
+        for (piece, offset) in self.pieces(Interval::new(start, end)) {
+            for (sub, other_offset) in other.pieces(piece.offset(offset)) {
+                let entry = MapEntry {
+                    source: sub.offset(-offset),
+                    offset: offset + other_offset,
+                };
+
+                // entries mapping values to themselves are dropped, and neighbours with the same offset joined
+                match entries.last_mut() {
+                    _ if entry.offset == 0 => {}
+                    Some(last) if last.offset == entry.offset && last.source.end == entry.source.start => {
+                        last.source.end = entry.source.end;
+                    }
+                    _ => entries.push(entry),
+                }
+            }
+        }
+
+        RangeMap { entries }
+    }
+
+    /// Splits `interval` at the boundaries of the entries, returning the pieces in order, each with the offset applied
+    /// to its values (0 for the pieces not covered by any entry).
+    fn pieces(&self, interval: Interval) -> Vec<(Interval, i64)> {
+        let mut pieces = Vec::new();
+        let mut rest = interval;
+
+        let first = self.entries.partition_point(|e| e.source.end <= interval.start);
+        for entry in &self.entries[first..] {
+            if rest.is_empty() || entry.source.start >= rest.end {
+                break;
+            }
+
+            let (before, after) = rest.difference(&entry.source);
+            if let Some(before) = before {
+                pieces.push((before, 0));
+            }
+            if let Some(common) = rest.intersection(&entry.source) {
+                pieces.push((common, entry.offset));
+            }
+            rest = after.unwrap_or(Interval::new(rest.end, rest.end));
+        }
+
+        if !rest.is_empty() {
+            pieces.push((rest, 0));
+        }
+        pieces
+    }
+}
+
+impl FromIterator<(Interval, i64)> for RangeMap {
+    /// Builds a map from pairs of source ranges and destination starts.
+    fn from_iter<I: IntoIterator<Item = (Interval, i64)>>(iter: I) -> Self {
+        let mut map = RangeMap::new();
+        for (source, destination_start) in iter {
+            map.insert(source, destination_start);
+        }
+        map
+    }
+}
+
+//----------
+// Tests
+//----------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The seed-to-soil map of the example of day 05.
+    fn seed_to_soil() -> RangeMap {
+        [(Interval::with_len(98, 2), 50), (Interval::with_len(50, 48), 52)]
+            .into_iter()
+            .collect()
+    }
+
+    #[test]
+    fn test_lookup() {
+        let map = seed_to_soil();
+        assert_eq!(map.lookup(79), 81);
+        assert_eq!(map.lookup(14), 14);
+        assert_eq!(map.lookup(98), 50);
+        assert_eq!(map.lookup(99), 51);
+        assert_eq!(map.lookup(100), 100);
+        assert_eq!(map.entries()[0].source, Interval::new(50, 98));
+    }
+
+    #[test]
+    fn test_preimages() {
+        let map = seed_to_soil();
+        assert_eq!(map.preimages(81), [79]);
+        assert_eq!(map.preimages(14), [14]);
+        assert_eq!(map.preimages(50), [98]);
+        // values inside a source range are not their own preimages
+        assert_eq!(map.preimages(52), [50]);
+        assert_eq!(map.preimages(98), [96]);
+        assert_eq!(map.preimages(100), [100]);
+
+        // a value outside every range can also be the image of a value inside one
+        let shift: RangeMap = [(Interval::with_len(0, 10), 20)].into_iter().collect();
+        assert_eq!(shift.preimages(25), [5, 25]);
+        assert_eq!(shift.preimages(5), Vec::<i64>::new());
+
+        // every value maps back onto one of its preimages
+        for value in 0..120 {
+            for source in map.preimages(value) {
+                assert_eq!(map.lookup(source), value);
+            }
+        }
+    }
+
+    #[test]
+    fn test_lookup_range() {
+        let map = seed_to_soil();
+        assert_eq!(
+            map.lookup_range(Interval::new(40, 105)),
+            [
+                Interval::new(40, 50),
+                Interval::new(52, 100),
+                Interval::new(50, 52),
+                Interval::new(100, 105)
+            ]
+        );
+        assert_eq!(map.lookup_range(Interval::new(60, 70)), [Interval::new(62, 72)]);
+        assert!(map.lookup_range(Interval::new(5, 5)).is_empty());
+    }
+
+    #[test]
+    fn test_compose() {
+        let first = seed_to_soil();
+        let second: RangeMap = [
+            (Interval::with_len(15, 37), 0),
+            (Interval::with_len(52, 2), 37),
+            (Interval::with_len(0, 15), 39),
+        ]
+        .into_iter()
+        .collect();
+
+        let composed = first.compose(&second);
+        for value in -5..120 {
+            assert_eq!(
+                composed.lookup(value),
+                second.lookup(first.lookup(value)),
+                "value {value}"
+            );
+        }
+
+        // composing with the inverse gives the identity
+        let inverse: RangeMap = [(Interval::new(50, 52), 98), (Interval::new(52, 100), 50)]
+            .into_iter()
+            .collect();
+        assert_eq!(first.compose(&inverse), RangeMap::new());
+    }
+
+    #[test]
+    #[should_panic(expected = "destination overflows")]
+    fn test_destination_overflow() {
+        RangeMap::new().insert(Interval::with_len(0, 10), i64::MAX - 5);
+    }
+
+    #[test]
+    #[should_panic(expected = "overlapping")]
+    fn test_overlap() {
+        let mut map = seed_to_soil();
+        map.insert(Interval::new(90, 99), 0);
+    }
+}
@@ -0,0 +1,84 @@
+//! Xorshift pseudo-random number generator.
+//!
+//! Good enough to shuffle the edges of a graph or to generate synthetic inputs, with no statistical or cryptographic
+//! guarantees. A generator created with [`Rng::new`] always yields the same sequence, so its results can be reproduced.
+
+use std::{
+    collections::hash_map::RandomState,
+    hash::{BuildHasher, Hasher},
+};
+
+//----------
+// Structs
+//----------
+
+/// Xorshift pseudo-random number generator.
+#[derive(Clone, Debug)]
+pub struct Rng(u64);
+
+//----------
+// Implementations
+//----------
+
+impl Rng {
+    /// Creates a generator seeded with `seed`, which must not be 0.
+    pub fn new(seed: u64) -> Rng {
+        assert_ne!(seed, 0, "xorshift needs a non-zero seed");
+        Rng(seed)
+    }
+
+    /// Creates a generator seeded with the random keys of the standard library hash maps.
+    pub fn from_entropy() -> Rng {
+        Rng::new(RandomState::new().build_hasher().finish() | 1)
+    }
+
+    /// Returns the next number of the sequence, never 0, advancing the generator.
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    /// Returns a number in `0..n`, which must not be empty.
+    pub fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+
+    /// Returns a random element of `slice`, which must not be empty.
+    pub fn pick<'a, T>(&mut self, slice: &'a [T]) -> &'a T {
+        &slice[self.below(slice.len())]
+    }
+
+    /// Shuffles `slice` with the Fisher-Yates algorithm.
+    pub fn shuffle<T>(&mut self, slice: &mut [T]) {
+        for i in (1..slice.len()).rev() {
+            let j = self.below(i + 1);
+            slice.swap(i, j);
+        }
+    }
+}
+
+//----------
+// Tests
+//----------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rng() {
+        let (mut a, mut b) = (Rng::new(7), Rng::new(7));
+        let values: Vec<_> = (0..100).map(|_| a.below(10)).collect();
+        assert!(values.iter().all(|&v| v < 10));
+        assert!((0..10).all(|v| values.contains(&v)));
+        assert_eq!(values, (0..100).map(|_| b.below(10)).collect::<Vec<_>>());
+
+        let mut slice: Vec<_> = (0..20).collect();
+        a.shuffle(&mut slice);
+        assert_ne!(slice, (0..20).collect::<Vec<_>>());
+        slice.sort();
+        assert_eq!(slice, (0..20).collect::<Vec<_>>());
+    }
+}
@@ -0,0 +1,190 @@
+//! Byte scanning helpers for the hot loops that search lines for digits or specific characters.
+//!
+//! With the `simd` feature (which needs a nightly compiler for `std::simd`), the input is compared 32 bytes at a time
+//! and the first match is taken from the resulting bit mask. Without it, the plain iterator searches are used. Both
+//! paths return the same results.
+
+use std::ops::Range;
+
+//----------
+// Structs
+//----------
+
+/// Class of bytes searched for.
+#[derive(Clone, Copy)]
+enum Class {
+    Byte(u8),
+    Digit,
+    NonDigit,
+}
+
+/// Iterator over the runs of consecutive ASCII digits of a byte slice, returned by `find_digits`.
+pub struct Digits<'a> {
+    haystack: &'a [u8],
+    pos: usize,
+}
+
+//----------
+// Implementations
+//----------
+
+impl Class {
+    fn matches(self, b: u8) -> bool {
+        match self {
+            Class::Byte(needle) => b == needle,
+            Class::Digit => b.is_ascii_digit(),
+            Class::NonDigit => !b.is_ascii_digit(),
+        }
+    }
+}
+
+impl Iterator for Digits<'_> {
+    type Item = Range<usize>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let rest = &self.haystack[self.pos..];
+        let start = self.pos + position(rest, Class::Digit)?;
+        let len = position(&self.haystack[start..], Class::NonDigit).unwrap_or(self.haystack.len() - start);
+
+        self.pos = start + len;
+        Some(start..self.pos)
+    }
+}
+
+//----------
+// Helper methods
+//----------
+
+/// Returns the index of the first occurrence of `needle` in `haystack`.
+pub fn find_byte(haystack: &[u8], needle: u8) -> Option<usize> {
+    position(haystack, Class::Byte(needle))
+}
+
+/// Returns the index of the first ASCII digit of `haystack`.
+pub fn find_digit(haystack: &[u8]) -> Option<usize> {
+    position(haystack, Class::Digit)
+}
+
+/// Returns the index of the last ASCII digit of `haystack`.
+pub fn rfind_digit(haystack: &[u8]) -> Option<usize> {
+    rposition(haystack, Class::Digit)
+}
+
+/// Returns an iterator over the index ranges of the runs of consecutive ASCII digits of `haystack`, from left to right.
+///
+/// For example, `find_digits(b"467..114")` yields `0..3` and `5..8`.
+pub fn find_digits(haystack: &[u8]) -> Digits<'_> {
+    Digits { haystack, pos: 0 }
+}
+
+#[cfg(not(feature = "simd"))]
+fn position(haystack: &[u8], class: Class) -> Option<usize> {
+    haystack.iter().position(|&b| class.matches(b))
+}
+
+#[cfg(not(feature = "simd"))]
+fn rposition(haystack: &[u8], class: Class) -> Option<usize> {
+    haystack.iter().rposition(|&b| class.matches(b))
+}
+
+#[cfg(feature = "simd")]
+use simd::{position, rposition};
+
+#[cfg(feature = "simd")]
+mod simd {
+    use super::Class;
+    use std::simd::{
+        cmp::{SimdPartialEq, SimdPartialOrd},
+        Simd,
+    };
+
+    const LANES: usize = 32;
+    type Chunk = Simd<u8, LANES>;
+
+    impl Class {
+        /// Returns a bit mask with bit `i` set if byte `i` of the chunk is in the class.
+        fn mask(self, chunk: Chunk) -> u64 {
+            let digits = || chunk.simd_ge(Chunk::splat(b'0')) & chunk.simd_le(Chunk::splat(b'9'));
+            match self {
+                Class::Byte(needle) => chunk.simd_eq(Chunk::splat(needle)).to_bitmask(),
+                Class::Digit => digits().to_bitmask(),
+                Class::NonDigit => (!digits()).to_bitmask(),
+            }
+        }
+    }
+
+    pub(super) fn position(haystack: &[u8], class: Class) -> Option<usize> {
+        let (chunks, remainder) = haystack.as_chunks::<LANES>();
+        for (i, chunk) in chunks.iter().enumerate() {
+            let mask = class.mask(Chunk::from_array(*chunk));
+            if mask != 0 {
+                return Some(i * LANES + mask.trailing_zeros() as usize);
+            }
+        }
+
+        let offset = chunks.len() * LANES;
+        remainder.iter().position(|&b| class.matches(b)).map(|i| offset + i)
+    }
+
+    pub(super) fn rposition(haystack: &[u8], class: Class) -> Option<usize> {
+        let (remainder, chunks) = haystack.as_rchunks::<LANES>();
+        let offset = remainder.len();
+        for (i, chunk) in chunks.iter().enumerate().rev() {
+            let mask = class.mask(Chunk::from_array(*chunk));
+            if mask != 0 {
+                return Some(offset + i * LANES + (63 - mask.leading_zeros() as usize));
+            }
+        }
+
+        remainder.iter().rposition(|&b| class.matches(b))
+    }
+}
+
+//----------
+// Tests
+//----------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find() {
+        assert_eq!(find_byte(b"abc*def", b'*'), Some(3));
+        assert_eq!(find_byte(b"abc", b'*'), None);
+        assert_eq!(find_digit(b"pqr3stu8vwx"), Some(3));
+        assert_eq!(rfind_digit(b"pqr3stu8vwx"), Some(7));
+        assert_eq!(find_digit(b""), None);
+        assert_eq!(rfind_digit(b"treb"), None);
+
+        // long inputs, crossing the chunk boundaries of the simd path
+        let mut long = vec![b'.'; 100];
+        for i in [0, 31, 32, 33, 63, 64, 70, 95, 96, 99] {
+            long[i] = b'7';
+            assert_eq!(find_digit(&long), long.iter().position(u8::is_ascii_digit), "find {i}");
+            assert_eq!(
+                rfind_digit(&long),
+                long.iter().rposition(u8::is_ascii_digit),
+                "rfind {i}"
+            );
+            assert_eq!(find_byte(&long[1..], b'7'), long[1..].iter().position(|&b| b == b'7'));
+            long[i] = b'.';
+        }
+    }
+
+    #[test]
+    fn test_find_digits() {
+        let runs: Vec<_> = find_digits(b"467..114..").collect();
+        assert_eq!(runs, [0..3, 5..8]);
+
+        let mut runs = find_digits(b"...*12");
+        assert_eq!(runs.next(), Some(4..6));
+        assert_eq!(runs.next(), None);
+
+        let mut long = vec![b'.'; 80];
+        long[30..40].fill(b'1');
+        long[75..].fill(b'2');
+        assert_eq!(find_digits(&long).collect::<Vec<_>>(), [30..40, 75..80]);
+        assert_eq!(find_digits(b"..").count(), 0);
+    }
+}
@@ -0,0 +1,53 @@
+//! Binary search over monotonic predicates.
+//!
+//! Useful when the answer is the boundary between values that fail a condition and values that satisfy it, and
+//! computing it directly would require floating point math.
+
+use std::ops::Range;
+
+/// Returns the first value of `range` satisfying `pred`, or `range.end` if none does.
+///
+/// `pred` must be monotonic over the range: false for every value before the returned one, and true from it onwards,
+/// like the predicate of `slice::partition_point` negated. It is called `O(log(range.len()))` times.
+pub fn lower_bound(range: Range<u64>, mut pred: impl FnMut(u64) -> bool) -> u64 {
+    let (mut lo, mut hi) = (range.start, range.end.max(range.start));
+
+    while lo < hi {
+        let mid = lo + (hi - lo) / 2;
+        if pred(mid) {
+            hi = mid;
+        } else {
+            lo = mid + 1;
+        }
+    }
+
+    lo
+}
+
+//----------
+// Tests
+//----------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lower_bound() {
+        assert_eq!(lower_bound(0..100, |x| x * x >= 50), 8);
+        assert_eq!(lower_bound(0..100, |_| true), 0);
+        assert_eq!(lower_bound(0..100, |_| false), 100);
+        assert_eq!(lower_bound(5..5, |_| true), 5);
+
+        // the full range of u64 does not overflow
+        assert_eq!(lower_bound(0..u64::MAX, |x| x >= u64::MAX - 1), u64::MAX - 1);
+
+        // same result as the standard library's `partition_point`
+        let values = [1, 3, 3, 5, 8, 13];
+        let expected = values.partition_point(|&v| v < 5) as u64;
+        assert_eq!(
+            lower_bound(0..values.len() as u64, |i| values[i as usize] >= 5),
+            expected
+        );
+    }
+}
@@ -0,0 +1,181 @@
+//! Sparse two-dimensional grid, for maps that are mostly empty, too large to be stored densely or unbounded.
+//!
+//! Only occupied cells are stored, in a `HashMap` keyed by the signed lattice `Point`s of the `geometry` module, so
+//! cells may lie at any distance from the origin, in every direction. When parsed from text, `x` is the column and `y`
+//! the row, growing downwards like the `Position`s of the dense `Grid`.
+
+use crate::geometry::Point;
+use std::collections::{hash_map, HashMap};
+
+//----------
+// Structs
+//----------
+
+/// Two-dimensional grid storing only its occupied cells.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Point, T>,
+}
+
+//----------
+// Implementations
+//----------
+
+impl<T> SparseGrid<T> {
+    /// Creates an empty grid.
+    pub fn new() -> SparseGrid<T> {
+        SparseGrid { cells: HashMap::new() }
+    }
+
+    /// Parses a grid from a string, one row per line, with the first byte at the origin. `f` converts each byte into a
+    /// cell, returning `None` for empty cells, which are not stored.
+    pub fn from_str_with(s: &str, mut f: impl FnMut(u8) -> Option<T>) -> SparseGrid<T> {
+        let cells = s
+            .lines()
+            .zip(0..)
+            .flat_map(|(line, y)| line.bytes().zip(0..).map(move |(b, x)| ((x, y), b)))
+            .filter_map(|(point, b)| f(b).map(|cell| (point, cell)))
+            .collect();
+
+        SparseGrid { cells }
+    }
+
+    /// Returns the number of occupied cells.
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// Checks whether there are no occupied cells.
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// Stores `cell` at `point`, returning the previous value, if any.
+    pub fn insert(&mut self, point: Point, cell: T) -> Option<T> {
+        self.cells.insert(point, cell)
+    }
+
+    /// Removes the cell at `point`, returning it.
+    pub fn remove(&mut self, point: Point) -> Option<T> {
+        self.cells.remove(&point)
+    }
+
+    /// Returns a reference to the cell at `point`, or `None` if it is empty.
+    pub fn get(&self, point: Point) -> Option<&T> {
+        self.cells.get(&point)
+    }
+
+    /// Returns a mutable reference to the cell at `point`, or `None` if it is empty.
+    pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+        self.cells.get_mut(&point)
+    }
+
+    /// Checks whether the cell at `point` is occupied.
+    pub fn contains(&self, point: Point) -> bool {
+        self.cells.contains_key(&point)
+    }
+
+    /// Returns an iterator over the occupied cells and their points, in arbitrary order.
+    pub fn iter(&self) -> hash_map::Iter<'_, Point, T> {
+        self.cells.iter()
+    }
+
+    /// Returns an iterator over the points of the occupied cells, in arbitrary order.
+    pub fn points(&self) -> impl Iterator<Item = Point> + '_ {
+        self.cells.keys().copied()
+    }
+
+    /// Returns the corners with the smallest and the largest coordinates (both inclusive) of the smallest rectangle
+    /// containing all occupied cells, or `None` if the grid is empty.
+    pub fn bounding_box(&self) -> Option<(Point, Point)> {
+        let mut points = self.points();
+        let first = points.next()?;
+
+        Some(points.fold((first, first), |(min, max), (x, y)| {
+            ((min.0.min(x), min.1.min(y)), (max.0.max(x), max.1.max(y)))
+        }))
+    }
+
+    /// Returns the occupied cells lying inside the rectangle from `min` to `max` (both inclusive).
+    pub fn within(&self, min: Point, max: Point) -> impl Iterator<Item = (&Point, &T)> {
+        self.cells
+            .iter()
+            .filter(move |((x, y), _)| (min.0..=max.0).contains(x) && (min.1..=max.1).contains(y))
+    }
+}
+
+//----------
+// Traits
+//----------
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        SparseGrid::new()
+    }
+}
+
+impl<T> FromIterator<(Point, T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = (Point, T)>>(iter: I) -> Self {
+        SparseGrid {
+            cells: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a SparseGrid<T> {
+    type Item = (&'a Point, &'a T);
+    type IntoIter = hash_map::Iter<'a, Point, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+//----------
+// Tests
+//----------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tests() {
+        let input = "...#......
+.......#..
+#.........";
+        let mut grid = SparseGrid::from_str_with(input, |b| (b == b'#').then_some(()));
+
+        assert_eq!(grid.len(), 3);
+        assert!(grid.contains((7, 1)));
+        assert!(!grid.contains((6, 1)));
+        assert_eq!(grid.bounding_box(), Some(((0, 0), (7, 2))));
+        assert_eq!(grid.within((1, 0), (9, 1)).count(), 2);
+
+        // cells far away from the origin, in any direction, cost no more than nearby ones
+        grid.insert((-1_000_000_000_000, 1_000_000_000_000), ());
+        grid.insert((i64::MAX, i64::MIN), ());
+        assert_eq!(grid.len(), 5);
+        assert_eq!(
+            grid.bounding_box(),
+            Some(((-1_000_000_000_000, i64::MIN), (i64::MAX, 1_000_000_000_000)))
+        );
+        assert_eq!(grid.within((i64::MIN, i64::MIN), (-1, i64::MAX)).count(), 1);
+
+        assert_eq!(grid.remove((i64::MAX, i64::MIN)), Some(()));
+        assert_eq!(grid.remove((i64::MAX, i64::MIN)), None);
+        assert_eq!(SparseGrid::<()>::new().bounding_box(), None);
+    }
+
+    #[test]
+    fn test_from_iter() {
+        let mut grid: SparseGrid<u8> = [((-2, 3), b'a'), ((0, -1), b'b')].into_iter().collect();
+        *grid.get_mut((0, -1)).unwrap() = b'c';
+
+        assert_eq!(grid.get((0, -1)), Some(&b'c'));
+        assert_eq!(grid.get((0, 0)), None);
+        let mut cells: Vec<_> = (&grid).into_iter().map(|(&p, &b)| (p, b)).collect();
+        cells.sort();
+        assert_eq!(cells, [((-2, 3), b'a'), ((0, -1), b'c')]);
+    }
+}
@@ -0,0 +1,809 @@
+//! Synthetic puzzle inputs, to measure the solvers beyond the size of the real inputs.
+//!
+//! [`generate`] writes a valid input for a day, from a seeded [`Rng`], so the same day, size and seed always give the
+//! same input. The size is the number of lines or records for most days, and the side of the map for the days whose
+//! input is a square map. The exceptions follow the limits of the solvers:
+//!
+//! - Day 05: the number of entries of each map, and of seed ranges;
+//! - Day 06: the number of races, at most four, as part 2 joins their numbers into a single race;
+//! - Day 08: the number of directions, the loop of every ghost being a multiple of it;
+//! - Day 20: the number of bits of each of the four counters, at most 15, as part 2 gives up after 2^16 presses;
+//! - Day 21: half the side of the map, which is `2 * size + 1` (131 for a size of 65, like the real inputs);
+//! - Day 23: the length of the corridors between the junctions, which are always laid out in a 6x6 lattice, as the
+//!   longest hike is exponential in their number;
+//! - Day 25: the number of components, split in two groups.
+//!
+//! Sizes too small for a day to have an answer are raised to the smallest one that has: a side of 5 for days 10 and 17
+//! (the ultra crucible moves at least four blocks), a side of 2 for day 11 (two galaxies), 2 bits for day 20, corridors
+//! of 2 tiles for day 23, 3 hailstones for day 24 (to find the rock) and 10 components for day 25.
+//!
+//! It is run with `aoc2023 gen-input <day> <size> [--seed <n>]`.
+
+use crate::rng::Rng;
+use std::fmt::Write;
+
+/// Lowercase letters, for the labels of the inputs.
+const LOWERCASE: &[u8] = b"abcdefghijklmnopqrstuvwxyz";
+
+/// Returns a synthetic input for `day` of the given `size`, or `None` if there is no such day.
+pub fn generate(day: u8, size: usize, rng: &mut Rng) -> Option<String> {
+    let size = size.max(1);
+    let input = match day {
+        1 => day01(rng, size),
+        2 => day02(rng, size),
+        3 => day03(rng, size),
+        4 => day04(rng, size),
+        5 => day05(rng, size),
+        6 => day06(rng, size),
+        7 => day07(rng, size),
+        8 => day08(rng, size),
+        9 => day09(rng, size),
+        10 => day10(rng, size),
+        11 => day11(rng, size),
+        12 => day12(rng, size),
+        13 => day13(rng, size),
+        14 => day14(rng, size),
+        15 => day15(rng, size),
+        16 => day16(rng, size),
+        17 => day17(rng, size),
+        18 => day18(rng, size),
+        19 => day19(rng, size),
+        20 => day20(rng, size),
+        21 => day21(rng, size),
+        22 => day22(rng, size),
+        23 => day23(rng, size),
+        24 => day24(rng, size),
+        25 => day25(rng, size),
+        _ => return None,
+    };
+    Some(input)
+}
+
+//----------
+// Days
+//----------
+
+/// Lines of letters and spelled digits, with at least one numeric digit.
+fn day01(rng: &mut Rng, size: usize) -> String {
+    const WORDS: [&str; 9] = ["one", "two", "three", "four", "five", "six", "seven", "eight", "nine"];
+
+    let mut out = String::new();
+    for _ in 0..size {
+        let mut line = String::new();
+        for _ in 0..1 + rng.below(8) {
+            match rng.below(3) {
+                0 => line.push_str(WORDS[rng.below(WORDS.len())]),
+                1 => line.push(char::from(b'0' + rng.below(10) as u8)),
+                _ => line.push(char::from(*rng.pick(LOWERCASE))),
+            }
+        }
+        let digit = char::from(b'1' + rng.below(9) as u8);
+        line.insert(rng.below(line.len() + 1), digit);
+        writeln!(out, "{line}").unwrap();
+    }
+    out
+}
+
+/// Games of a few sets of red, green and blue cubes.
+fn day02(rng: &mut Rng, size: usize) -> String {
+    const COLORS: [&str; 3] = ["red", "green", "blue"];
+
+    let mut out = String::new();
+    for id in 1..=size {
+        let sets: Vec<String> = (0..1 + rng.below(6))
+            .map(|_| {
+                let mut colors = COLORS;
+                rng.shuffle(&mut colors);
+                let n_colors = 1 + rng.below(3);
+                let cubes: Vec<_> = colors[..n_colors]
+                    .iter()
+                    .map(|color| format!("{} {color}", 1 + rng.below(20)))
+                    .collect();
+                cubes.join(", ")
+            })
+            .collect();
+        writeln!(out, "Game {id}: {}", sets.join("; ")).unwrap();
+    }
+    out
+}
+
+/// A schematic 140 columns wide, with numbers of up to three digits and symbols scattered around.
+fn day03(rng: &mut Rng, size: usize) -> String {
+    const WIDTH: usize = 140;
+    const SYMBOLS: &[u8] = b"*#+$/@=%&-";
+
+    let mut out = String::new();
+    for _ in 0..size {
+        let mut row = vec![b'.'; WIDTH];
+        let mut col = 0;
+        while col < WIDTH {
+            match rng.below(12) {
+                0 => {
+                    // a number, followed by at least one '.' so it does not merge with the next one
+                    let len = (1 + rng.below(3)).min(WIDTH - col);
+                    row[col] = b'1' + rng.below(9) as u8;
+                    for cell in &mut row[col + 1..col + len] {
+                        *cell = b'0' + rng.below(10) as u8;
+                    }
+                    col += len + 1;
+                }
+                1 => {
+                    row[col] = *rng.pick(SYMBOLS);
+                    col += 1;
+                }
+                _ => col += 1,
+            }
+        }
+        writeln!(out, "{}", String::from_utf8(row).unwrap()).unwrap();
+    }
+    out
+}
+
+/// Cards of 10 winning numbers and 25 numbers. Only a quarter of the cards have matches, and at most three, so the
+/// copies won in part 2 do not grow exponentially with the number of cards.
+fn day04(rng: &mut Rng, size: usize) -> String {
+    let mut numbers: Vec<usize> = (1..100).collect();
+
+    let mut out = String::new();
+    for id in 1..=size {
+        rng.shuffle(&mut numbers);
+        let matches = if rng.below(4) == 0 { 1 + rng.below(3) } else { 0 };
+        let (winning, others) = numbers.split_at(10);
+        let mut yours: Vec<usize> = winning[..matches]
+            .iter()
+            .chain(&others[..25 - matches])
+            .copied()
+            .collect();
+        rng.shuffle(&mut yours);
+
+        let list = |numbers: &[usize]| numbers.iter().map(|n| format!("{n:>2}")).collect::<Vec<_>>().join(" ");
+        writeln!(out, "Card {id:>3}: {} | {}", list(winning), list(&yours)).unwrap();
+    }
+    out
+}
+
+/// Seed ranges and seven maps, each a permutation of the same `[0, 2^32)` range split in `size` pieces.
+fn day05(rng: &mut Rng, size: usize) -> String {
+    const CATEGORIES: [&str; 8] = [
+        "seed",
+        "soil",
+        "fertilizer",
+        "water",
+        "light",
+        "temperature",
+        "humidity",
+        "location",
+    ];
+    const END: u64 = 1 << 32;
+
+    let mut out = String::from("seeds:");
+    for _ in 0..size {
+        let start = rng.next_u64() % (END / 2);
+        write!(out, " {start} {}", 1 + rng.next_u64() % (END / 2 / size as u64)).unwrap();
+    }
+    out.push('\n');
+
+    for map in CATEGORIES.windows(2) {
+        let mut cuts: Vec<u64> = (1..size).map(|_| 1 + rng.next_u64() % (END - 1)).collect();
+        cuts.extend([0, END]);
+        cuts.sort_unstable();
+        cuts.dedup();
+        let mut pieces: Vec<(u64, u64)> = cuts.windows(2).map(|w| (w[0], w[1] - w[0])).collect();
+        rng.shuffle(&mut pieces);
+
+        write!(out, "\n{}-to-{} map:\n", map[0], map[1]).unwrap();
+        let mut destination = 0;
+        for (source, len) in pieces {
+            writeln!(out, "{destination} {source} {len}").unwrap();
+            destination += len;
+        }
+    }
+    out
+}
+
+/// Up to four races with two digit times and records that can be beaten.
+fn day06(rng: &mut Rng, size: usize) -> String {
+    let times: Vec<u64> = (0..size.min(4)).map(|_| 10 + rng.below(90) as u64).collect();
+    // the longest distance is `time² / 4`, rounded down
+    let records: Vec<u64> = times
+        .iter()
+        .map(|t| t * t / 8 + rng.next_u64() % (t * t / 4 - t * t / 8))
+        .collect();
+
+    let list = |numbers: &[u64]| numbers.iter().map(|n| format!("{n:>5}")).collect::<String>();
+    format!("Time:    {}\nDistance:{}\n", list(&times), list(&records))
+}
+
+/// Hands of five cards, with bids up to 1000.
+fn day07(rng: &mut Rng, size: usize) -> String {
+    const CARDS: &[u8] = b"23456789TJQKA";
+
+    let mut out = String::new();
+    for _ in 0..size {
+        let hand: String = (0..5).map(|_| char::from(*rng.pick(CARDS))).collect();
+        writeln!(out, "{hand} {}", 1 + rng.below(1000)).unwrap();
+    }
+    out
+}
+
+/// `size` directions and six ghosts, each walking a loop of a prime multiple of the number of directions, with its Z
+/// node at the end. The first ghost starts at `AAA` and loops at `ZZZ`.
+fn day08(rng: &mut Rng, size: usize) -> String {
+    const PRIMES: [usize; 10] = [43, 47, 53, 59, 61, 67, 71, 73, 79, 83];
+    // B to Y, so the inner nodes do not end with A or Z
+    const INNER: &[u8] = b"BCDEFGHIJKLMNOPQRSTUVWXY";
+
+    let directions: String = (0..size).map(|_| *rng.pick(&['L', 'R'])).collect();
+    let mut primes = PRIMES;
+    rng.shuffle(&mut primes);
+
+    let mut out = format!("{directions}\n\n");
+    let mut next_inner = 0;
+    for (ghost, prime) in primes[..6].iter().enumerate() {
+        let (start, end) = match ghost {
+            0 => ("AAA".to_owned(), "ZZZ".to_owned()),
+            _ => (
+                format!("{}A", label(ghost, INNER, 2)),
+                format!("{}Z", label(ghost, INNER, 2)),
+            ),
+        };
+        // start -> inner nodes -> end, and back to the first inner node
+        let inner: Vec<String> = (0..size * prime - 1).map(|i| label(next_inner + i, INNER, 3)).collect();
+        next_inner += inner.len();
+
+        let mut node = &start;
+        for next in inner.iter().chain([&end]) {
+            writeln!(out, "{node} = ({next}, {next})").unwrap();
+            node = next;
+        }
+        let first = inner.first().unwrap_or(&end);
+        writeln!(out, "{end} = ({first}, {first})").unwrap();
+    }
+    out
+}
+
+/// Histories of 21 values of polynomials up to degree 20, so the differences go up to 20 levels deep.
+fn day09(rng: &mut Rng, size: usize) -> String {
+    const LEN: usize = 21;
+
+    let mut out = String::new();
+    for _ in 0..size {
+        // coefficients in the binomial basis: the value at `n` is the sum of `coefs[k] * C(n, k)`
+        let degree = rng.below(LEN);
+        let coefs: Vec<i64> = (0..=degree).map(|_| rng.below(19) as i64 - 9).collect();
+        let values: Vec<String> = (0..LEN as i64)
+            .map(|n| {
+                let mut binomial = 1;
+                let mut value = 0;
+                for (k, coef) in coefs.iter().enumerate() {
+                    value += coef * binomial;
+                    binomial = binomial * (n - k as i64) / (k as i64 + 1);
+                }
+                value.to_string()
+            })
+            .collect();
+        writeln!(out, "{}", values.join(" ")).unwrap();
+    }
+    out
+}
+
+/// A square map of random pipes, with a rectangular main loop one tile away from the borders, starting at its top left
+/// corner.
+fn day10(rng: &mut Rng, size: usize) -> String {
+    const PIPES: &[u8] = b"|-LJ7F..";
+
+    let side = size.max(5);
+    let (first, last) = (1, side - 2);
+    let mut map: Vec<Vec<u8>> = (0..side)
+        .map(|_| (0..side).map(|_| *rng.pick(PIPES)).collect())
+        .collect();
+    for i in first..=last {
+        for (row, col, pipe) in [(first, i, b'-'), (last, i, b'-'), (i, first, b'|'), (i, last, b'|')] {
+            map[row][col] = pipe;
+        }
+    }
+    map[first][first] = b'S';
+    map[first][last] = b'7';
+    map[last][first] = b'L';
+    map[last][last] = b'J';
+    // no pipe outside of the loop may lead to the start
+    map[first - 1][first] = b'.';
+    map[first][first - 1] = b'.';
+
+    grid_to_string(&map)
+}
+
+/// A square image with galaxies scattered around and in two opposite corners, leaving about a tenth of its other rows
+/// and columns empty.
+fn day11(rng: &mut Rng, size: usize) -> String {
+    let side = size.max(2);
+    let empty =
+        |rng: &mut Rng| -> Vec<bool> { (0..side).map(|i| 0 < i && i < side - 1 && rng.below(10) == 0).collect() };
+    let (empty_rows, empty_cols) = (empty(rng), empty(rng));
+
+    let mut image: Vec<Vec<u8>> = (0..side)
+        .map(|row| {
+            (0..side)
+                .map(|col| match !empty_rows[row] && !empty_cols[col] && rng.below(50) == 0 {
+                    true => b'#',
+                    false => b'.',
+                })
+                .collect()
+        })
+        .collect();
+    image[0][0] = b'#';
+    image[side - 1][side - 1] = b'#';
+    grid_to_string(&image)
+}
+
+/// Rows of up to 20 springs, a third of them unknown.
+fn day12(rng: &mut Rng, size: usize) -> String {
+    let mut out = String::new();
+    for _ in 0..size {
+        let len = 4 + rng.below(17);
+        let mut springs: Vec<u8> = (0..len).map(|_| *rng.pick(b".##")).collect();
+        springs[rng.below(len)] = b'#';
+
+        let groups: Vec<String> = springs
+            .split(|&s| s == b'.')
+            .filter(|run| !run.is_empty())
+            .map(|run| run.len().to_string())
+            .collect();
+        for spring in &mut springs {
+            if rng.below(3) == 0 {
+                *spring = b'?';
+            }
+        }
+        writeln!(out, "{} {}", String::from_utf8(springs).unwrap(), groups.join(",")).unwrap();
+    }
+    out
+}
+
+/// Patterns with a single line of reflection along one of their edges, and a single smudge whose fix creates another
+/// one across the pattern.
+fn day13(rng: &mut Rng, size: usize) -> String {
+    let mut patterns = Vec::new();
+    while patterns.len() < size {
+        let (n_rows, n_cols) = (7 + rng.below(11), 7 + rng.below(11));
+        let random_row = |rng: &mut Rng| -> Vec<u8> { (0..n_cols).map(|_| *rng.pick(b".#")).collect() };
+
+        // the fixed pattern reflects across the line above row `line`, and also across the line above row 1
+        let line = 2 + rng.below(n_rows - 3);
+        let mut rows: Vec<Vec<u8>> = (0..n_rows).map(|_| random_row(rng)).collect();
+        rows[1] = rows[0].clone();
+        for i in 0..line.min(n_rows - line) {
+            rows[line + i] = rows[line - 1 - i].clone();
+        }
+        // the smudge, only breaking the reflection across `line`
+        let i = rng.below(line.min(n_rows - line));
+        let col = rng.below(n_cols);
+        rows[line + i][col] ^= b'.' ^ b'#';
+
+        if rng.below(2) == 0 {
+            rows.reverse();
+        }
+        if rng.below(2) == 0 {
+            rows = (0..n_cols)
+                .map(|col| rows.iter().map(|row| row[col]).collect())
+                .collect();
+        }
+        // random rows may reflect somewhere else by chance
+        if reflections(&rows, 0) == 1 && reflections(&rows, 1) == 1 {
+            patterns.push(grid_to_string(&rows));
+        }
+    }
+    patterns.join("\n")
+}
+
+/// A square platform with rounded rocks on a quarter of the tiles and cube rocks on a sixth.
+fn day14(rng: &mut Rng, size: usize) -> String {
+    const TILES: &[u8] = b"OOO##............";
+    random_grid(rng, size, TILES)
+}
+
+/// `size` steps over about `size / 4` labels, so lenses get replaced and removed.
+fn day15(rng: &mut Rng, size: usize) -> String {
+    let labels: Vec<String> = (0..size.div_ceil(4))
+        .map(|_| {
+            (0..2 + rng.below(5))
+                .map(|_| char::from(*rng.pick(LOWERCASE)))
+                .collect()
+        })
+        .collect();
+
+    let steps: Vec<String> = (0..size)
+        .map(|_| {
+            let label = rng.pick(&labels);
+            match rng.below(3) {
+                0 => format!("{label}-"),
+                _ => format!("{label}={}", 1 + rng.below(9)),
+            }
+        })
+        .collect();
+    steps.join(",") + "\n"
+}
+
+/// A square contraption with a mirror or splitter on a tenth of the tiles.
+fn day16(rng: &mut Rng, size: usize) -> String {
+    const TILES: &[u8] = b"/\\|-....................................";
+    random_grid(rng, size, TILES)
+}
+
+/// A square map of random heat losses, with a side of at least 5 so the ultra crucible of part 2 can reach the
+/// factory.
+fn day17(rng: &mut Rng, size: usize) -> String {
+    random_grid(rng, size.max(5), b"123456789")
+}
+
+/// A dig plan of `size` steps right and down, and back left and up, for both parts. The lengths of the steps of part 2
+/// keep the area of the lagoon within an `i64`.
+fn day18(rng: &mut Rng, size: usize) -> String {
+    const MAX_HEX: u64 = 0xFFFFF;
+    let max_step = (3_000_000_000 / size as u64).clamp(1, MAX_HEX);
+
+    let mut moves = Vec::new();
+    let (mut width, mut height) = ([0, 0], [0, 0]);
+    for _ in 0..size {
+        for (dir, total) in [(b'R', &mut width), (b'D', &mut height)] {
+            let steps = [1 + rng.next_u64() % 9, 1 + rng.next_u64() % max_step];
+            moves.push((dir, steps));
+            total[0] += steps[0];
+            total[1] += steps[1];
+        }
+    }
+    // the way back, in pieces short enough for five hexadecimal digits
+    for (dir, total) in [(b'L', width), (b'U', height)] {
+        let pieces = total[1].div_ceil(MAX_HEX);
+        for i in 0..pieces {
+            let piece = |total: u64| total / pieces + u64::from(i < total % pieces);
+            moves.push((dir, [piece(total[0]), piece(total[1])]));
+        }
+    }
+
+    let mut out = String::new();
+    for (dir, [steps, hex]) in moves {
+        let code = b"RDLU".iter().position(|&d| d == dir).unwrap();
+        writeln!(out, "{} {steps} (#{hex:05x}{code})", char::from(dir)).unwrap();
+    }
+    out
+}
+
+/// A tree of `size` workflows starting at `in`, and `size` parts.
+fn day19(rng: &mut Rng, size: usize) -> String {
+    let mut out = String::new();
+    let mut pending = vec!["in".to_owned()];
+    let mut n_workflows = 1;
+
+    while let Some(name) = pending.pop() {
+        let mut target = |rng: &mut Rng, pending: &mut Vec<String>, force: bool| {
+            if n_workflows < size && (force || rng.below(3) != 0) {
+                let next = label(n_workflows, LOWERCASE, 3);
+                n_workflows += 1;
+                pending.push(next.clone());
+                next
+            } else {
+                rng.pick(&["A", "R"]).to_string()
+            }
+        };
+
+        let mut rules = Vec::new();
+        for _ in 0..1 + rng.below(3) {
+            let category = rng.pick(&['x', 'm', 'a', 's']);
+            let op = rng.pick(&['<', '>']);
+            let rating = 1 + rng.below(4000);
+            rules.push(format!("{category}{op}{rating}:{}", target(rng, &mut pending, false)));
+        }
+        // keeps the tree growing until it has all its workflows
+        let force = pending.is_empty();
+        rules.push(target(rng, &mut pending, force));
+        writeln!(out, "{name}{{{}}}", rules.join(",")).unwrap();
+    }
+
+    out.push('\n');
+    for _ in 0..size {
+        let [x, m, a, s] = [(); 4].map(|_| 1 + rng.below(4000));
+        writeln!(out, "{{x={x},m={m},a={a},s={s}}}").unwrap();
+    }
+    out
+}
+
+/// Four binary counters of `size` flip-flops, at most 15, like the real inputs: a conjunction resets each counter when
+/// it reaches its period, and `rx` receives a low pulse when all of them do.
+fn day20(rng: &mut Rng, size: usize) -> String {
+    // without x, so no label is `rx`
+    const LETTERS: &[u8] = b"abcdefghijklmnopqrstuvwyz";
+    const COUNTERS: usize = 4;
+
+    let bits = size.clamp(2, 15);
+    let mut ids = 0..;
+    let mut name = || label(ids.next().unwrap(), LETTERS, 2);
+    let feeder = name();
+
+    let mut lines = Vec::new();
+    let mut firsts = Vec::new();
+    for _ in 0..COUNTERS {
+        // odd, with the top bit set
+        let period = (1 << (bits - 1)) | rng.below(1 << (bits - 1)) | 1;
+        let flip_flops: Vec<String> = (0..bits).map(|_| name()).collect();
+        let (hub, inverter) = (name(), name());
+
+        let mut hub_outputs = vec![flip_flops[0].clone()];
+        for (bit, flip_flop) in flip_flops.iter().enumerate() {
+            let mut outputs: Vec<&str> = flip_flops.get(bit + 1).map(String::as_str).into_iter().collect();
+            match (period >> bit) & 1 {
+                1 => outputs.push(&hub),
+                _ => hub_outputs.push(flip_flop.clone()),
+            }
+            lines.push(format!("%{flip_flop} -> {}", outputs.join(", ")));
+        }
+        hub_outputs.push(inverter.clone());
+        lines.push(format!("&{hub} -> {}", hub_outputs.join(", ")));
+        lines.push(format!("&{inverter} -> {feeder}"));
+        firsts.push(flip_flops[0].clone());
+    }
+    lines.push(format!("broadcaster -> {}", firsts.join(", ")));
+    lines.push(format!("&{feeder} -> rx"));
+    rng.shuffle(&mut lines);
+
+    lines.join("\n") + "\n"
+}
+
+/// A square map of side `2 * size + 1` with rocks on an eighth of the plots, and the start at the center of clear rows
+/// and columns through it and along the borders.
+fn day21(rng: &mut Rng, size: usize) -> String {
+    let side = 2 * size + 1;
+    let map: Vec<Vec<u8>> = (0..side)
+        .map(|row| {
+            (0..side)
+                .map(|col| {
+                    if row == size && col == size {
+                        b'S'
+                    } else if [0, size, side - 1].contains(&row) || [0, size, side - 1].contains(&col) {
+                        b'.'
+                    } else {
+                        *rng.pick(b"#.......")
+                    }
+                })
+                .collect()
+        })
+        .collect();
+    grid_to_string(&map)
+}
+
+/// A snapshot of `size` bricks of up to four cubes falling over a 10x10 area, in random order.
+fn day22(rng: &mut Rng, size: usize) -> String {
+    const SIDE: usize = 10;
+
+    // top of every column, so the bricks never overlap
+    let mut heights = [[0; SIDE]; SIDE];
+    let mut lines = Vec::new();
+    for _ in 0..size {
+        let axis = rng.below(3);
+        let len = 1 + rng.below(4);
+        let mut extent = [1; 3];
+        extent[axis] = len;
+        let (x, y) = (rng.below(SIDE + 1 - extent[0]), rng.below(SIDE + 1 - extent[1]));
+
+        let footprint = || (x..x + extent[0]).flat_map(|x| (y..y + extent[1]).map(move |y| (x, y)));
+        let z = footprint().map(|(x, y)| heights[x][y]).max().unwrap() + 1 + rng.below(3);
+        for (x, y) in footprint() {
+            heights[x][y] = z + extent[2] - 1;
+        }
+        let high = [x + extent[0] - 1, y + extent[1] - 1, z + extent[2] - 1];
+        lines.push(format!("{x},{y},{z}~{},{},{}", high[0], high[1], high[2]));
+    }
+    rng.shuffle(&mut lines);
+
+    lines.join("\n") + "\n"
+}
+
+/// A map of straight corridors of `size` tiles joining a 6x6 lattice of junctions, with slopes leading right and down
+/// out of every junction.
+fn day23(_rng: &mut Rng, size: usize) -> String {
+    const JUNCTIONS: usize = 6;
+
+    let spacing = size.max(2) + 1;
+    let side = (JUNCTIONS - 1) * spacing + 3;
+    let mut map = vec![vec![b'#'; side]; side];
+    map[0][1] = b'.';
+    map[side - 1][side - 2] = b'.';
+
+    for i in 0..JUNCTIONS {
+        for j in 0..JUNCTIONS {
+            let (row, col) = (1 + i * spacing, 1 + j * spacing);
+            map[row][col] = b'.';
+            if j + 1 < JUNCTIONS {
+                map[row][col + 1..col + spacing].fill(b'.');
+                map[row][col + 1] = b'>';
+            }
+            if i + 1 < JUNCTIONS {
+                for tiles in &mut map[row + 1..row + spacing] {
+                    tiles[col] = b'.';
+                }
+                map[row + 1][col] = b'v';
+            }
+        }
+    }
+    grid_to_string(&map)
+}
+
+/// `size` hailstones, at least 3 to find the rock of part 2, all hit by a rock thrown from around the test area of part
+/// 1.
+fn day24(rng: &mut Rng, size: usize) -> String {
+    let mut coordinate = |low: i64, high: i64| low + (rng.next_u64() % (high - low) as u64) as i64;
+    let rock = [0; 3].map(|_| coordinate(200_000_000_000_000, 400_000_000_000_000));
+    let rock_velocity = [0; 3].map(|_| coordinate(-300, 300));
+
+    let mut out = String::new();
+    for _ in 0..size.max(3) {
+        // the hailstone is where the rock is at time `t`, and moves at a different speed along every axis
+        let velocity = rock_velocity.map(|v| loop {
+            let velocity = coordinate(-300, 300);
+            if velocity != v && velocity != 0 {
+                break velocity;
+            }
+        });
+        let t = coordinate(100_000_000_000, 1_000_000_000_000);
+        let position: Vec<i64> = (0..3).map(|i| rock[i] + t * (rock_velocity[i] - velocity[i])).collect();
+        writeln!(
+            out,
+            "{}, {}, {} @ {}, {}, {}",
+            position[0], position[1], position[2], velocity[0], velocity[1], velocity[2]
+        )
+        .unwrap();
+    }
+    out
+}
+
+/// Two groups of components, of `size / 2` components each, joined by three wires. Every component is wired to the
+/// next two of its group, around a ring, so a group cannot be split by cutting less than four wires.
+fn day25(rng: &mut Rng, size: usize) -> String {
+    let group = (size / 2).max(5);
+    let mut names: Vec<String> = (0..2 * group).map(|i| label(i, LOWERCASE, 3)).collect();
+    rng.shuffle(&mut names);
+
+    let mut wires = vec![Vec::new(); 2 * group];
+    for start in [0, group] {
+        for i in 0..group {
+            for step in 1..=2 {
+                wires[start + i].push(start + (i + step) % group);
+            }
+            // extra wires inside of the group
+            if rng.below(2) == 0 {
+                let other = start + rng.below(group);
+                if other != start + i {
+                    wires[start + i].push(other);
+                }
+            }
+        }
+    }
+    let mut ends: Vec<usize> = (0..group).collect();
+    rng.shuffle(&mut ends);
+    for &i in &ends[..3] {
+        wires[i].push(group + rng.below(group));
+    }
+
+    let mut out = String::new();
+    for (component, wired) in wires.iter().enumerate() {
+        let wired: Vec<&str> = wired.iter().map(|&c| names[c].as_str()).collect();
+        writeln!(out, "{}: {}", names[component], wired.join(" ")).unwrap();
+    }
+    out
+}
+
+//----------
+// Helper methods
+//----------
+
+/// Returns the label of number `n` with the digits of `alphabet`, at least `len` of them.
+fn label(mut n: usize, alphabet: &[u8], len: usize) -> String {
+    let mut label = String::new();
+    while label.len() < len || n > 0 {
+        label.push(char::from(alphabet[n % alphabet.len()]));
+        n /= alphabet.len();
+    }
+    label
+}
+
+/// Returns a square grid of `side` tiles picked from `tiles`.
+fn random_grid(rng: &mut Rng, side: usize, tiles: &[u8]) -> String {
+    let grid: Vec<Vec<u8>> = (0..side)
+        .map(|_| (0..side).map(|_| *rng.pick(tiles)).collect())
+        .collect();
+    grid_to_string(&grid)
+}
+
+/// Writes the rows of a grid, one per line.
+fn grid_to_string(rows: &[Vec<u8>]) -> String {
+    let mut out = String::new();
+    for row in rows {
+        writeln!(out, "{}", String::from_utf8_lossy(row)).unwrap();
+    }
+    out
+}
+
+/// Returns the number of lines of reflection of a pattern, between rows or columns, with exactly `smudges` tiles
+/// differing from their reflection.
+fn reflections(rows: &[Vec<u8>], smudges: usize) -> usize {
+    let differences = |rows: &[Vec<u8>], line: usize| -> usize {
+        let (above, below) = rows.split_at(line);
+        above
+            .iter()
+            .rev()
+            .zip(below)
+            .map(|(a, b)| a.iter().zip(b).filter(|(a, b)| a != b).count())
+            .sum()
+    };
+    let columns: Vec<Vec<u8>> = (0..rows[0].len())
+        .map(|col| rows.iter().map(|row| row[col]).collect())
+        .collect();
+
+    [rows, &columns[..]]
+        .iter()
+        .map(|pattern| {
+            (1..pattern.len())
+                .filter(|&line| differences(pattern, line) == smudges)
+                .count()
+        })
+        .sum()
+}
+
+//----------
+// Tests
+//----------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runner;
+
+    /// Checks that the inputs of every day of `size` are the same for the same seed, and solved.
+    fn check_generate(size: usize) {
+        for day in 1..=25 {
+            let input = generate(day, size, &mut Rng::new(day as u64)).unwrap();
+            assert_eq!(input, generate(day, size, &mut Rng::new(day as u64)).unwrap());
+            if let Err(e) = runner(day).unwrap()(&input) {
+                panic!("day {day}, size {size}: {e}\n{input}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_generate_small() {
+        check_generate(1);
+        check_generate(3);
+        assert_eq!(generate(26, 10, &mut Rng::new(1)), None);
+    }
+
+    #[test]
+    fn test_generate_large() {
+        check_generate(100);
+    }
+
+    #[test]
+    fn test_minimum_sizes() {
+        let mut rng = Rng::new(2023);
+        let races = generate(6, 2, &mut rng).unwrap();
+        assert_eq!(races.lines().next().unwrap().split_whitespace().count(), 3);
+        assert_eq!(
+            generate(6, 9, &mut rng)
+                .unwrap()
+                .lines()
+                .next()
+                .unwrap()
+                .split_whitespace()
+                .count(),
+            5
+        );
+        assert_eq!(generate(11, 1, &mut rng).unwrap(), "#.\n.#\n");
+        assert_eq!(generate(17, 1, &mut rng).unwrap().lines().count(), 5);
+        assert_eq!(generate(24, 1, &mut rng).unwrap().lines().count(), 3);
+    }
+
+    #[test]
+    fn test_label() {
+        assert_eq!(label(0, LOWERCASE, 3), "aaa");
+        assert_eq!(label(27, LOWERCASE, 2), "bb");
+        assert_eq!(label(26 * 26, LOWERCASE, 2), "aab");
+    }
+}
@@ -0,0 +1,104 @@
+//! Test harness shared by the days.
+//!
+//! The `aoc_test!` macro generates one test function per expected answer, so a day only lists its sample input, the
+//! functions solving it and the answers from the puzzle text.
+//!
+//! Property tests are written with `proptest`, and `corrupt` builds the invalid inputs of their round trips.
+
+#[cfg(test)]
+use proptest::sample::Index;
+
+/// Generates test functions checking the answers of solver functions for an input.
+///
+/// Each test is written as `name: solver => expected`, where `solver` is a function or closure taking the input as a
+/// `&str` and returning a `Result`, whose value must be `expected`. The input is either a sample given as a string
+/// (`sample: "..."`), or the real input of a day loaded from the inputs directory (`real: 5`), `inputs/` or the one set
+/// in `AOC_INPUT_DIR`; tests on real inputs are `#[ignore]`d, and run with `cargo test -- --ignored`.
+///
+/// ```
+/// use aoc2023::{aoc_test, error::AocError};
+///
+/// fn count_lines(input: &str) -> Result<usize, AocError> {
+///     Ok(input.lines().count())
+/// }
+///
+/// aoc_test! {
+///     sample: "a\nb\nc",
+///     pt1_sample: count_lines => 3,
+///     pt2_sample: |input: &str| count_lines(input).map(|n| n + 2) => 5,
+/// }
+///
+/// aoc_test! {
+///     real: 1,
+///     pt1_real: count_lines => 1000,
+/// }
+/// ```
+#[macro_export]
+macro_rules! aoc_test {
+    (sample: $sample:expr, $($name:ident: $solver:expr => $expected:expr),+ $(,)?) => {
+        $(
+            #[test]
+            fn $name() {
+                assert_eq!(($solver)($sample).unwrap(), $expected);
+            }
+        )+
+    };
+
+    (real: $day:literal, $($name:ident: $solver:expr => $expected:expr),+ $(,)?) => {
+        $(
+            #[test]
+            #[ignore = "needs the real input in the inputs directory"]
+            fn $name() {
+                let input = $crate::input::load_from(&$crate::input::input_dir(), $day).unwrap();
+                assert_eq!(($solver)(input.as_str()).unwrap(), $expected);
+            }
+        )+
+    };
+}
+
+/// Returns `s` with the character at `index`, or an appended one if it is empty, replaced by `c`. Used by the
+/// round-trip property tests to check that an invalid character makes parsing fail.
+#[cfg(test)]
+pub(crate) fn corrupt(s: &str, index: Index, c: char) -> String {
+    let mut chars: Vec<char> = s.chars().collect();
+    match chars.len() {
+        0 => chars.push(c),
+        len => chars[index.index(len)] = c,
+    }
+    chars.into_iter().collect()
+}
+
+//----------
+// Tests
+//----------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::AocError;
+    use proptest::prelude::*;
+
+    fn words(input: &str) -> Result<usize, AocError> {
+        Ok(input.split_whitespace().count())
+    }
+
+    aoc_test! {
+        sample: "seed-to-soil map:\n50 98 2",
+        test_function: words => 5,
+        test_closure: |input: &str| Ok::<_, AocError>(input.lines().count()) => 2,
+    }
+
+    aoc_test! {
+        real: 1,
+        test_real: |input: &str| Ok::<_, AocError>(input.is_empty()) => false,
+    }
+
+    proptest! {
+        #[test]
+        fn test_corrupt(s in "[ab]{0,4}", index in any::<Index>()) {
+            let corrupted = corrupt(&s, index, 'x');
+            prop_assert_eq!(corrupted.len(), s.len().max(1));
+            prop_assert_eq!(corrupted.matches('x').count(), 1);
+        }
+    }
+}
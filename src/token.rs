@@ -0,0 +1,272 @@
+//! Lightweight tokenizer keeping the byte range of every token, so parsers can report the exact line and column of an
+//! unexpected token.
+//!
+//! Whitespace (including newlines) separates tokens and is otherwise ignored. A token is one of:
+//! - a word: a letter or `_` followed by letters, digits or `_`;
+//! - a number: digits, optionally preceded by a `-` sign;
+//! - a symbol: any other single character.
+
+use crate::error::AocError;
+use std::{fmt::Display, ops::Range, str::FromStr};
+
+//----------
+// Structs and Enums
+//----------
+
+/// Kinds of tokens.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TokenKind {
+    Word,
+    Number,
+    Symbol,
+}
+
+/// A token and its location in the source.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Token<'a> {
+    pub kind: TokenKind,
+    pub text: &'a str,
+    /// Byte range of the token in the source.
+    pub span: Range<usize>,
+}
+
+/// Iterator over the tokens of a string, with helpers to consume the expected ones.
+#[derive(Clone, Debug)]
+pub struct Tokenizer<'a> {
+    source: &'a str,
+    pos: usize,
+}
+
+//----------
+// Implementations
+//----------
+
+impl<'a> Tokenizer<'a> {
+    /// Creates a tokenizer over `source`.
+    pub fn new(source: &'a str) -> Tokenizer<'a> {
+        Tokenizer { source, pos: 0 }
+    }
+
+    /// Returns the next token without consuming it.
+    pub fn peek(&self) -> Option<Token<'a>> {
+        self.clone().next()
+    }
+
+    /// Returns the kind of the next token without consuming it.
+    pub fn peek_kind(&self) -> Option<TokenKind> {
+        self.peek().map(|token| token.kind)
+    }
+
+    /// Checks whether all tokens have been consumed.
+    pub fn is_empty(&self) -> bool {
+        self.peek().is_none()
+    }
+
+    /// Consumes the next token if it is the symbol `c`, returning whether it was.
+    pub fn eat_symbol(&mut self, c: char) -> bool {
+        let is_symbol =
+            matches!(self.peek(), Some(token) if token.kind == TokenKind::Symbol && token.text.starts_with(c));
+        if is_symbol {
+            self.next();
+        }
+        is_symbol
+    }
+
+    /// Consumes the symbol `c`, or returns an error pointing to the unexpected token.
+    pub fn expect_symbol(&mut self, c: char) -> Result<(), AocError> {
+        if self.eat_symbol(c) {
+            Ok(())
+        } else {
+            Err(self.unexpected(&format!("`{c}`")))
+        }
+    }
+
+    /// Consumes the word `word`, or returns an error pointing to the unexpected token.
+    pub fn expect_keyword(&mut self, word: &str) -> Result<(), AocError> {
+        match self.peek() {
+            Some(token) if token.kind == TokenKind::Word && token.text == word => {
+                self.next();
+                Ok(())
+            }
+            _ => Err(self.unexpected(&format!("`{word}`"))),
+        }
+    }
+
+    /// Consumes and returns the next token if it is a word.
+    pub fn expect_word(&mut self) -> Result<&'a str, AocError> {
+        self.expect_kind(TokenKind::Word, "a word").map(|token| token.text)
+    }
+
+    /// Consumes the next token if it is a number, and parses it as `T`.
+    pub fn number<T>(&mut self) -> Result<T, AocError>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        let token = self.expect_kind(TokenKind::Number, "a number")?;
+        token
+            .text
+            .parse()
+            .map_err(|e| AocError::parse_at_offset(self.source, token.span.start, e))
+    }
+
+    /// Consumes the next token, whatever its kind, and parses it as `T`. Errors raised by `T` are moved to the
+    /// position of the token.
+    pub fn parse_next<T>(&mut self) -> Result<T, AocError>
+    where
+        T: FromStr,
+        T::Err: Into<AocError>,
+    {
+        let token = self.next().ok_or_else(|| self.unexpected("a token"))?;
+        token
+            .text
+            .parse()
+            .map_err(|e: T::Err| e.into().at_offset(self.source, token.span.start))
+    }
+
+    /// Returns an error if there are tokens left.
+    pub fn expect_end(&self) -> Result<(), AocError> {
+        match self.peek() {
+            Some(_) => Err(self.unexpected("the end of the input")),
+            None => Ok(()),
+        }
+    }
+
+    /// Consumes the next token if it is of the given `kind`, described as `expected` in the error otherwise.
+    fn expect_kind(&mut self, kind: TokenKind, expected: &str) -> Result<Token<'a>, AocError> {
+        match self.peek() {
+            Some(token) if token.kind == kind => {
+                self.next();
+                Ok(token)
+            }
+            _ => Err(self.unexpected(expected)),
+        }
+    }
+
+    /// Returns an error pointing to the next token (or to the end of the input), which was not the `expected` one.
+    fn unexpected(&self, expected: &str) -> AocError {
+        match self.peek() {
+            Some(token) => AocError::parse_at_offset(
+                self.source,
+                token.span.start,
+                format!("Unexpected token `{}`, expected {}.", token.text, expected),
+            ),
+            None => AocError::parse_at_offset(
+                self.source,
+                self.source.len(),
+                format!("Unexpected end of input, expected {expected}."),
+            ),
+        }
+    }
+}
+
+impl<'a> Iterator for Tokenizer<'a> {
+    type Item = Token<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let rest = &self.source[self.pos..];
+        let start = self.pos + (rest.len() - rest.trim_start().len());
+        let rest = &self.source[start..];
+        let first = rest.chars().next()?;
+
+        let is_digit = |c: char| c.is_ascii_digit();
+        let is_word = |c: char| c.is_alphanumeric() || c == '_';
+        let (kind, len) = if first.is_alphabetic() || first == '_' {
+            (TokenKind::Word, rest.find(|c| !is_word(c)).unwrap_or(rest.len()))
+        } else if first.is_ascii_digit() || (first == '-' && rest[1..].starts_with(is_digit)) {
+            let digits = &rest[1..];
+            (
+                TokenKind::Number,
+                1 + digits.find(|c| !is_digit(c)).unwrap_or(digits.len()),
+            )
+        } else {
+            (TokenKind::Symbol, first.len_utf8())
+        };
+
+        self.pos = start + len;
+        Some(Token {
+            kind,
+            text: &self.source[start..self.pos],
+            span: start..self.pos,
+        })
+    }
+}
+
+//----------
+// Tests
+//----------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use TokenKind::*;
+
+    #[test]
+    fn test_tokens() {
+        let tokens: Vec<_> = Tokenizer::new("Game 12: -3 blue,\n  x_1-y")
+            .map(|t| (t.kind, t.text))
+            .collect();
+        assert_eq!(
+            tokens,
+            [
+                (Word, "Game"),
+                (Number, "12"),
+                (Symbol, ":"),
+                (Number, "-3"),
+                (Word, "blue"),
+                (Symbol, ","),
+                (Word, "x_1"),
+                (Symbol, "-"),
+                (Word, "y")
+            ]
+        );
+
+        let token = Tokenizer::new("  ab 7").nth(1).unwrap();
+        assert_eq!(token.span, 5..6);
+        assert!(Tokenizer::new(" \n ").is_empty());
+    }
+
+    #[test]
+    fn test_expect() {
+        let mut tokens = Tokenizer::new("Card 1: 41 | 83");
+        tokens.expect_keyword("Card").unwrap();
+        assert_eq!(tokens.number::<u32>().unwrap(), 1);
+        assert!(tokens.eat_symbol(':'));
+        assert!(!tokens.eat_symbol('|'));
+        assert_eq!(tokens.number::<u8>().unwrap(), 41);
+        tokens.expect_symbol('|').unwrap();
+        assert_eq!(tokens.peek_kind(), Some(Number));
+        assert!(tokens.expect_end().is_err());
+        assert_eq!(tokens.number::<u8>().unwrap(), 83);
+        tokens.expect_end().unwrap();
+    }
+
+    #[test]
+    fn test_errors() {
+        let source = "seeds: 1 2\n\nmap:\n1 x 3";
+        let mut tokens = Tokenizer::new(source);
+        tokens.nth(6);
+        let err = tokens.number::<u64>().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Line 4, column 3: Unexpected token `x`, expected a number."
+        );
+
+        let mut tokens = Tokenizer::new("a 300");
+        tokens.expect_word().unwrap();
+        let err = tokens.number::<u8>().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Line 1, column 3: number too large to fit in target type"
+        );
+
+        let mut tokens = Tokenizer::new("a:");
+        tokens.expect_keyword("a").unwrap();
+        tokens.expect_symbol(':').unwrap();
+        let err = tokens.expect_symbol(';').unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Line 1, column 3: Unexpected end of input, expected `;`."
+        );
+    }
+}
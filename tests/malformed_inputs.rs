@@ -0,0 +1,61 @@
+//! Checks that every day returns an error, instead of panicking, on empty, degenerate and random inputs. The inputs of
+//! the `solve` fuzz target in `fuzz/` that once crashed a day are kept here, so they run without `cargo fuzz`.
+
+use aoc2023::runner;
+use proptest::prelude::*;
+use std::panic;
+
+/// Inputs without any meaningful content.
+const DEGENERATE: [&str; 8] = ["", "\n", "\n\n", "\r\n", " ", "#", "0", "S"];
+
+/// Inputs found by fuzzing that used to crash a day, by overflowing or by allocating too much memory.
+const CRASHES: [(u8, &str); 8] = [
+    (2, "Game 1: 4294967295 red, 4294967295 green, 4294967295 blue"),
+    (3, "99999999999*1"),
+    (6, "Time: 99999999999\nDistance: 1"),
+    (7, "AAAAA 18446744073709551615\nKKKKK 18446744073709551615"),
+    (9, "0 9223372036854775807 -9223372036854775808"),
+    (16, ""),
+    (21, "S..\n..."),
+    (22, "0,0,1~0,99999999999,1"),
+];
+
+/// Characters of the random inputs, covering the digits, separators and tiles of every day.
+const ALPHABET: [char; 24] = [
+    '0', '1', '5', '9', '.', '#', '?', 'S', 'O', '|', '-', '/', '\\', '7', 'L', ',', ':', '~', '@', '=', ' ', '\n',
+    'a', 'Z',
+];
+
+#[test]
+fn degenerate_inputs() {
+    for day in 1..=25 {
+        for input in DEGENERATE {
+            check_no_panic(day, input);
+        }
+    }
+}
+
+#[test]
+fn fuzzed_inputs() {
+    for (day, input) in CRASHES {
+        check_no_panic(day, input);
+    }
+}
+
+proptest! {
+    #[test]
+    fn random_inputs(input in prop::collection::vec(prop::sample::select(&ALPHABET[..]), 0..=40)) {
+        let input: String = input.into_iter().collect();
+        for day in 1..=25 {
+            check_no_panic(day, &input);
+        }
+    }
+}
+
+/// Runs `day` on `input`, failing with the day and the input if it panics.
+fn check_no_panic(day: u8, input: &str) {
+    let run = runner(day).unwrap();
+    if panic::catch_unwind(|| run(input)).is_err() {
+        panic!("day {day} panicked on the input {input:?}");
+    }
+}
@@ -0,0 +1,140 @@
+//! Checks the answers of every day on the real inputs.
+//!
+//! The inputs are read from `inputs/` (or the directory set in `AOC_INPUT_DIR`), and the expected answers from
+//! `answers.toml`, with a table per day:
+//!
+//! ```toml
+//! [day01]
+//! part1 = 54601
+//! part2 = 54078
+//! ```
+//!
+//! Neither is committed, so the test passes without checking anything when they are absent, and days without an input
+//! file or a table of answers are skipped.
+
+use aoc2023::{input, runner};
+use std::{collections::HashMap, fs, path::Path};
+
+/// File with the expected answers, relative to the working directory.
+const ANSWERS_FILE: &str = "answers.toml";
+
+/// Expected answers of each day, as they are printed.
+type Answers = HashMap<u8, [Option<String>; 2]>;
+
+#[test]
+fn real_inputs() {
+    let dir = input::input_dir();
+    let Ok(toml) = fs::read_to_string(ANSWERS_FILE) else {
+        eprintln!("No `{ANSWERS_FILE}`, skipping the real inputs.");
+        return;
+    };
+    if !dir.is_dir() {
+        eprintln!("No `{}` directory, skipping the real inputs.", dir.display());
+        return;
+    }
+
+    let answers = parse_answers(&toml);
+    let mut failures = Vec::new();
+    for day in 1..=25 {
+        let (Some(run), Some(expected)) = (runner(day), answers.get(&day)) else {
+            continue;
+        };
+        if !input_exists(&dir, day) {
+            eprintln!("Day {day:02}: no input, skipped.");
+            continue;
+        }
+
+        let input = input::load_from(&dir, day).unwrap();
+        match run(&input) {
+            Ok(found) => {
+                for (part, (found, expected)) in found.iter().zip(expected).enumerate() {
+                    let Some(expected) = expected else {
+                        continue;
+                    };
+                    if found.to_string() != *expected {
+                        failures.push(format!(
+                            "Day {day:02}, part {}: found {found}, expected {expected}",
+                            part + 1
+                        ));
+                    }
+                }
+            }
+            Err(e) => failures.push(e.to_string()),
+        }
+    }
+
+    assert!(failures.is_empty(), "wrong answers:\n{}", failures.join("\n"));
+}
+
+/// Checks whether the input file of `day` exists in `dir`.
+fn input_exists(dir: &Path, day: u8) -> bool {
+    dir.join(format!("day{day:02}")).is_file()
+}
+
+/// Parses the subset of TOML used by the answers file: `[dayNN]` tables with `part1` and `part2` keys, whose values are
+/// integers or basic strings without escapes. Comments and blank lines are ignored.
+fn parse_answers(toml: &str) -> Answers {
+    let mut answers = Answers::new();
+    let mut day = None;
+
+    for (i, line) in toml.lines().enumerate() {
+        let line = strip_comment(line).trim();
+        if line.is_empty() {
+            continue;
+        }
+        let fail = |msg| -> ! { invalid_line(i + 1, msg) };
+
+        if let Some(table) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            let n = table.trim().strip_prefix("day").and_then(|n| n.parse().ok());
+            day = Some(n.unwrap_or_else(|| fail("expected a `[dayNN]` table")));
+            answers.entry(day.unwrap()).or_default();
+            continue;
+        }
+
+        let Some((key, value)) = line.split_once('=') else {
+            fail("expected a `key = value` pair");
+        };
+        let part = match key.trim() {
+            "part1" => 0,
+            "part2" => 1,
+            _ => fail("expected the key `part1` or `part2`"),
+        };
+        let value = value.trim();
+        let value = value
+            .strip_prefix('"')
+            .and_then(|v| v.strip_suffix('"'))
+            .unwrap_or(value);
+        let Some(day) = day else {
+            fail("answer outside of a day table");
+        };
+        answers.get_mut(&day).unwrap()[part] = Some(value.to_owned());
+    }
+
+    answers
+}
+
+/// Returns `line` without its comment, which starts at the first `#` outside of a string.
+fn strip_comment(line: &str) -> &str {
+    let mut in_string = false;
+    for (i, c) in line.char_indices() {
+        match c {
+            '"' => in_string = !in_string,
+            '#' if !in_string => return &line[..i],
+            _ => {}
+        }
+    }
+    line
+}
+
+/// Panics with the error of line `n` of the answers file.
+fn invalid_line(n: usize, msg: &str) -> ! {
+    panic!("{ANSWERS_FILE}, line {n}: {msg}")
+}
+
+#[test]
+fn answers_file() {
+    let toml = "# answers\n[day01]\npart1 = 54601 # checked\n\n[day14]\npart2 = \"#.O#\" # the grid\n";
+    let answers = parse_answers(toml);
+    assert_eq!(answers[&1], [Some("54601".to_owned()), None]);
+    assert_eq!(answers[&14], [None, Some("#.O#".to_owned())]);
+}